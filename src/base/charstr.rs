@@ -25,8 +25,9 @@
 
 use super::cmp::CanonicalOrd;
 use super::octets::{
-    Compose, EmptyBuilder, FromBuilder, IntoBuilder, OctetsBuilder,
-    OctetsFrom, OctetsRef, Parse, ParseError, Parser, ShortBuf,
+    Compose, EmptyBuilder, FormError, FromBuilder, IntoBuilder,
+    OctetsBuilder, OctetsFrom, OctetsRef, Parse, ParseError, Parser,
+    ShortBuf,
 };
 use super::str::{BadSymbol, Symbol, SymbolError};
 #[cfg(feature = "master")]
@@ -82,7 +83,7 @@ impl<Octets: ?Sized> CharStr<Octets> {
     /// Creates a character string from octets without length check.
     ///
     /// As this can break the guarantees made by the type, it is unsafe.
-    unsafe fn from_octets_unchecked(octets: Octets) -> Self
+    pub(crate) unsafe fn from_octets_unchecked(octets: Octets) -> Self
     where
         Octets: Sized,
     {
@@ -327,10 +328,7 @@ impl<T: AsRef<[u8]> + ?Sized> hash::Hash for CharStr<T> {
 
 impl<Ref: OctetsRef> Parse<Ref> for CharStr<Ref::Range> {
     fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
-        let len = parser.parse_u8()? as usize;
-        parser
-            .parse_octets(len)
-            .map(|bytes| unsafe { Self::from_octets_unchecked(bytes) })
+        parser.parse_char_str()
     }
 
     fn skip(parser: &mut Parser<Ref>) -> Result<(), ParseError> {
@@ -633,6 +631,16 @@ impl<'a> Iterator for Iter<'a> {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct CharStrError;
 
+//--- From
+
+impl From<CharStrError> for ParseError {
+    fn from(_: CharStrError) -> ParseError {
+        ParseError::Form(FormError::new("illegal character string"))
+    }
+}
+
+//--- Display and Error
+
 impl fmt::Display for CharStrError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str("illegal character string")
@@ -96,3 +96,41 @@ pub trait CanonicalOrd<Rhs: ?Sized = Self> {
         )
     }
 }
+
+//------------ constant_time_eq ----------------------------------------------
+
+/// Compares two octet sequences in constant time.
+///
+/// The time it takes to compare the two sequences only depends on their
+/// length but not on their content. This should be used instead of `==`
+/// whenever secret values such as cookies or MACs are compared in order to
+/// not reveal how much of a guessed value was correct via timing.
+///
+/// If the two sequences differ in length, the function returns `false`
+/// right away. The length is not considered secret.
+pub fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    let res = left
+        .iter()
+        .zip(right)
+        .fold(0u8, |res, (left, right)| res | (left ^ right));
+
+    // Read the result through a volatile pointer so the compiler can’t
+    // turn the loop above into one that stops early.
+    unsafe { core::ptr::read_volatile(&res) == 0 }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn constant_time_eq() {
+        assert!(super::constant_time_eq(b"", b""));
+        assert!(super::constant_time_eq(b"foobar", b"foobar"));
+        assert!(!super::constant_time_eq(b"foobar", b"foobaz"));
+        assert!(!super::constant_time_eq(b"foobar", b"fooba"));
+    }
+}
@@ -57,3 +57,36 @@ int_enum! {
 }
 
 int_enum_str_with_prefix!(Class, "CLASS", b"CLASS", u16, "unknown class");
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::string::ToString;
+
+    #[test]
+    fn generic_class() {
+        assert_eq!(Class::from_str("CLASS255").unwrap(), Class::Any);
+        assert_eq!(Class::from_str("CLASS42").unwrap(), Class::Int(42));
+        assert_eq!(Class::from_str("class1").unwrap(), Class::In);
+        assert_eq!(Class::from_str("CLASS255").unwrap().to_string(), "*");
+        assert_eq!(
+            Class::from_str("CLASS42").unwrap().to_string(),
+            "CLASS42"
+        );
+        assert_eq!(Class::from_str("class1").unwrap().to_string(), "IN");
+        assert_eq!(Class::from_bytes(b"CLASS255"), Some(Class::Any));
+        assert_eq!(Class::from_bytes(b"CLASS42"), Some(Class::Int(42)));
+        assert_eq!(Class::from_bytes(b"class1"), Some(Class::In));
+        assert!(Class::from_str("CLASS").is_err());
+        assert!(Class::from_bytes(b"CLASS").is_none());
+        assert!(Class::from_str("CLASS+1").is_err());
+        assert!(Class::from_bytes(b"CLASS+1").is_none());
+        assert!(Class::from_str("CLASS65536").is_err());
+        assert!(Class::from_bytes(b"CLASS65536").is_none());
+        assert!(Class::from_str("CLASSX").is_err());
+        assert!(Class::from_bytes(b"CLASSX").is_none());
+    }
+}
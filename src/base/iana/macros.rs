@@ -47,6 +47,23 @@ macro_rules! int_enum {
                 None
             }
 
+            /// Returns whether the value has a named variant.
+            ///
+            /// This is also true if the value of a named variant is hidden
+            /// in an `Int` variant.
+            pub fn is_known(self) -> bool {
+                self.to_mnemonic().is_some()
+            }
+
+            /// Returns an iterator over all values with a named variant.
+            ///
+            /// The values are returned in the order of their definition.
+            /// The catch-all `Int` variant is not included.
+            pub fn iter_known() -> impl Iterator<Item = Self> {
+                const KNOWN: &[$ianatype] = &[ $( $ianatype::$variant ),* ];
+                KNOWN.iter().copied()
+            }
+
             /// Returns the mnemonic for this value if there is one.
             ///
             /// This will also return a mnemonic if a well-defined variant
@@ -299,7 +316,7 @@ macro_rules! int_enum_str_with_decimal {
                     Some(res) => Ok(res),
                     None => {
                         if let Ok(res) = s.parse() {
-                            Ok($ianatype::Int(res))
+                            Ok($ianatype::from_int(res))
                         } else {
                             Err(FromStrError)
                         }
@@ -361,7 +378,9 @@ macro_rules! int_enum_str_with_prefix {
                         return None;
                     }
                     let (l, r) = bytes.split_at($u8_prefix.len());
-                    if !l.eq_ignore_ascii_case($u8_prefix) {
+                    if !l.eq_ignore_ascii_case($u8_prefix)
+                        || !r.iter().all(u8::is_ascii_digit)
+                    {
                         return None;
                     }
                     let r = match core::str::from_utf8(r) {
@@ -386,7 +405,9 @@ macro_rules! int_enum_str_with_prefix {
                             s.char_indices().nth($str_prefix.len())
                         {
                             let (l, r) = s.split_at(n);
-                            if l.eq_ignore_ascii_case($str_prefix) {
+                            if l.eq_ignore_ascii_case($str_prefix)
+                                && r.bytes().all(|ch| ch.is_ascii_digit())
+                            {
                                 let value = match r.parse() {
                                     Ok(x) => x,
                                     Err(..) => return Err(FromStrError),
@@ -171,3 +171,19 @@ int_enum! {
 }
 
 int_enum_str_with_decimal!(OptionCode, u16, "unknown option code");
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iter_known() {
+        assert!(OptionCode::iter_known().any(|c| c == OptionCode::Padding));
+        assert!(OptionCode::iter_known().all(|c| match c {
+            OptionCode::Int(_) => false,
+            c => c.to_mnemonic().is_some(),
+        }));
+    }
+}
@@ -18,7 +18,7 @@
 //!
 #![allow(clippy::upper_case_acronyms)]
 
-use core::{cmp, fmt, hash};
+use core::{cmp, fmt, hash, str};
 
 //------------ Rcode --------------------------------------------------------
 
@@ -324,7 +324,8 @@ impl hash::Hash for Rcode {
 /// Because of this, we decided to have separate types.
 ///
 /// The values for all three response code types are defined in
-/// the [IANA DNS RCODEs] registry. This type is complete as of 2019-01-28.
+/// the [IANA DNS RCODEs] registry. All values registered for use with
+/// OPT records have their own variant.
 ///
 /// [Rcode]: enum.Rcode.html
 /// [`TsigRcode`]: enum.TsigRcode.html
@@ -459,8 +460,66 @@ pub enum OptRcode {
     /// [RFC 6891]: https://tools.ietf.org/html/rfc6891
     BadVers,
 
-    // XXX We will not define the values from the TSIG and TKEY RFCs,
-    //     unless are used in OPT records, too?
+    /// Key not recognized.
+    ///
+    /// The server did not recognize the key used for generating the
+    /// signature.
+    ///
+    /// Defined in [RFC 2845].
+    ///
+    /// [RFC 2845]: https://tools.ietf.org/html/rfc2845
+    BadKey,
+
+    /// Signature out of time window.
+    ///
+    /// The server time was outside the time interval specified by the
+    /// request.
+    ///
+    /// Defined in [RFC 2845].
+    ///
+    /// [RFC 2845]: https://tools.ietf.org/html/rfc2845
+    BadTime,
+
+    /// Bad TKEY mode.
+    ///
+    /// The mode field in a TKEY resource record contained a mode not
+    /// supported by the server.
+    ///
+    /// Defined in [RFC 2930].
+    ///
+    /// [RFC 2930]: https://tools.ietf.org/html/rfc2930
+    BadMode,
+
+    /// Duplicate key name.
+    ///
+    /// In TKEY records, when establishing a new key, the name used already
+    /// exists at the server or when deleting a key, a key of this name does
+    /// not exist.
+    ///
+    /// Defined in [RFC 2930].
+    ///
+    /// [RFC 2930]: https://tools.ietf.org/html/rfc2930
+    BadName,
+
+    /// Algorithm not supported.
+    ///
+    /// The value is defined in [RFC 2930] but never actually explained.
+    /// Presumably, it will be returned when the algorithm field of a TKEY
+    /// record contains a value not supported by the server.
+    ///
+    /// [RFC 2930]: https://tools.ietf.org/html/rfc2930
+    BadAlg,
+
+    /// Bad truncation.
+    ///
+    /// A TSIG record was received with a MAC too short for the local
+    /// policy in force.
+    ///
+    /// Defined in [RFC 4635].
+    ///
+    /// [RFC 4635]: https://tools.ietf.org/html/rfc4635
+    BadTrunc,
+
     /// Bad or missing server cookie.
     ///
     /// The request contained a COOKIE option either without a server cookie
@@ -498,6 +557,12 @@ impl OptRcode {
             9 => NotAuth,
             10 => NotZone,
             16 => BadVers,
+            17 => BadKey,
+            18 => BadTime,
+            19 => BadMode,
+            20 => BadName,
+            21 => BadAlg,
+            22 => BadTrunc,
             23 => BadCookie,
             value => Int(value),
         }
@@ -520,20 +585,34 @@ impl OptRcode {
             NotAuth => 9,
             NotZone => 10,
             BadVers => 16,
+            BadKey => 17,
+            BadTime => 18,
+            BadMode => 19,
+            BadName => 20,
+            BadAlg => 21,
+            BadTrunc => 22,
             BadCookie => 23,
-            Int(value) => value & 0x0F,
+            Int(value) => value & 0x0FFF,
         }
     }
 
     /// Creates an extended rcode value from its parts.
+    ///
+    /// The `rcode` provides the lower four bits as transmitted in the
+    /// message header while `ext` provides the upper eight bits as
+    /// transmitted in the OPT record.
     pub fn from_parts(rcode: Rcode, ext: u8) -> OptRcode {
         OptRcode::from_int(u16::from(ext) << 4 | u16::from(rcode.to_int()))
     }
 
     /// Returns the two parts of an extended rcode value.
+    ///
+    /// The first element is the rcode to be placed into the message header
+    /// and the second element is the octet to be placed into the OPT
+    /// record.
     pub fn to_parts(self) -> (Rcode, u8) {
         let res = self.to_int();
-        (Rcode::from_int(res as u8), (res >> 8) as u8)
+        (Rcode::from_int((res & 0x0F) as u8), (res >> 4) as u8)
     }
 
     /// Returns the rcode part of the extended rcode.
@@ -586,6 +665,12 @@ impl fmt::Display for OptRcode {
             NotAuth => "NOAUTH".fmt(f),
             NotZone => "NOTZONE".fmt(f),
             BadVers => "BADVER".fmt(f),
+            BadKey => "BADKEY".fmt(f),
+            BadTime => "BADTIME".fmt(f),
+            BadMode => "BADMODE".fmt(f),
+            BadName => "BADNAME".fmt(f),
+            BadAlg => "BADALG".fmt(f),
+            BadTrunc => "BADTRUNC".fmt(f),
             BadCookie => "BADCOOKIE".fmt(f),
             Int(i) => match OptRcode::from_int(i) {
                 Int(i) => i.fmt(f),
@@ -595,6 +680,115 @@ impl fmt::Display for OptRcode {
     }
 }
 
+//--- FromStr
+
+impl str::FromStr for OptRcode {
+    type Err = FromStrError;
+
+    /// Creates an extended rcode from its mnemonic or decimal value.
+    ///
+    /// Mnemonics are recognized ignoring case. In addition to the
+    /// mnemonics produced by the `Display` impl, the registry names
+    /// `BADVERS` and `NOTAUTH` are accepted, too.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::OptRcode::*;
+
+        const MNEMONICS: &[(&str, OptRcode)] = &[
+            ("NOERROR", NoError),
+            ("FORMERR", FormErr),
+            ("SERVFAIL", ServFail),
+            ("NXDOMAIN", NXDomain),
+            ("NOTIMP", NotImp),
+            ("REFUSED", Refused),
+            ("YXDOMAIN", YXDomain),
+            ("YXRRSET", YXRRSet),
+            ("NXRRSET", NXRRSet),
+            ("NOAUTH", NotAuth),
+            ("NOTAUTH", NotAuth),
+            ("NOTZONE", NotZone),
+            ("BADVER", BadVers),
+            ("BADVERS", BadVers),
+            ("BADKEY", BadKey),
+            ("BADTIME", BadTime),
+            ("BADMODE", BadMode),
+            ("BADNAME", BadName),
+            ("BADALG", BadAlg),
+            ("BADTRUNC", BadTrunc),
+            ("BADCOOKIE", BadCookie),
+        ];
+
+        for &(mnemonic, value) in MNEMONICS {
+            if s.eq_ignore_ascii_case(mnemonic) {
+                return Ok(value);
+            }
+        }
+        if !s.is_empty() && s.bytes().all(|ch| ch.is_ascii_digit()) {
+            if let Ok(value) = s.parse::<u16>() {
+                if value <= 0x0FFF {
+                    return Ok(OptRcode::from_int(value));
+                }
+            }
+        }
+        Err(FromStrError)
+    }
+}
+
+//--- PartialEq and Eq
+
+impl cmp::PartialEq for OptRcode {
+    fn eq(&self, other: &OptRcode) -> bool {
+        self.to_int() == other.to_int()
+    }
+}
+
+impl cmp::PartialEq<u16> for OptRcode {
+    fn eq(&self, other: &u16) -> bool {
+        self.to_int() == *other
+    }
+}
+
+impl cmp::PartialEq<OptRcode> for u16 {
+    fn eq(&self, other: &OptRcode) -> bool {
+        *self == other.to_int()
+    }
+}
+
+impl cmp::Eq for OptRcode {}
+
+//--- PartialOrd and Ord
+
+impl cmp::PartialOrd for OptRcode {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::PartialOrd<u16> for OptRcode {
+    fn partial_cmp(&self, other: &u16) -> Option<cmp::Ordering> {
+        self.to_int().partial_cmp(other)
+    }
+}
+
+impl cmp::PartialOrd<OptRcode> for u16 {
+    fn partial_cmp(&self, other: &OptRcode) -> Option<cmp::Ordering> {
+        self.partial_cmp(&other.to_int())
+    }
+}
+
+impl cmp::Ord for OptRcode {
+    fn cmp(&self, other: &OptRcode) -> cmp::Ordering {
+        self.to_int().cmp(&other.to_int())
+    }
+}
+
+//--- Hash
+
+impl hash::Hash for OptRcode {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.to_int().hash(state)
+    }
+}
+
 //------------ TsigRcode ----------------------------------------------------
 
 int_enum! {
@@ -825,3 +1019,85 @@ impl From<OptRcode> for TsigRcode {
 }
 
 int_enum_str_with_decimal!(TsigRcode, u16, "unknown TSIG error");
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const REGISTERED: &[(OptRcode, u16, &str)] = &[
+        (OptRcode::NoError, 0, "NOERROR"),
+        (OptRcode::FormErr, 1, "FORMERR"),
+        (OptRcode::ServFail, 2, "SERVFAIL"),
+        (OptRcode::NXDomain, 3, "NXDOMAIN"),
+        (OptRcode::NotImp, 4, "NOTIMP"),
+        (OptRcode::Refused, 5, "REFUSED"),
+        (OptRcode::YXDomain, 6, "YXDOMAIN"),
+        (OptRcode::YXRRSet, 7, "YXRRSET"),
+        (OptRcode::NXRRSet, 8, "NXRRSET"),
+        (OptRcode::NotAuth, 9, "NOAUTH"),
+        (OptRcode::NotZone, 10, "NOTZONE"),
+        (OptRcode::BadVers, 16, "BADVER"),
+        (OptRcode::BadKey, 17, "BADKEY"),
+        (OptRcode::BadTime, 18, "BADTIME"),
+        (OptRcode::BadMode, 19, "BADMODE"),
+        (OptRcode::BadName, 20, "BADNAME"),
+        (OptRcode::BadAlg, 21, "BADALG"),
+        (OptRcode::BadTrunc, 22, "BADTRUNC"),
+        (OptRcode::BadCookie, 23, "BADCOOKIE"),
+    ];
+
+    #[test]
+    fn opt_rcode_parts() {
+        for &(rcode, value, _) in REGISTERED {
+            assert_eq!(rcode.to_int(), value);
+            assert_eq!(OptRcode::from_int(value), rcode);
+            let (header, ext) = rcode.to_parts();
+            assert_eq!(header.to_int(), (value & 0x0F) as u8);
+            assert_eq!(ext, (value >> 4) as u8);
+            assert_eq!(OptRcode::from_parts(header, ext), rcode);
+        }
+        assert_eq!(OptRcode::BadVers.to_parts(), (Rcode::NoError, 1));
+        assert_eq!(OptRcode::BadCookie.to_parts(), (Rcode::Int(7), 1));
+    }
+
+    #[test]
+    fn opt_rcode_int() {
+        assert_eq!(OptRcode::from_int(0x0FFF), OptRcode::Int(0x0FFF));
+        assert_eq!(OptRcode::Int(0x0FFF).to_int(), 0x0FFF);
+        assert_eq!(OptRcode::Int(0xF123).to_int(), 0x0123);
+        assert_eq!(OptRcode::Int(16), OptRcode::BadVers);
+        assert_eq!(
+            OptRcode::Int(0x0ABC).to_parts(),
+            (Rcode::Int(0x0C), 0xAB)
+        );
+        assert_eq!(
+            OptRcode::from_parts(Rcode::Int(0x0C), 0xAB),
+            OptRcode::Int(0x0ABC)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn opt_rcode_str() {
+        use std::string::ToString;
+
+        for &(rcode, value, mnemonic) in REGISTERED {
+            assert_eq!(rcode.to_string(), mnemonic);
+            assert_eq!(mnemonic.parse::<OptRcode>().unwrap(), rcode);
+            assert_eq!(
+                mnemonic.to_ascii_lowercase().parse::<OptRcode>().unwrap(),
+                rcode
+            );
+            assert_eq!(value.to_string().parse::<OptRcode>().unwrap(), rcode);
+        }
+        assert_eq!("BADVERS".parse::<OptRcode>().unwrap(), OptRcode::BadVers);
+        assert_eq!("NOTAUTH".parse::<OptRcode>().unwrap(), OptRcode::NotAuth);
+        assert_eq!(OptRcode::Int(3000).to_string(), "3000");
+        assert_eq!("3000".parse::<OptRcode>().unwrap(), OptRcode::Int(3000));
+        assert!("4096".parse::<OptRcode>().is_err());
+        assert!("+1".parse::<OptRcode>().is_err());
+        assert!("BADSIG".parse::<OptRcode>().is_err());
+    }
+}
@@ -299,6 +299,16 @@ int_enum! {
     /// See draft-wessels-dns-zone-digest.
     (Zonemd => 63, b"ZONEMD")
 
+    /// General-purpose service binding.
+    ///
+    /// See RFC 9460.
+    (Svcb => 64, b"SVCB")
+
+    /// Service binding for HTTPS.
+    ///
+    /// See RFC 9460.
+    (Https => 65, b"HTTPS")
+
     /// SPF.
     ///
     /// RFC 7208.
@@ -415,3 +425,47 @@ int_enum! {
 }
 
 int_enum_str_with_prefix!(Rtype, "TYPE", b"TYPE", u16, "unknown record type");
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::string::ToString;
+
+    #[test]
+    fn generic_type() {
+        assert_eq!(Rtype::from_str("TYPE65280").unwrap(), Rtype::Int(65280));
+        assert_eq!(Rtype::from_str("type1").unwrap(), Rtype::A);
+        assert_eq!(Rtype::from_str("TYPE1").unwrap(), Rtype::A);
+        assert_eq!(
+            Rtype::from_str("TYPE65280").unwrap().to_string(),
+            "TYPE65280"
+        );
+        assert_eq!(Rtype::from_str("type1").unwrap().to_string(), "A");
+        assert_eq!(Rtype::from_str("TYPE1").unwrap().to_string(), "A");
+        assert_eq!(Rtype::from_bytes(b"TYPE65280"), Some(Rtype::Int(65280)));
+        assert_eq!(Rtype::from_bytes(b"type1"), Some(Rtype::A));
+        assert_eq!(Rtype::from_bytes(b"TYPE1"), Some(Rtype::A));
+        assert!(Rtype::from_str("TYPE").is_err());
+        assert!(Rtype::from_bytes(b"TYPE").is_none());
+        assert!(Rtype::from_str("TYPE+1").is_err());
+        assert!(Rtype::from_bytes(b"TYPE+1").is_none());
+        assert!(Rtype::from_str("TYPE65536").is_err());
+        assert!(Rtype::from_bytes(b"TYPE65536").is_none());
+        assert!(Rtype::from_str("TYPEA").is_err());
+        assert!(Rtype::from_bytes(b"TYPEA").is_none());
+    }
+
+    #[test]
+    fn iter_known() {
+        assert!(Rtype::iter_known().any(|rtype| rtype == Rtype::Aaaa));
+        assert!(Rtype::iter_known().any(|rtype| rtype == Rtype::Opt));
+        assert!(Rtype::iter_known().all(|rtype| match rtype {
+            Rtype::Int(_) => false,
+            rtype => rtype.to_mnemonic().is_some(),
+        }));
+        assert_eq!(Rtype::iter_known().next(), Some(Rtype::A));
+    }
+}
@@ -117,3 +117,36 @@ int_enum! {
 }
 
 int_enum_str_with_decimal!(SecAlg, u8, "unknown algorithm");
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::string::ToString;
+
+    #[test]
+    fn sec_alg_str() {
+        assert_eq!(SecAlg::EcdsaP256Sha256.to_string(), "ECDSAP256SHA256");
+        assert_eq!(SecAlg::Int(13).to_string(), "ECDSAP256SHA256");
+        assert_eq!(SecAlg::from_int(99).to_string(), "99");
+
+        assert_eq!(
+            SecAlg::from_str("ECDSAP256SHA256").unwrap(),
+            SecAlg::EcdsaP256Sha256
+        );
+        assert_eq!(
+            SecAlg::from_str("ecdsap256sha256").unwrap(),
+            SecAlg::EcdsaP256Sha256
+        );
+        assert!(matches!(
+            SecAlg::from_str("13").unwrap(),
+            SecAlg::EcdsaP256Sha256
+        ));
+        assert_eq!(SecAlg::from_str("99").unwrap(), SecAlg::Int(99));
+        assert_eq!(SecAlg::from_str("99").unwrap().to_string(), "99");
+        assert!(SecAlg::from_str("256").is_err());
+        assert!(SecAlg::from_str("ECDSA").is_err());
+    }
+}
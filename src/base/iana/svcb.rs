@@ -0,0 +1,70 @@
+//! Service binding parameter keys.
+
+//------------ SvcParamKey ---------------------------------------------------
+
+int_enum! {
+    /// Service binding parameter keys.
+    ///
+    /// These keys identify the parameters of the SVCB and HTTPS record
+    /// types defined in [RFC 9460].
+    ///
+    /// For the currently registered values see the [IANA registration].
+    /// In presentation format, keys without a mnemonic are given as the
+    /// string `key` followed by the decimal value of the key.
+    ///
+    /// [RFC 9460]: https://tools.ietf.org/html/rfc9460
+    /// [IANA registration]: https://www.iana.org/assignments/dns-svcb/dns-svcb.xhtml#dns-svcparamkeys
+    =>
+    SvcParamKey, u16;
+
+    /// Keys that must be understood by a client to use the record.
+    (Mandatory => 0, b"mandatory")
+
+    /// Additional supported protocols.
+    (Alpn => 1, b"alpn")
+
+    /// No support for the default protocol.
+    (NoDefaultAlpn => 2, b"no-default-alpn")
+
+    /// The port for alternative endpoints.
+    (Port => 3, b"port")
+
+    /// IPv4 address hints.
+    (Ipv4Hint => 4, b"ipv4hint")
+
+    /// Encrypted client hello configuration.
+    (Ech => 5, b"ech")
+
+    /// IPv6 address hints.
+    (Ipv6Hint => 6, b"ipv6hint")
+}
+
+int_enum_str_with_prefix!(
+    SvcParamKey,
+    "key",
+    b"key",
+    u16,
+    "unknown service parameter key"
+);
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::string::ToString;
+
+    #[test]
+    fn from_str_and_display() {
+        assert_eq!(
+            SvcParamKey::from_str("no-default-alpn").unwrap(),
+            SvcParamKey::NoDefaultAlpn
+        );
+        assert_eq!(SvcParamKey::from_str("key3").unwrap(), SvcParamKey::Port);
+        assert_eq!(SvcParamKey::Port.to_string(), "port");
+        assert_eq!(SvcParamKey::Int(65000).to_string(), "key65000");
+        assert!(SvcParamKey::from_str("key").is_err());
+    }
+}
@@ -11,19 +11,34 @@
 //! [`Message`]: struct.Message.html
 
 use super::header::{Header, HeaderCounts, HeaderSection};
-use super::iana::{Class, Rcode, Rtype};
-use super::message_builder::{AdditionalBuilder, AnswerBuilder};
+use super::iana::{Class, OptRcode, OptionCode, Rcode, Rtype};
+#[cfg(feature = "std")]
+use super::message_builder::MessageBuilder;
+use super::message_builder::{
+    AdditionalBuilder, AnswerBuilder, OptPushError,
+};
 use super::name::ParsedDname;
+use super::net::IpAddr;
+#[cfg(feature = "std")]
+use super::octets::Compose;
 use super::octets::{
-    OctetsBuilder, OctetsFrom, OctetsRef, Parse, ParseError, Parser, ShortBuf,
+    FormError, OctetsBuilder, OctetsFrom, OctetsRef, Parse, ParseError,
+    Parser, ShortBuf,
 };
 use super::opt::{Opt, OptRecord};
 use super::question::Question;
 use super::rdata::ParseRecordData;
 use super::record::{AsRecord, ParsedRecord, Record};
-use crate::rdata::rfc1035::Cname;
+use crate::rdata::rfc1035::{Cname, A};
+use crate::rdata::rfc3596::Aaaa;
+#[cfg(feature = "std")]
+use crate::rdata::AllRecordData;
 use core::marker::PhantomData;
 use core::{fmt, mem};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use std::vec::Vec;
 
 //------------ Message -------------------------------------------------------
 
@@ -129,6 +144,13 @@ use core::{fmt, mem};
 /// message is fully broken, the next iteration will return `None` to signal
 /// that.
 ///
+/// None of this requires allocating memory. In particular, a message can be
+/// created atop a plain `&[u8]`, e.g., a receive buffer borrowed from a
+/// socket. Domain names in the message will then be of type
+/// [`ParsedDname`] and reference the octets of the buffer – including for
+/// compressed names –, and record data types that are generic over octets
+/// sequences will also hold slices of the buffer.
+///
 /// [`additional`]: #method.additional
 /// [`answer`]: #method.answer
 /// [`authority`]: #method.authority
@@ -223,6 +245,11 @@ impl<Octets> Message<Octets> {
 
 /// # Header Section
 ///
+/// The methods in this section only access the fixed-size header section at
+/// the start of the message. They neither parse nor check the remainder of
+/// the message and are therefore very cheap. They can be used even if the
+/// message body is malformed or truncated.
+///
 impl<Octets: AsRef<[u8]>> Message<Octets> {
     /// Returns the message header.
     pub fn header(&self) -> Header {
@@ -258,6 +285,65 @@ impl<Octets: AsRef<[u8]>> Message<Octets> {
     }
 }
 
+/// # Sanity Checks
+///
+impl<Octets: AsRef<[u8]>> Message<Octets> {
+    /// Checks that the section counts of the message match its content.
+    ///
+    /// Because parsing of a message happens lazily, a message with section
+    /// counts that are inconsistent with the actual data is only detected
+    /// once the affected section is reached. This method can be used to
+    /// reject such messages up front.
+    ///
+    /// The method first checks whether the message could even hold the
+    /// number of questions and records claimed in the header given their
+    /// minimum size. Only if that succeeds, it walks over all questions and
+    /// records and checks that each of them can be parsed and that there is
+    /// no trailing data after the last record. Record data is skipped over
+    /// without being parsed.
+    pub fn check_sanity(&self) -> Result<(), SanityError> {
+        let counts = self.header_counts();
+        let available =
+            self.as_slice().len() - mem::size_of::<HeaderSection>();
+        let required = usize::from(counts.qdcount()) * MIN_QUESTION_LEN
+            + (usize::from(counts.ancount())
+                + usize::from(counts.nscount())
+                + usize::from(counts.arcount()))
+                * MIN_RECORD_LEN;
+        if required > available {
+            return Err(SanityError::ExcessiveCounts);
+        }
+
+        let msg = self.for_slice();
+        let mut question = msg.question();
+        for item in &mut question {
+            item.map_err(SanityError::Question)?;
+        }
+        let mut section = question.answer().map_err(SanityError::Question)?;
+        loop {
+            let which = section.section;
+            for item in &mut section {
+                item.map_err(|err| SanityError::Record(which, err))?;
+            }
+            section = match section.next_section() {
+                Ok(Some(section)) => section,
+                Ok(None) => break,
+                Err(err) => return Err(SanityError::Record(which, err)),
+            }
+        }
+        if section.parser.remaining() > 0 {
+            return Err(SanityError::TrailingData);
+        }
+        Ok(())
+    }
+}
+
+/// The minimum length of a question: root name, type, and class.
+const MIN_QUESTION_LEN: usize = 5;
+
+/// The minimum length of a record: root name, type, class, TTL, and rdlen.
+const MIN_RECORD_LEN: usize = 11;
+
 /// # Access to Sections
 ///
 impl<Octets> Message<Octets>
@@ -431,6 +517,157 @@ where
         answer.limit_to::<Data>().next().is_some()
     }
 
+    /// Returns an iterator over all records of the given type.
+    ///
+    /// The iterator walks over the answer, authority, and additional
+    /// sections in this order and yields only those records whose record
+    /// type is `rtype`, parsed into record data of type `Data`. Records that
+    /// `Data` doesn’t want to parse are skipped.
+    ///
+    /// If parsing a record fails, the iterator returns an error. Once the
+    /// message itself fails to parse, this error is the last item.
+    ///
+    /// Use [`records_of_type_in`][Self::records_of_type_in] to only look at
+    /// one section.
+    pub fn records_of_type<'s, Data>(
+        &'s self,
+        rtype: Rtype,
+    ) -> impl Iterator<
+        Item = Result<Record<ParsedDname<&'s Octets>, Data>, ParseError>,
+    > + 's
+    where
+        Data: ParseRecordData<&'s Octets> + 's,
+    {
+        self.filter_records(rtype, None)
+    }
+
+    /// Returns an iterator over all records of a type in a given section.
+    ///
+    /// This is the same as [`records_of_type`][Self::records_of_type] but
+    /// only yields records from `section`.
+    pub fn records_of_type_in<'s, Data>(
+        &'s self,
+        rtype: Rtype,
+        section: Section,
+    ) -> impl Iterator<
+        Item = Result<Record<ParsedDname<&'s Octets>, Data>, ParseError>,
+    > + 's
+    where
+        Data: ParseRecordData<&'s Octets> + 's,
+    {
+        self.filter_records(rtype, Some(section))
+    }
+
+    /// Filters the records of the message by type and, optionally, section.
+    fn filter_records<'s, Data>(
+        &'s self,
+        rtype: Rtype,
+        section: Option<Section>,
+    ) -> impl Iterator<
+        Item = Result<Record<ParsedDname<&'s Octets>, Data>, ParseError>,
+    > + 's
+    where
+        Data: ParseRecordData<&'s Octets> + 's,
+    {
+        self.iter().filter_map(move |item| {
+            let (record, record_section) = match item {
+                Ok(item) => item,
+                Err(err) => return Some(Err(err)),
+            };
+            if record.rtype() != rtype
+                || section.map(|s| s != record_section).unwrap_or(false)
+            {
+                return None;
+            }
+            record.into_record().transpose()
+        })
+    }
+
+    /// Collects the records of the answer section into their RRsets.
+    ///
+    /// The records are parsed using the record data type `Data` and
+    /// collected into a map keyed by their owner name and record type.
+    /// Records of types `Data` doesn’t know how to parse are skipped, so use
+    /// [`AllRecordData`] to get all records as is useful, e.g., when
+    /// looking at the response to a query for [`Rtype::Any`].
+    ///
+    /// Since owner names are compared ignoring ASCII case, records whose
+    /// owners only differ in case end up in the same RRset.
+    ///
+    /// The method fails if any record in the answer section fails to parse.
+    ///
+    /// [`AllRecordData`]: crate::rdata::AllRecordData
+    #[cfg(feature = "std")]
+    #[allow(clippy::type_complexity)]
+    pub fn answer_rrsets<'s, Data>(
+        &'s self,
+    ) -> Result<
+        BTreeMap<
+            (ParsedDname<&'s Octets>, Rtype),
+            Vec<Record<ParsedDname<&'s Octets>, Data>>,
+        >,
+        ParseError,
+    >
+    where
+        Data: ParseRecordData<&'s Octets>,
+    {
+        let mut res = BTreeMap::new();
+        for record in self.answer()?.limit_to::<Data>() {
+            let record = record?;
+            res.entry((*record.owner(), record.rtype()))
+                .or_insert_with(Vec::new)
+                .push(record);
+        }
+        Ok(res)
+    }
+
+    /// Returns all records that duplicate an earlier record.
+    ///
+    /// Two records are considered duplicates if they appear in the same
+    /// section and have the same owner name, record type, class, and record
+    /// data. Their TTLs are ignored. The comparison uses the canonical form
+    /// of the records as defined in [RFC 4034], i.e., domain names are
+    /// compared ignoring ASCII case.
+    ///
+    /// For each record that duplicates an earlier record in its section,
+    /// the record and its section are returned in message order. If a
+    /// record appears three times, its second and third appearance are
+    /// returned. If the message has no duplicates, the returned vec is
+    /// empty.
+    ///
+    /// The method fails if any record in the message fails to parse.
+    ///
+    /// [RFC 4034]: https://tools.ietf.org/html/rfc4034
+    #[cfg(feature = "std")]
+    pub fn find_duplicates(
+        &self,
+    ) -> Result<Vec<(ParsedRecord<&Octets>, Section)>, ParseError> {
+        let mut res = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut current = Section::first();
+        for item in self {
+            let (record, section) = item?;
+            if section != current {
+                seen.clear();
+                current = section;
+            }
+            let parsed = match record.to_record::<AllRecordData<_, _>>()? {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+            // Composing into a vec can’t fail.
+            let mut key = Vec::new();
+            parsed.owner().compose_canonical(&mut key).unwrap();
+            parsed.rtype().compose(&mut key).unwrap();
+            parsed.class().compose(&mut key).unwrap();
+            parsed.data().compose_canonical(&mut key).unwrap();
+            if !seen.insert(key) {
+                res.push((record, section))
+            }
+        }
+        Ok(res)
+    }
+
     /// Resolves the canonical name of the answer.
     ///
     /// The CNAME record allows a domain name to be an alias for a different
@@ -484,6 +721,53 @@ where
         None
     }
 
+    /// Returns the addresses for the query name contained in the answer.
+    ///
+    /// The method determines the canonical name of the answer via
+    /// [`canonical_name`][Self::canonical_name] and then yields the
+    /// addresses of all A and AAAA records in the answer section that are
+    /// owned by this name.
+    ///
+    /// As suggested by the ‘Happy Eyeballs’ algorithm of [RFC 8305], the
+    /// addresses of both families are interleaved starting with an IPv6
+    /// address. Once the addresses of one family are exhausted, the
+    /// remaining addresses of the other family follow. Within each family,
+    /// the addresses are returned in the order they appear in the message.
+    ///
+    /// If the message has no question, if there is a CNAME loop, or if the
+    /// answer section cannot be parsed at all, the iterator is empty. Any
+    /// records following a parse error are ignored.
+    ///
+    /// [RFC 8305]: https://tools.ietf.org/html/rfc8305
+    pub fn addresses(&self) -> impl Iterator<Item = IpAddr> + '_ {
+        let (name, answer) = match (self.canonical_name(), self.answer()) {
+            (Some(name), Ok(answer)) => (Some(name), Some(answer)),
+            _ => (None, None),
+        };
+        let mut v6 = answer
+            .into_iter()
+            .flat_map(|answer| answer.limit_to::<Aaaa>())
+            .filter_map(Result::ok)
+            .filter(move |record| Some(*record.owner()) == name)
+            .map(|record| IpAddr::V6(record.data().addr()));
+        let mut v4 = answer
+            .into_iter()
+            .flat_map(|answer| answer.limit_to::<A>())
+            .filter_map(Result::ok)
+            .filter(move |record| Some(*record.owner()) == name)
+            .map(|record| IpAddr::V4(record.data().addr()));
+        let mut next_v6 = true;
+        core::iter::from_fn(move || {
+            let res = if next_v6 {
+                v6.next().or_else(|| v4.next())
+            } else {
+                v4.next().or_else(|| v6.next())
+            };
+            next_v6 = !next_v6;
+            res
+        })
+    }
+
     /// Returns the OPT record from the message, if there is one.
     pub fn opt(&self) -> Option<OptRecord<<&Octets as OctetsRef>::Range>> {
         match self.additional() {
@@ -495,6 +779,98 @@ where
         }
     }
 
+    /// Returns the EDNS information of the message, if it supports EDNS.
+    ///
+    /// EDNS is signalled through the presence of an OPT record in the
+    /// additional section. If there is none, the method returns `None`,
+    /// meaning the sender of the message does not support EDNS. This is
+    /// different from an OPT record that is present but, for instance,
+    /// states a UDP payload size of zero.
+    ///
+    /// The method only parses the message and does not allocate. It is a
+    /// thin wrapper around [`opt`][Self::opt] for code that checks for EDNS
+    /// support rather than for the OPT record itself.
+    pub fn edns(&self) -> Option<OptRecord<<&Octets as OctetsRef>::Range>> {
+        self.opt()
+    }
+
+    /// Returns the length of the message without any EDNS padding.
+    ///
+    /// If the message contains an OPT record with one or more [Padding]
+    /// options, the returned length excludes these options including
+    /// their option headers. The OPT record itself is still counted.
+    /// Otherwise, this is the length of the message.
+    ///
+    /// The message itself is not changed.
+    ///
+    /// [Padding]: super::opt::rfc7830::Padding
+    pub fn unpadded_len(&self) -> usize {
+        let padding: usize = match self.opt() {
+            Some(opt) => opt
+                .as_opt()
+                .raw_options()
+                .filter(|(code, _)| *code == OptionCode::Padding)
+                .map(|(_, data)| data.len() + 4)
+                .sum(),
+            None => 0,
+        };
+        self.as_slice().len() - padding
+    }
+
+    /// Returns the full, extended rcode of the message.
+    ///
+    /// If the message has an OPT record, the rcode is combined from the
+    /// lower four bits in the header and the upper eight bits in the OPT
+    /// record. Otherwise, it is just the rcode of the header.
+    pub fn rcode(&self) -> OptRcode {
+        match self.opt() {
+            Some(opt) => opt.rcode(self.header()),
+            None => self.header().rcode().into(),
+        }
+    }
+
+    /// Starts an empty response to this message.
+    ///
+    /// The returned builder contains a message with the ID, opcode, and RD
+    /// flag copied from this message, the QR flag set, and the question
+    /// section copied over. If this message has an OPT record, the response
+    /// receives one, too, with the UDP payload size and DO flag copied.
+    ///
+    /// Since the OPT record lives in the additional section, the builder is
+    /// positioned there and can be finished right away for an empty
+    /// response.
+    ///
+    /// Moving back to an earlier section, e.g., via [`answer`], truncates
+    /// the message to the end of that section and thus drops the OPT record
+    /// again. If you need to add answers, use
+    /// [`MessageBuilder::start_answer`] instead and add the OPT record via
+    /// [`AdditionalBuilder::opt`] once you have reached the additional
+    /// section.
+    ///
+    /// [`answer`]: ../message_builder/struct.AdditionalBuilder.html#method.answer
+    /// [`AdditionalBuilder::opt`]: ../message_builder/struct.AdditionalBuilder.html#method.opt
+    /// [`MessageBuilder::start_answer`]: ../message_builder/struct.MessageBuilder.html#method.start_answer
+    #[cfg(feature = "std")]
+    pub fn response_builder(
+        &self,
+    ) -> Result<AdditionalBuilder<std::vec::Vec<u8>>, ShortBuf> {
+        let mut target = MessageBuilder::new_vec()
+            .start_answer(self, Rcode::NoError)?
+            .additional();
+        if let Some(opt) = self.opt() {
+            // The section is still empty, so running out of space is the
+            // only possible error.
+            target
+                .opt(|builder| {
+                    builder.set_udp_payload_size(opt.udp_payload_size());
+                    builder.set_dnssec_ok(opt.dnssec_ok());
+                    Ok(())
+                })
+                .map_err(|_| ShortBuf)?;
+        }
+        Ok(target)
+    }
+
     /// Returns the last additional record from the message.
     ///
     /// The method tries to parse the last record of the additional section
@@ -1065,6 +1441,9 @@ pub enum CopyRecordsError {
     /// Parsing the source message failed.
     Parse(ParseError),
 
+    /// Adding a record to the target failed for reasons other than space.
+    Push(OptPushError),
+
     /// Not enough space in the target.
     ShortBuf,
 }
@@ -1077,6 +1456,15 @@ impl From<ParseError> for CopyRecordsError {
     }
 }
 
+impl From<OptPushError> for CopyRecordsError {
+    fn from(err: OptPushError) -> Self {
+        match err {
+            OptPushError::ShortBuf => CopyRecordsError::ShortBuf,
+            err => CopyRecordsError::Push(err),
+        }
+    }
+}
+
 impl From<ShortBuf> for CopyRecordsError {
     fn from(_: ShortBuf) -> Self {
         CopyRecordsError::ShortBuf
@@ -1089,6 +1477,7 @@ impl fmt::Display for CopyRecordsError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             CopyRecordsError::Parse(ref err) => err.fmt(f),
+            CopyRecordsError::Push(ref err) => err.fmt(f),
             CopyRecordsError::ShortBuf => ShortBuf.fmt(f),
         }
     }
@@ -1097,14 +1486,71 @@ impl fmt::Display for CopyRecordsError {
 #[cfg(feature = "std")]
 impl std::error::Error for CopyRecordsError {}
 
+//------------ SanityError ---------------------------------------------------
+
+/// A message failed the sanity check.
+///
+/// This error is returned by [`Message::check_sanity`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SanityError {
+    /// The header claims more entries than the message can possibly hold.
+    ExcessiveCounts,
+
+    /// Parsing the question section failed.
+    Question(ParseError),
+
+    /// Parsing a record in the given section failed.
+    Record(Section, ParseError),
+
+    /// There is data left after the last record of the message.
+    TrailingData,
+}
+
+//--- From
+
+impl From<SanityError> for ParseError {
+    fn from(err: SanityError) -> Self {
+        match err {
+            SanityError::ExcessiveCounts => ParseError::Form(FormError::new(
+                "section counts exceed message size",
+            )),
+            SanityError::Question(err) => err,
+            SanityError::Record(_, err) => err,
+            SanityError::TrailingData => {
+                ParseError::Form(FormError::new("trailing data"))
+            }
+        }
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for SanityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SanityError::ExcessiveCounts => {
+                f.write_str("section counts exceed message size")
+            }
+            SanityError::Question(ref err) => {
+                write!(f, "question section: {}", err)
+            }
+            SanityError::Record(section, ref err) => {
+                write!(f, "{:?} section: {}", section, err)
+            }
+            SanityError::TrailingData => f.write_str("trailing data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SanityError {}
+
 //============ Testing =======================================================
 
 #[cfg(test)]
 mod test {
     use super::*;
     #[cfg(feature = "std")]
-    use crate::base::message_builder::MessageBuilder;
-    #[cfg(feature = "std")]
     use crate::base::name::Dname;
     #[cfg(feature = "std")]
     use crate::rdata::{AllRecordData, Ns};
@@ -1132,12 +1578,182 @@ mod test {
         msg.into_message()
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn records_of_type() {
+        use crate::base::name::ToDname;
+        use crate::base::net::Ipv6Addr;
+        use crate::rdata::{Aaaa, A};
+        use core::str::FromStr;
+
+        let aaaa = |s: &str| Aaaa::new(Ipv6Addr::from_str(s).unwrap());
+        let name = |s: &str| Dname::vec_from_str(s).unwrap();
+
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push((name("www.example.com."), 3600, aaaa("2001:db8::1")))
+            .unwrap();
+        msg.push((
+            name("www.example.com."),
+            3600,
+            A::from_octets(192, 0, 2, 1),
+        ))
+        .unwrap();
+        msg.push((name("www.example.com."), 3600, aaaa("2001:db8::2")))
+            .unwrap();
+        let mut msg = msg.authority();
+        msg.push((
+            name("example.com."),
+            3600,
+            Ns::new(name("ns.example.com.")),
+        ))
+        .unwrap();
+        let mut msg = msg.additional();
+        msg.push((name("ns.example.com."), 3600, aaaa("2001:db8::53")))
+            .unwrap();
+        let msg = msg.into_message();
+
+        let found: Vec<_> = msg
+            .records_of_type::<Aaaa>(Rtype::Aaaa)
+            .map(|record| {
+                let record = record.unwrap();
+                (
+                    record.owner().to_dname::<Vec<u8>>().unwrap(),
+                    record.data().clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (name("www.example.com."), aaaa("2001:db8::1")),
+                (name("www.example.com."), aaaa("2001:db8::2")),
+                (name("ns.example.com."), aaaa("2001:db8::53")),
+            ]
+        );
+
+        let found: Vec<_> = msg
+            .records_of_type_in::<Aaaa>(Rtype::Aaaa, Section::Additional)
+            .map(|record| record.unwrap().data().clone())
+            .collect();
+        assert_eq!(found, [aaaa("2001:db8::53")]);
+
+        // AllRecordData parses everything, yet only the asked-for type
+        // is returned.
+        assert_eq!(
+            msg.records_of_type::<AllRecordData<_, _>>(Rtype::Ns)
+                .count(),
+            1
+        );
+        assert_eq!(msg.records_of_type::<Aaaa>(Rtype::A).count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn find_duplicates() {
+        use crate::rdata::A;
+
+        let name = |s: &str| Dname::vec_from_str(s).unwrap();
+        let a = |last| A::from_octets(192, 0, 2, last);
+
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push((name("www.example.com."), 3600, a(1))).unwrap();
+        msg.push((name("www.example.com."), 3600, a(2))).unwrap();
+        msg.push((name("WWW.Example.com."), 60, a(1))).unwrap();
+        let mut msg = msg.additional();
+        msg.push((name("www.example.com."), 3600, a(1))).unwrap();
+        let msg = msg.into_message();
+
+        let dups = msg.find_duplicates().unwrap();
+        assert_eq!(dups.len(), 1);
+        let (record, section) = &dups[0];
+        assert_eq!(*section, Section::Answer);
+        let record = record.to_record::<A>().unwrap().unwrap();
+        assert_eq!(*record.owner(), name("WWW.Example.com."));
+        assert_eq!(record.ttl(), 60);
+        assert_eq!(*record.data(), a(1));
+
+        assert!(get_test_message().find_duplicates().unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_slice() {
+        use crate::base::net::Ipv6Addr;
+        use crate::rdata::Aaaa;
+
+        // A response for www.example.com AAAA with a compressed owner name,
+        // borrowed from some buffer.
+        let buf: [u8; 61] = [
+            0x12, 0x34, 0x81, 0x80, 0, 1, 0, 1, 0, 0, 0, 0, // header
+            3, b'w', b'w', b'w', 7, b'e', b'x', b'a', b'm', b'p', b'l', b'e',
+            3, b'c', b'o', b'm', 0, 0, 28, 0, 1, // question
+            0xc0, 12, 0, 28, 0, 1, 0, 0, 0x0e, 0x10, 0, 16, // answer
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+        ];
+        let msg = Message::from_octets(&buf[..]).unwrap();
+        assert_eq!(msg.header().id(), 0x1234);
+
+        let question = msg.sole_question().unwrap();
+        assert_eq!(question.qtype(), Rtype::Aaaa);
+
+        let mut answer = msg.answer().unwrap().limit_to::<Aaaa>();
+        let record = answer.next().unwrap().unwrap();
+        assert!(answer.next().is_none());
+        assert_eq!(record.owner(), question.qname());
+        assert_eq!(record.ttl(), 3600);
+        assert_eq!(
+            record.data().addr(),
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)
+        );
+
+        // The owner name references the buffer.
+        let mut labels = record.owner().iter();
+        assert_eq!(
+            labels.next().unwrap().as_slice().as_ptr(),
+            buf[13..].as_ptr()
+        );
+    }
+
     #[test]
     fn short_message() {
         assert!(Message::from_octets(&[0u8; 11]).is_err());
         assert!(Message::from_octets(&[0u8; 12]).is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn check_sanity() {
+        fn with_ancount(ancount: u16) -> Message<Vec<u8>> {
+            let mut octets = get_test_message().into_octets();
+            HeaderCounts::for_message_slice_mut(&mut octets)
+                .set_ancount(ancount);
+            Message::from_octets(octets).unwrap()
+        }
+
+        assert_eq!(get_test_message().check_sanity(), Ok(()));
+        assert_eq!(
+            with_ancount(1000).check_sanity(),
+            Err(SanityError::ExcessiveCounts)
+        );
+        assert_eq!(
+            with_ancount(0xFFFF).check_sanity(),
+            Err(SanityError::ExcessiveCounts)
+        );
+        assert_eq!(
+            with_ancount(2).check_sanity(),
+            Err(SanityError::Record(
+                Section::Authority,
+                ParseError::ShortInput
+            ))
+        );
+
+        let mut octets = get_test_message().into_octets();
+        octets.extend_from_slice(b"garbage");
+        assert_eq!(
+            Message::from_octets(octets).unwrap().check_sanity(),
+            Err(SanityError::TrailingData)
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn canonical_name() {
@@ -1196,6 +1812,42 @@ mod test {
         assert!(msg.as_message().canonical_name().is_none());
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn addresses() {
+        use crate::base::net::{Ipv4Addr, Ipv6Addr};
+        use core::str::FromStr;
+
+        let name = Dname::vec_from_str("www.example.com.").unwrap();
+        let alias = Dname::vec_from_str("cdn.example.net.").unwrap();
+        let other = Dname::vec_from_str("other.example.net.").unwrap();
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push((&name, Rtype::Aaaa)).unwrap();
+        let mut msg = msg.answer();
+        msg.push((&name, 3600, Cname::new(alias.clone()))).unwrap();
+        msg.push((&alias, 3600, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        msg.push((&alias, 3600, A::from_octets(192, 0, 2, 2)))
+            .unwrap();
+        msg.push((&other, 3600, A::from_octets(192, 0, 2, 3)))
+            .unwrap();
+        msg.push((&alias, 3600, Aaaa::from_str("2001:db8::1").unwrap()))
+            .unwrap();
+        let msg = msg.into_message();
+
+        assert_eq!(
+            msg.addresses().collect::<Vec<_>>(),
+            [
+                IpAddr::V6(Ipv6Addr::from_str("2001:db8::1").unwrap()),
+                IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)),
+                IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2)),
+            ]
+        );
+
+        let msg = MessageBuilder::new_vec().into_message();
+        assert_eq!(msg.addresses().next(), None);
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn message_iterator() {
@@ -1211,6 +1863,61 @@ mod test {
         assert_eq!(Section::Authority, section);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn answer_rrsets() {
+        use crate::base::net::{Ipv4Addr, Ipv6Addr};
+        use crate::rdata::{Aaaa, Mx, A};
+        use std::string::{String, ToString};
+
+        let name = Dname::vec_from_str("example.com.").unwrap();
+        let other = Dname::vec_from_str("www.example.com.").unwrap();
+        let mut msg =
+            MessageBuilder::new_vec().any_query(&name).unwrap().answer();
+        msg.header_mut().set_qr(true);
+        msg.push((&name, 3600, A::new(Ipv4Addr::new(192, 0, 2, 1))))
+            .unwrap();
+        msg.push((&name, 3600, Aaaa::new(Ipv6Addr::LOCALHOST)))
+            .unwrap();
+        msg.push((&other, 3600, A::new(Ipv4Addr::new(192, 0, 2, 3))))
+            .unwrap();
+        msg.push((&name, 3600, Mx::new(10, name.clone()))).unwrap();
+        msg.push((&name, 3600, A::new(Ipv4Addr::new(192, 0, 2, 2))))
+            .unwrap();
+        let msg = msg.into_message();
+        assert_eq!(msg.qtype(), Some(Rtype::Any));
+
+        let rrsets = msg
+            .answer_rrsets::<AllRecordData<_, ParsedDname<_>>>()
+            .unwrap();
+        let keys: Vec<_> = rrsets
+            .keys()
+            .map(|(owner, rtype)| (owner.to_string(), *rtype))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                (String::from("example.com"), Rtype::A),
+                (String::from("example.com"), Rtype::Mx),
+                (String::from("example.com"), Rtype::Aaaa),
+                (String::from("www.example.com"), Rtype::A),
+            ]
+        );
+        let a =
+            &rrsets[&(msg.first_question().unwrap().into_qname(), Rtype::A)];
+        assert_eq!(a.len(), 2);
+        match a[1].data() {
+            AllRecordData::A(data) => {
+                assert_eq!(data.addr(), Ipv4Addr::new(192, 0, 2, 2))
+            }
+            _ => panic!("not an A record"),
+        }
+
+        // Limiting the data type skips all other records.
+        let rrsets = msg.answer_rrsets::<A>().unwrap();
+        assert_eq!(rrsets.len(), 2);
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn copy_records() {
@@ -1235,4 +1942,147 @@ mod test {
             assert_eq!(0, msg.header_counts().arcount());
         }
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn edns() {
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push((Dname::vec_from_str("example.com.").unwrap(), Rtype::A))
+            .unwrap();
+        let plain = msg.clone().into_message();
+        assert!(plain.edns().is_none());
+
+        let mut msg = msg.additional();
+        msg.opt(|opt| {
+            opt.set_udp_payload_size(0);
+            Ok(())
+        })
+        .unwrap();
+        let msg = msg.into_message();
+        let edns = msg.edns().unwrap();
+        assert_eq!(edns.udp_payload_size(), 0);
+        assert_eq!(edns.version(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn unpadded_len() {
+        use crate::base::opt::rfc7830::{Padding, PaddingMode};
+
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push((Dname::vec_from_str("example.com.").unwrap(), Rtype::A))
+            .unwrap();
+        assert_eq!(msg.clone().into_message().unpadded_len(), 29);
+
+        let mut msg = msg.additional();
+        let mut plain = msg.clone();
+        plain.opt(|_| Ok(())).unwrap();
+        let plain = plain.into_message();
+        assert_eq!(plain.unpadded_len(), plain.as_slice().len());
+
+        msg.opt(|opt| Padding::push_block(opt, 128, PaddingMode::Zero))
+            .unwrap();
+        let padded = msg.into_message();
+        assert_eq!(padded.as_slice().len(), 128);
+        assert_eq!(padded.unpadded_len(), plain.as_slice().len());
+    }
+
+    #[test]
+    fn header_only() {
+        // A response header with ID 0x1234, QR, AA, RD, RA, NXDOMAIN, and
+        // counts 1/2/3/4 but only a truncated question.
+        let msg = Message::from_octets(
+            b"\x12\x34\x85\x83\x00\x01\x00\x02\x00\x03\x00\x04\x07exa"
+                .as_ref(),
+        )
+        .unwrap();
+        let header = msg.header();
+        assert_eq!(header.id(), 0x1234);
+        assert!(header.qr());
+        assert!(header.aa());
+        assert!(!header.tc());
+        assert!(header.rd());
+        assert!(header.ra());
+        assert_eq!(header.rcode(), Rcode::NXDomain);
+        let counts = msg.header_counts();
+        assert_eq!(counts.qdcount(), 1);
+        assert_eq!(counts.ancount(), 2);
+        assert_eq!(counts.nscount(), 3);
+        assert_eq!(counts.arcount(), 4);
+        assert!(msg.question().next().unwrap().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rcode() {
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push((Dname::vec_from_str("example.com.").unwrap(), Rtype::A))
+            .unwrap();
+
+        // Plain NXDOMAIN without an OPT record.
+        let mut nxdomain = msg.clone();
+        nxdomain.header_mut().set_rcode(Rcode::NXDomain);
+        let nxdomain = nxdomain.into_message();
+        assert!(nxdomain.opt().is_none());
+        assert_eq!(nxdomain.rcode(), OptRcode::NXDomain);
+
+        // BADVERS needs the upper bits from the OPT record.
+        let mut badvers = msg.additional();
+        badvers
+            .opt(|opt| {
+                opt.set_rcode(OptRcode::BadVers);
+                Ok(())
+            })
+            .unwrap();
+        let badvers = badvers.into_message();
+        assert_eq!(badvers.header().rcode(), Rcode::NoError);
+        assert_eq!(badvers.rcode(), OptRcode::BadVers);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn response_builder() {
+        let mut query = MessageBuilder::new_vec();
+        query.header_mut().set_id(0x1234);
+        query.header_mut().set_rd(true);
+        let mut query = query.question();
+        query
+            .push((Dname::vec_from_str("example.com.").unwrap(), Rtype::Aaaa))
+            .unwrap();
+        let plain = query.clone().into_message();
+        let mut query = query.additional();
+        query
+            .opt(|opt| {
+                opt.set_udp_payload_size(1232);
+                opt.set_dnssec_ok(true);
+                Ok(())
+            })
+            .unwrap();
+        let query = query.into_message();
+
+        let response = query.response_builder().unwrap().into_message();
+        assert!(response.is_answer(&query));
+        assert_eq!(response.header().id(), 0x1234);
+        assert!(response.header().qr());
+        assert!(response.header().rd());
+        assert_eq!(response.header().rcode(), Rcode::NoError);
+        assert_eq!(response.question(), query.question());
+        assert_eq!(response.header_counts().ancount(), 0);
+        assert_eq!(response.header_counts().nscount(), 0);
+        let opt = response.opt().unwrap();
+        assert_eq!(opt.udp_payload_size(), 1232);
+        assert!(opt.dnssec_ok());
+
+        // Moving back to an earlier section drops the OPT record.
+        let response =
+            query.response_builder().unwrap().answer().into_message();
+        assert!(response.opt().is_none());
+        assert_eq!(response.header_counts().arcount(), 0);
+        assert_eq!(response.question(), query.question());
+
+        let response = plain.response_builder().unwrap().into_message();
+        assert!(response.is_answer(&plain));
+        assert!(response.opt().is_none());
+        assert_eq!(response.header_counts().arcount(), 0);
+    }
 }
@@ -45,6 +45,14 @@
 //! and makes sure the message doesn’t become longer than what the counter
 //! can provide for.
 //!
+//! The [`LimitedTarget`] restricts the size of the message. This is useful
+//! when building responses for datagram transports where the message needs
+//! to fit into the UDP payload size requested by the client. Since pushing
+//! a record either adds the complete record or nothing at all, a record that
+//! doesn’t fit is never partially included. You can then use
+//! [`AdditionalBuilder::truncate_with_opt`] to mark the message as truncated
+//! and finish it.
+//!
 //! Two further types, [`TreeCompressor`] and [`StaticCompressor`], provide
 //! name compression. This is a mechanism to decrease the size of a DNS
 //! message by avoiding repeating domain names: Instead of including a domain
@@ -119,6 +127,8 @@
 //! [`AuthorityBuilder`]: struct.AuthorityBuilder.html
 //! [`AdditionalBuilder`]: struct.AdditionalBuilder.html
 //! [`AdditionalBuilder::opt`]: struct.AdditionalBuilder.html#method.opt
+//! [`AdditionalBuilder::truncate_with_opt`]: struct.AdditionalBuilder.html#method.truncate_with_opt
+//! [`LimitedTarget`]: struct.LimitedTarget.html
 //! [`OptBuilder`]: struct.OptBuilder.html
 //! [`RecordSectionBuilder`]: trait.RecordSectionBuilder.html
 //! [`StaticCompressor`]: struct.StaticCompressor.html
@@ -134,16 +144,19 @@ use super::message::Message;
 use super::name::{Label, ToDname};
 #[cfg(feature = "std")]
 use super::octets::Octets64;
-use super::octets::{Compose, OctetsBuilder, OctetsRef, ShortBuf};
+use super::octets::{
+    Compose, OctetsBuilder, OctetsRef, Parse, Parser, ShortBuf,
+};
 use super::opt::{OptData, OptHeader};
 use super::question::AsQuestion;
-use super::record::AsRecord;
+use super::rdata::RecordData;
+use super::record::{AsRecord, ParsedRecord};
 #[cfg(feature = "bytes")]
 use bytes::BytesMut;
 #[cfg(feature = "std")]
 use core::convert::TryInto;
-use core::mem;
 use core::ops::{Deref, DerefMut};
+use core::{fmt, mem};
 #[cfg(feature = "std")]
 use std::collections::HashMap;
 #[cfg(feature = "std")]
@@ -263,6 +276,72 @@ impl<Target: OctetsBuilder> MessageBuilder<Target> {
         builder.push((apex, Rtype::Axfr))?;
         Ok(builder.answer())
     }
+
+    /// Creates a query for all records of a name.
+    ///
+    /// Sets a random ID and the RD flag and pushes a question for `qname`
+    /// with the query type [`Rtype::Any`]. The answer section of the
+    /// response can then be split into its RRsets via
+    /// [`Message::answer_rrsets`].
+    ///
+    /// Note that servers may decline to answer such queries fully as
+    /// described in [RFC 8482].
+    ///
+    /// The method converts the message builder into a question builder
+    /// positioned after the question.
+    ///
+    /// [RFC 8482]: https://tools.ietf.org/html/rfc8482
+    pub fn any_query<N: ToDname>(
+        mut self,
+        qname: N,
+    ) -> Result<QuestionBuilder<Target>, ShortBuf> {
+        {
+            let header = self.header_mut();
+            header.set_random_id();
+            header.set_rd(true);
+        }
+        let mut builder = self.question();
+        builder.push((qname, Rtype::Any))?;
+        Ok(builder)
+    }
+
+    /// Creates a query that requests DNSSEC records.
+    ///
+    /// Sets a random ID and the RD flag, pushes the question for `qname`
+    /// and `qtype`, and adds an OPT record with the DO flag set and the
+    /// given UDP payload size. If `cd` is `true`, the CD flag is set as
+    /// well, asking the resolver not to perform DNSSEC validation itself.
+    ///
+    /// The method converts the message builder into an additional builder
+    /// positioned after the OPT record. Note that moving back to an
+    /// earlier section will drop the OPT record.
+    pub fn dnssec_query<N: ToDname>(
+        mut self,
+        qname: N,
+        qtype: Rtype,
+        udp_payload_size: u16,
+        cd: bool,
+    ) -> Result<AdditionalBuilder<Target>, ShortBuf> {
+        {
+            let header = self.header_mut();
+            header.set_random_id();
+            header.set_rd(true);
+            header.set_cd(cd);
+        }
+        let mut builder = self.question();
+        builder.push((qname, qtype))?;
+        let mut builder = builder.additional();
+        // The section is still empty, so running out of space is the only
+        // possible error.
+        builder
+            .opt(|opt| {
+                opt.set_udp_payload_size(udp_payload_size);
+                opt.set_dnssec_ok(true);
+                Ok(())
+            })
+            .map_err(|_| ShortBuf)?;
+        Ok(builder)
+    }
 }
 
 /// # Access to the Message Header
@@ -730,6 +809,31 @@ impl<Target: OctetsBuilder> AnswerBuilder<Target> {
             err
         })
     }
+
+    /// Appends a record that has already been composed.
+    ///
+    /// The octets in `record` are appended to the message as is and the
+    /// answer count is increased by one. This is useful when forwarding
+    /// records from one message into another without parsing them first.
+    ///
+    /// It is the caller’s responsibility to make sure that `record` contains
+    /// exactly one complete record in wire format. In particular, the
+    /// domain names in the record must not be compressed since compression
+    /// pointers would refer to the original message. Likewise, the names
+    /// will not be used for compressing names of subsequent records even if
+    /// the builder’s target is a compressor.
+    ///
+    /// If the record doesn’t fit into the message, returns an error and
+    /// leaves the message unchanged.
+    pub fn push_raw_record(&mut self, record: &[u8]) -> Result<(), ShortBuf> {
+        let pos = self.as_target().len();
+        self.as_target_mut().append_slice(record)?;
+        if let Err(err) = self.counts_mut().inc_ancount() {
+            self.as_target_mut().truncate(pos);
+            return Err(err);
+        }
+        Ok(())
+    }
 }
 
 /// # Conversions
@@ -964,6 +1068,22 @@ impl<Target: OctetsBuilder> AuthorityBuilder<Target> {
             err
         })
     }
+
+    /// Appends a record that has already been composed.
+    ///
+    /// The octets in `record` are appended to the message as is and the
+    /// authority count is increased by one. The record must not contain
+    /// compressed domain names. See
+    /// [`AnswerBuilder::push_raw_record`] for details.
+    pub fn push_raw_record(&mut self, record: &[u8]) -> Result<(), ShortBuf> {
+        let pos = self.as_target().len();
+        self.as_target_mut().append_slice(record)?;
+        if let Err(err) = self.counts_mut().inc_nscount() {
+            self.as_target_mut().truncate(pos);
+            return Err(err);
+        }
+        Ok(())
+    }
 }
 
 /// # Conversions
@@ -1140,6 +1260,7 @@ impl<Target: AsRef<[u8]>> AsRef<[u8]> for AuthorityBuilder<Target> {
 /// easier. The [`opt`] method creates an [`OptBuilder`] and passes it to a
 /// closure. This way, you can add and remove OPT records from additional
 /// builders that are part of another type and cannot be traded in easily.
+/// The method makes sure that the section contains at most one OPT record.
 ///
 /// Once you are finished building the additional section, you can finish the
 /// message via [`finish`]. Additionally, conversions to all other builder
@@ -1157,6 +1278,11 @@ pub struct AdditionalBuilder<Target> {
 
     /// The index in the octets builder where the additional section starts.
     start: usize,
+
+    /// The index in the octets builder where the OPT record starts.
+    ///
+    /// This is `None` if no OPT record has been added yet.
+    opt: Option<usize>,
 }
 
 impl<Target: OctetsBuilder> AdditionalBuilder<Target> {
@@ -1167,6 +1293,7 @@ impl<Target: OctetsBuilder> AdditionalBuilder<Target> {
         AdditionalBuilder {
             start: authority.as_target().as_ref().len(),
             authority,
+            opt: None,
         }
     }
 
@@ -1197,30 +1324,183 @@ impl<Target: OctetsBuilder> AdditionalBuilder<Target> {
     ///     (Dname::root_ref(), 86400, A::from_octets(192, 0, 2, 1))
     /// ).unwrap();
     /// ```
-    pub fn push(&mut self, record: impl AsRecord) -> Result<(), ShortBuf> {
+    ///
+    /// Since a message must contain at most one OPT record, the method fails
+    /// with [`OptPushError::DuplicateOpt`] if `record` is an OPT record and
+    /// the section already contains one.
+    pub fn push(
+        &mut self,
+        record: impl AsRecord,
+    ) -> Result<(), OptPushError> {
+        let is_opt = record.data().rtype() == Rtype::Opt;
+        if is_opt && self.opt.is_some() {
+            return Err(OptPushError::DuplicateOpt);
+        }
         let pos = self.as_target().len();
         record.compose_record(self.as_target_mut())?;
-        self.counts_mut().inc_arcount().map_err(|err| {
+        if let Err(err) = self.counts_mut().inc_arcount() {
             self.as_target_mut().truncate(pos);
-            err
-        })
+            return Err(err.into());
+        }
+        if is_opt {
+            self.opt = Some(pos);
+        }
+        Ok(())
+    }
+
+    /// Appends a record that has already been composed.
+    ///
+    /// The octets in `record` are appended to the message as is and the
+    /// additional count is increased by one. The record must not contain
+    /// compressed domain names. See
+    /// [`AnswerBuilder::push_raw_record`] for details.
+    ///
+    /// OPT records are treated just as if they had been added via
+    /// [`push`][Self::push]: the method fails with
+    /// [`OptPushError::DuplicateOpt`] if the section already contains one and
+    /// a later call to [`opt`][Self::opt] will replace it.
+    pub fn push_raw_record(
+        &mut self,
+        record: &[u8],
+    ) -> Result<(), OptPushError> {
+        // An OPT record always has the root name as its owner, so its
+        // type starts at the second octet.
+        let is_opt = record.len() >= 3
+            && record[0] == 0
+            && Rtype::from_int(u16::from_be_bytes([record[1], record[2]]))
+                == Rtype::Opt;
+        if is_opt && self.opt.is_some() {
+            return Err(OptPushError::DuplicateOpt);
+        }
+        let pos = self.as_target().len();
+        self.as_target_mut().append_slice(record)?;
+        if let Err(err) = self.counts_mut().inc_arcount() {
+            self.as_target_mut().truncate(pos);
+            return Err(err.into());
+        }
+        if is_opt {
+            self.opt = Some(pos);
+        }
+        Ok(())
     }
 
     /// Appends and builds an OPT record.
     ///
     /// The actual building of the record is handled by a closure that
     /// receives an [`OptBuilder`] which can both change the header of the
-    /// record and add options.
+    /// record – i.e., the UDP payload size, the extended rcode, the EDNS
+    /// version, and the DO bit – and add options. Unless changed by the
+    /// closure, the UDP payload size is [`DEFAULT_UDP_PAYLOAD_SIZE`] and
+    /// all other header fields are zero.
+    ///
+    /// A message must contain at most one OPT record. If an OPT record has
+    /// been added to the section before, it is removed and replaced by the
+    /// newly built record. This is only possible if it still is the last
+    /// record in the section. If other records have been added after it,
+    /// the method fails with [`OptPushError::OptNotLast`] and leaves the
+    /// section unchanged.
     ///
     /// The method will return whatever the closure returns. In addition, it
     /// will return an error if it failed to add the header of the OPT record.
     ///
     /// [`OptBuilder`]: struct.OptBuilder.html
-    pub fn opt<F, R>(&mut self, build: F) -> Result<R, ShortBuf>
+    /// [`DEFAULT_UDP_PAYLOAD_SIZE`]: crate::base::opt::DEFAULT_UDP_PAYLOAD_SIZE
+    pub fn opt<F, R>(&mut self, build: F) -> Result<R, OptPushError>
     where
         F: FnOnce(&mut OptBuilder<Target>) -> Result<R, ShortBuf>,
     {
-        build(&mut OptBuilder::new(self)?)
+        if let Some(pos) = self.opt {
+            if !self.is_last_record(pos) {
+                return Err(OptPushError::OptNotLast);
+            }
+            self.as_target_mut().truncate(pos);
+            self.counts_mut().dec_arcount();
+            self.opt = None;
+        }
+        Ok(build(&mut OptBuilder::new(self)?)?)
+    }
+
+    /// Returns whether the record starting at `pos` is the last record.
+    fn is_last_record(&self, pos: usize) -> bool {
+        let mut parser = Parser::from_ref(self.as_target().as_ref());
+        parser.seek(pos).is_ok()
+            && ParsedRecord::skip(&mut parser).is_ok()
+            && parser.remaining() == 0
+    }
+
+    /// Marks the message as truncated and adds an OPT record.
+    ///
+    /// This method is intended for finishing a response that didn’t fit
+    /// into the available space, e.g., because pushing a record to a
+    /// message built atop a [`LimitedTarget`] failed. It sets the TC flag,
+    /// removes all records from the additional section, and then adds an
+    /// OPT record built by the closure `build` which works just like the one
+    /// passed to [`opt`].
+    ///
+    /// If there isn’t enough space for the OPT record, records are removed
+    /// from the end of the authority and then answer sections until it fits.
+    /// Only complete records are ever removed. If the OPT record doesn’t fit
+    /// even with both sections empty, the method returns an error. In this
+    /// case, the message is left with its TC flag set and without any
+    /// records.
+    ///
+    /// If the response doesn’t need an OPT record, simply setting the TC
+    /// flag via [`header_mut`] is enough.
+    ///
+    /// [`LimitedTarget`]: struct.LimitedTarget.html
+    /// [`header_mut`]: struct.MessageBuilder.html#method.header_mut
+    /// [`opt`]: #method.opt
+    pub fn truncate_with_opt<F>(
+        &mut self,
+        mut build: F,
+    ) -> Result<(), ShortBuf>
+    where
+        F: FnMut(&mut OptBuilder<Target>) -> Result<(), ShortBuf>,
+    {
+        self.header_mut().set_tc(true);
+        loop {
+            self.rewind();
+            if self.opt(&mut build).is_ok() {
+                return Ok(());
+            }
+            self.rewind();
+            if !self.drop_last_record() {
+                return Err(ShortBuf);
+            }
+        }
+    }
+
+    /// Removes the last record of the answer or authority sections.
+    ///
+    /// Assumes that the additional section is empty. Returns `false` if
+    /// both sections are empty already.
+    fn drop_last_record(&mut self) -> bool {
+        let counts = self.counts();
+        let count =
+            usize::from(counts.ancount()) + usize::from(counts.nscount());
+        if count == 0 {
+            return false;
+        }
+        let mut parser = Parser::from_ref(self.as_target().as_ref());
+        if parser.seek(self.authority.answer.start).is_err() {
+            return false;
+        }
+        let mut last = parser.pos();
+        for _ in 0..count {
+            last = parser.pos();
+            if ParsedRecord::skip(&mut parser).is_err() {
+                return false;
+            }
+        }
+        self.as_target_mut().truncate(last);
+        if counts.nscount() > 0 {
+            self.counts_mut().dec_nscount();
+        } else {
+            self.counts_mut().dec_ancount();
+            self.authority.start = last;
+        }
+        self.start = last;
+        true
     }
 }
 
@@ -1235,6 +1515,7 @@ impl<Target: OctetsBuilder> AdditionalBuilder<Target> {
     pub fn rewind(&mut self) {
         self.authority.as_target_mut().truncate(self.start);
         self.counts_mut().set_arcount(0);
+        self.opt = None;
     }
 
     /// Converts the additional builder into a message builder.
@@ -1398,23 +1679,23 @@ pub trait RecordSectionBuilder {
     /// Apart from record values and references, this are tuples of the owner
     /// domain name, optionally the class (which is taken to be IN if
     /// missing), the TTL, and record data.
-    fn push(&mut self, record: impl AsRecord) -> Result<(), ShortBuf>;
+    fn push(&mut self, record: impl AsRecord) -> Result<(), OptPushError>;
 }
 
 impl<Target> RecordSectionBuilder for AnswerBuilder<Target>
 where
     Target: OctetsBuilder,
 {
-    fn push(&mut self, record: impl AsRecord) -> Result<(), ShortBuf> {
-        Self::push(self, record)
+    fn push(&mut self, record: impl AsRecord) -> Result<(), OptPushError> {
+        Self::push(self, record).map_err(Into::into)
     }
 }
 
 impl<Target: OctetsBuilder> RecordSectionBuilder
     for AuthorityBuilder<Target>
 {
-    fn push(&mut self, record: impl AsRecord) -> Result<(), ShortBuf> {
-        Self::push(self, record)
+    fn push(&mut self, record: impl AsRecord) -> Result<(), OptPushError> {
+        Self::push(self, record).map_err(Into::into)
     }
 }
 
@@ -1422,7 +1703,7 @@ impl<Target> RecordSectionBuilder for AdditionalBuilder<Target>
 where
     Target: OctetsBuilder,
 {
-    fn push(&mut self, record: impl AsRecord) -> Result<(), ShortBuf> {
+    fn push(&mut self, record: impl AsRecord) -> Result<(), OptPushError> {
         Self::push(self, record)
     }
 }
@@ -1456,12 +1737,8 @@ impl<'a, Target: OctetsBuilder> OptBuilder<'a, Target> {
         let start = additional.as_target().as_ref().len();
         let arcount = additional.counts().arcount();
 
-        let err = additional
-            .as_target_mut()
-            .append_all(|target| {
-                OptHeader::default().compose(target)?;
-                0u16.compose(target)
-            })
+        let err = OptHeader::default()
+            .compose_with_rdlen(0, additional.as_target_mut())
             .is_err();
         if err {
             return Err(ShortBuf);
@@ -1470,6 +1747,7 @@ impl<'a, Target: OctetsBuilder> OptBuilder<'a, Target> {
             additional.as_target_mut().truncate(start);
             return Err(ShortBuf);
         }
+        additional.opt = Some(start);
 
         Ok(OptBuilder {
             additional,
@@ -1483,6 +1761,20 @@ impl<'a, Target: OctetsBuilder> OptBuilder<'a, Target> {
         self.push_raw_option(opt.code(), |target| opt.compose(target))
     }
 
+    /// Appends an option with the given code and data to the OPT record.
+    ///
+    /// This is useful for passing on options received in a message
+    /// untouched, for instance, via [`Opt::iter_all`].
+    ///
+    /// [`Opt::iter_all`]: ../opt/struct.Opt.html#method.iter_all
+    pub fn push_raw(
+        &mut self,
+        code: OptionCode,
+        data: &[u8],
+    ) -> Result<(), ShortBuf> {
+        self.push_raw_option(code, |target| target.append_slice(data))
+    }
+
     /// Appends a raw option to the OPT record.
     ///
     /// The method will append an option with the given option code. The data
@@ -1612,8 +1904,9 @@ impl<'a, Target: OctetsBuilder> OptBuilder<'a, Target> {
 /// to that builder interface, the type will update the length value.
 ///
 /// Because the length is 16 bits long, the assembled message can be at most
-/// 65536 octets long, independently of the maximum length the underlying
-/// builder allows.
+/// 65535 octets long, independently of the maximum length the underlying
+/// builder allows. Attempts to append data beyond that fail with a
+/// [`ShortBuf`] error.
 #[derive(Clone, Debug)]
 pub struct StreamTarget<Target> {
     /// The underlying octets builder.
@@ -1699,6 +1992,9 @@ impl<Target: OctetsBuilder> OctetsBuilder for StreamTarget<Target> {
     type Octets = Target::Octets;
 
     fn append_slice(&mut self, slice: &[u8]) -> Result<(), ShortBuf> {
+        if self.target.len() - 2 + slice.len() > usize::from(u16::MAX) {
+            return Err(ShortBuf);
+        }
         match self.target.append_slice(slice) {
             Ok(()) => {
                 self.update_shim();
@@ -1718,6 +2014,115 @@ impl<Target: OctetsBuilder> OctetsBuilder for StreamTarget<Target> {
     }
 }
 
+//------------ LimitedTarget -------------------------------------------------
+
+/// A builder target restricting the size of the message.
+///
+/// This type wraps an octets builder and refuses to grow it beyond a given
+/// limit. Appending data that would exceed the limit fails with a
+/// [`ShortBuf`] error, leaving the builder untouched. Because all push
+/// methods of the message builders append either a complete record or
+/// nothing at all, a record that doesn’t fit will not be included at all.
+///
+/// This can be used to make sure that a response fits into the UDP payload
+/// size advertised by the requester. If pushing a record fails, the
+/// response can be finished with [`AdditionalBuilder::truncate_with_opt`].
+///
+/// The limit is applied to the octets builder wrapped by this type. When
+/// combining it with other targets, place it inside a compressor and
+/// outside a [`StreamTarget`]. In the latter case, the limit includes the
+/// two octets of the length shim.
+///
+/// [`AdditionalBuilder::truncate_with_opt`]: struct.AdditionalBuilder.html#method.truncate_with_opt
+/// [`ShortBuf`]: ../octets/struct.ShortBuf.html
+/// [`StreamTarget`]: struct.StreamTarget.html
+#[derive(Clone, Debug)]
+pub struct LimitedTarget<Target> {
+    /// The underlying octets builder.
+    target: Target,
+
+    /// The maximum length of the octets builder.
+    limit: usize,
+}
+
+impl<Target> LimitedTarget<Target> {
+    /// Creates a new limited target wrapping an octets builder.
+    ///
+    /// The target may already be longer than `limit`. In this case, all
+    /// attempts to append data will fail.
+    pub fn new(target: Target, limit: usize) -> Self {
+        LimitedTarget { target, limit }
+    }
+
+    /// Returns the current limit.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Changes the limit.
+    ///
+    /// The new limit only applies to data appended from now on. If the
+    /// target is already longer than the new limit, it is not truncated.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit
+    }
+
+    /// Returns a reference to the underlying octets builder.
+    pub fn as_target(&self) -> &Target {
+        &self.target
+    }
+
+    /// Converts the limited target into the underlying octets builder.
+    pub fn into_target(self) -> Target {
+        self.target
+    }
+}
+
+#[cfg(feature = "std")]
+impl LimitedTarget<Vec<u8>> {
+    /// Creates a limited target atop an empty `Vec<u8>`.
+    ///
+    /// The vec will be allocated with a capacity of `limit`.
+    pub fn new_vec(limit: usize) -> Self {
+        Self::new(Vec::with_capacity(limit), limit)
+    }
+}
+
+//--- AsRef, AsMut
+
+impl<Target: AsRef<[u8]>> AsRef<[u8]> for LimitedTarget<Target> {
+    fn as_ref(&self) -> &[u8] {
+        self.target.as_ref()
+    }
+}
+
+impl<Target: AsMut<[u8]>> AsMut<[u8]> for LimitedTarget<Target> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.target.as_mut()
+    }
+}
+
+//--- OctetsBuilder
+
+impl<Target: OctetsBuilder> OctetsBuilder for LimitedTarget<Target> {
+    type Octets = Target::Octets;
+
+    fn append_slice(&mut self, slice: &[u8]) -> Result<(), ShortBuf> {
+        if self.target.len() + slice.len() > self.limit {
+            return Err(ShortBuf);
+        }
+        self.target.append_slice(slice)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.target.truncate(len)
+    }
+
+    fn freeze(self) -> Self::Octets {
+        self.target.freeze()
+    }
+}
+
 //------------ StaticCompressor ----------------------------------------------
 
 /// A domain name compressor that doesn’t require an allocator.
@@ -2098,6 +2503,51 @@ impl<Target: OctetsBuilder> OctetsBuilder for TreeCompressor<Target> {
     }
 }
 
+//============ Error Types ===================================================
+
+//------------ OptPushError --------------------------------------------------
+
+/// An error happened while adding a record to the additional section.
+///
+/// Apart from running out of space, this can happen because a message may
+/// only ever contain a single OPT record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OptPushError {
+    /// The additional section already contains an OPT record.
+    DuplicateOpt,
+
+    /// An earlier OPT record can’t be replaced as it isn’t the last record.
+    OptNotLast,
+
+    /// The buffer is too short to contain the record.
+    ShortBuf,
+}
+
+//--- From
+
+impl From<ShortBuf> for OptPushError {
+    fn from(_: ShortBuf) -> OptPushError {
+        OptPushError::ShortBuf
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for OptPushError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OptPushError::DuplicateOpt => f.write_str("duplicate OPT record"),
+            OptPushError::OptNotLast => {
+                f.write_str("OPT record is not the last record")
+            }
+            OptPushError::ShortBuf => ShortBuf.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OptPushError {}
+
 //============ Testing =======================================================
 
 #[cfg(test)]
@@ -2178,6 +2628,18 @@ mod test {
         assert_eq!(rr.data(), &A::from_octets(192, 0, 2, 1));
     }
 
+    #[test]
+    fn opt_default_payload_size() {
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.opt(|_| Ok(())).unwrap();
+        let msg = Message::from_octets(msg.finish()).unwrap();
+        let opt = msg.opt().unwrap();
+        assert_eq!(opt.udp_payload_size(), opt::DEFAULT_UDP_PAYLOAD_SIZE);
+        assert_eq!(opt.udp_payload_size(), 1232);
+        assert_eq!(opt.version(), 0);
+        assert!(!opt.dnssec_ok());
+    }
+
     #[test]
     fn opt_builder() {
         let mut msg = MessageBuilder::new_vec().additional();
@@ -2200,6 +2662,193 @@ mod test {
         assert_eq!(opts.next(), Some(Ok(nsid)));
     }
 
+    #[test]
+    fn opt_builder_response() {
+        use crate::base::iana::OptRcode;
+        use crate::base::opt::rfc7873::Cookie;
+
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let mut query = MessageBuilder::new_vec().question();
+        query.push((&name, Rtype::A)).unwrap();
+        let query = query.into_message();
+
+        let mut msg = MessageBuilder::new_vec()
+            .start_answer(&query, Rcode::NoError)
+            .unwrap();
+        msg.push((&name, 86400, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        let mut msg = msg.additional();
+
+        // Build the OPT record twice. The second one must replace the
+        // first one.
+        msg.opt(|opt| {
+            opt.set_udp_payload_size(4096);
+            Ok(())
+        })
+        .unwrap();
+        msg.opt(|opt| {
+            opt.set_udp_payload_size(1232);
+            opt.set_version(0);
+            opt.set_rcode(OptRcode::Refused);
+            opt.set_dnssec_ok(true);
+            opt.push(&Cookie::new(*b"cookie!!"))
+        })
+        .unwrap();
+
+        let msg = msg.into_message();
+        assert_eq!(msg.header_counts().arcount(), 1);
+        assert_eq!(msg.header().rcode(), OptRcode::Refused.rcode());
+        let opt = msg.opt().unwrap();
+        assert_eq!(opt.udp_payload_size(), 1232);
+        assert_eq!(opt.version(), 0);
+        assert!(opt.dnssec_ok());
+        assert_eq!(
+            opt.rcode(msg.header()).to_int(),
+            OptRcode::Refused.to_int()
+        );
+        let mut cookies = opt.as_opt().iter::<Cookie>();
+        assert_eq!(cookies.next(), Some(Ok(Cookie::new(*b"cookie!!"))));
+        assert_eq!(cookies.next(), None);
+    }
+
+    #[test]
+    fn dnssec_query() {
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let msg = MessageBuilder::new_vec()
+            .dnssec_query(&name, Rtype::Dnskey, 1232, true)
+            .unwrap()
+            .into_message();
+        assert!(msg.header().rd());
+        assert!(msg.header().cd());
+        assert!(!msg.header().qr());
+        let question = msg.sole_question().unwrap();
+        assert_eq!(question.qname(), &name);
+        assert_eq!(question.qtype(), Rtype::Dnskey);
+        let opt = msg.opt().unwrap();
+        assert!(opt.dnssec_ok());
+        assert_eq!(opt.udp_payload_size(), 1232);
+
+        let msg = MessageBuilder::new_vec()
+            .dnssec_query(&name, Rtype::A, 4096, false)
+            .unwrap()
+            .into_message();
+        assert!(!msg.header().cd());
+        assert!(msg.opt().unwrap().dnssec_ok());
+    }
+
+    #[test]
+    fn stream_target_limit() {
+        let mut target = StreamTarget::new_vec();
+        target.append_slice(&[0; 0xFFFE]).unwrap();
+        assert_eq!(target.append_slice(&[0; 2]), Err(ShortBuf));
+        target.append_slice(&[0; 1]).unwrap();
+        assert_eq!(target.as_stream_slice()[..2], [0xFF, 0xFF]);
+        assert_eq!(target.append_slice(&[0; 1]), Err(ShortBuf));
+
+        // A message that doesn’t fit is refused rather than truncated.
+        let mut msg = MessageBuilder::new_stream_vec().answer();
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let data = crate::rdata::Null::new(vec![0u8; 0xFF00]);
+        msg.push((&name, 3600, data.clone())).unwrap();
+        assert_eq!(msg.push((&name, 3600, data)), Err(ShortBuf));
+        let msg = msg.finish();
+        let len = msg.as_stream_slice().len() - 2;
+        assert_eq!(
+            usize::from(u16::from_be_bytes([
+                msg.as_stream_slice()[0],
+                msg.as_stream_slice()[1]
+            ])),
+            len
+        );
+    }
+
+    #[test]
+    fn truncate_with_opt() {
+        let name = Dname::<Vec<u8>>::from_str("www.example.com").unwrap();
+        let target = LimitedTarget::new_vec(128);
+        let mut msg = MessageBuilder::from_target(target).unwrap().question();
+        msg.push((&name, Rtype::A)).unwrap();
+        let mut msg = msg.answer();
+
+        // Each record is 31 octets, the question section ends at 33 octets.
+        let mut count = 0u8;
+        let err = loop {
+            match msg.push((&name, 86400, A::from_octets(192, 0, 2, count))) {
+                Ok(()) => count += 1,
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err, ShortBuf);
+        assert_eq!(count, 3);
+        assert_eq!(msg.as_target().as_ref().len(), 33 + 3 * 31);
+        assert_eq!(msg.counts().ancount(), 3);
+
+        // Without the OPT, the message is still complete.
+        let message = Message::from_octets(msg.as_slice().to_vec()).unwrap();
+        let answer = message.answer().unwrap().limit_to::<A>();
+        assert_eq!(answer.count(), 3);
+
+        // Adding the OPT record requires dropping one answer.
+        let mut msg = msg.additional();
+        msg.truncate_with_opt(|opt| {
+            opt.set_udp_payload_size(512);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(msg.as_target().as_ref().len(), 33 + 2 * 31 + 11);
+
+        let message = msg.into_message();
+        assert!(message.header().tc());
+        assert_eq!(message.header_counts().ancount(), 2);
+        assert_eq!(message.header_counts().nscount(), 0);
+        assert_eq!(message.header_counts().arcount(), 1);
+        let answer = message.answer().unwrap().limit_to::<A>();
+        assert_eq!(
+            answer
+                .map(|record| record.unwrap().data().addr().octets()[3])
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+        assert_eq!(message.opt().unwrap().udp_payload_size(), 512);
+
+        // If the OPT doesn’t fit at all, we get an error.
+        let mut msg = MessageBuilder::from_target(LimitedTarget::new_vec(20))
+            .unwrap()
+            .additional();
+        assert!(msg.truncate_with_opt(|_| Ok(())).is_err());
+        assert!(msg.header().tc());
+    }
+
+    #[test]
+    fn opt_builder_not_last() {
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.opt(|_| Ok(())).unwrap();
+        msg.push((Dname::root_slice(), 0, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        assert_eq!(msg.opt(|_| Ok(())), Err(OptPushError::OptNotLast));
+        assert_eq!(msg.counts().arcount(), 2);
+    }
+
+    #[test]
+    fn push_duplicate_opt() {
+        use crate::base::rdata::UnknownRecordData;
+
+        let opt = (
+            Dname::root_slice(),
+            0,
+            UnknownRecordData::from_octets(Rtype::Opt, b"".as_ref()),
+        );
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.push(opt.clone()).unwrap();
+        assert_eq!(msg.push(opt.clone()), Err(OptPushError::DuplicateOpt));
+        assert_eq!(msg.counts().arcount(), 1);
+
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.opt(|_| Ok(())).unwrap();
+        assert_eq!(msg.push(opt.clone()), Err(OptPushError::DuplicateOpt));
+        assert_eq!(msg.counts().arcount(), 1);
+    }
+
     fn create_compressed<T: OctetsBuilder + AsRef<[u8]>>(target: T) -> T {
         let mut msg = MessageBuilder::from_target(target).unwrap().question();
         msg.header_mut().set_rcode(Rcode::NXDomain);
@@ -2252,4 +2901,125 @@ mod test {
         let msg = create_compressed(TreeCompressor::new(Vec::new()));
         assert_eq!(&expect[..], msg.as_ref());
     }
+
+    #[test]
+    fn opt_round_trip() {
+        use crate::base::opt::{Opt, UnknownOptData};
+
+        // NSID, an unknown option, and a cookie.
+        let rdata = b"\x00\x03\x00\x03ns1\
+                      \xfd\xe9\x00\x02xy\
+                      \x00\x0a\x00\x08abcdefgh";
+        let opt = Opt::from_octets(rdata.as_ref()).unwrap();
+
+        // Via the raw options.
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.opt(|builder| {
+            for (header, data) in opt.iter_all() {
+                builder.push_raw(header.code().into(), data)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert!(msg.as_slice().ends_with(rdata));
+
+        // Via unknown option data.
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.opt(|builder| {
+            for option in opt.iter::<UnknownOptData<_>>() {
+                builder.push(&option.unwrap())?;
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert!(msg.as_slice().ends_with(rdata));
+        assert_eq!(
+            msg.as_message().opt().unwrap().option_count(),
+            opt.option_count()
+        );
+    }
+
+    #[test]
+    fn push_raw_record() {
+        use crate::base::iana::Class;
+        use crate::base::octets::Octets64;
+        use crate::rdata::AllRecordData;
+
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+
+        // A captured response with an uncompressed answer record.
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push((&name, 86400, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        let captured = msg.into_message();
+        let mut answer = captured.answer().unwrap();
+        let start = answer.pos();
+        answer.next().unwrap().unwrap();
+        let raw = &captured.as_slice()[start..answer.pos()];
+
+        // Forward the record into the answer and authority sections of a
+        // new message.
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push_raw_record(raw).unwrap();
+        let mut msg = msg.authority();
+        msg.push_raw_record(raw).unwrap();
+        msg.push((&name, 3600, Ns::new(name.clone()))).unwrap();
+        let mut msg = msg.additional();
+        msg.push_raw_record(raw).unwrap();
+        let msg = msg.into_message();
+
+        let counts = msg.header_counts();
+        assert_eq!(counts.ancount(), 1);
+        assert_eq!(counts.nscount(), 2);
+        assert_eq!(counts.arcount(), 1);
+        for item in &msg {
+            let (record, _) = item.unwrap();
+            let record = record
+                .into_record::<AllRecordData<_, _>>()
+                .unwrap()
+                .unwrap();
+            assert_eq!(*record.owner(), name);
+            assert_eq!(record.class(), Class::In);
+        }
+        let record = msg
+            .answer()
+            .unwrap()
+            .limit_to::<A>()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.ttl(), 86400);
+        assert_eq!(*record.data(), A::from_octets(192, 0, 2, 1));
+
+        // A record that doesn’t fit leaves the message unchanged.
+        let mut msg = MessageBuilder::from_target(Octets64::default())
+            .unwrap()
+            .answer();
+        msg.push_raw_record(raw).unwrap();
+        assert_eq!(msg.as_slice().len(), 12 + raw.len());
+        assert!(msg.push_raw_record(raw).is_err());
+        assert_eq!(msg.as_slice().len(), 12 + raw.len());
+        assert_eq!(msg.counts().ancount(), 1);
+    }
+
+    #[test]
+    fn push_raw_opt_record() {
+        let mut msg = MessageBuilder::new_vec().additional();
+        msg.push_raw_record(b"\0\0\x29\x04\xd0\0\0\0\0\0\0")
+            .unwrap();
+        // The raw OPT record is replaced rather than added a second time.
+        msg.opt(|opt| {
+            opt.set_udp_payload_size(512);
+            Ok(())
+        })
+        .unwrap();
+        // A second raw OPT record is refused.
+        assert_eq!(
+            msg.push_raw_record(b"\0\0\x29\x04\xd0\0\0\0\0\0\0"),
+            Err(OptPushError::DuplicateOpt)
+        );
+        let msg = msg.into_message();
+        assert_eq!(msg.header_counts().arcount(), 1);
+        assert_eq!(msg.opt().unwrap().udp_payload_size(), 512);
+    }
 }
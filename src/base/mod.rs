@@ -62,6 +62,7 @@
 //! * [serial](serial/index.html) for serial numbers of zones, and
 //! * [record](record/index.html) for DNS resource records including record
 //!   data,
+//! * [rrset](rrset/index.html) for sets of resource records,
 //! * [rdata](rdata/index.html) for all the individual record types.
 //!
 //!
@@ -100,7 +101,8 @@ pub use self::message::{Message, QuestionSection, RecordSection};
 #[cfg(feature = "std")]
 pub use self::message_builder::TreeCompressor;
 pub use self::message_builder::{
-    MessageBuilder, RecordSectionBuilder, StaticCompressor, StreamTarget,
+    LimitedTarget, MessageBuilder, RecordSectionBuilder, StaticCompressor,
+    StreamTarget,
 };
 pub use self::name::{
     Dname, DnameBuilder, ParsedDname, RelativeDname, ToDname, ToRelativeDname,
@@ -111,7 +113,9 @@ pub use self::octets::{Compose, Parser, ShortBuf};
 pub use self::question::Question;
 pub use self::rdata::{ParseRecordData, RecordData, UnknownRecordData};
 pub use self::record::{ParsedRecord, Record, RecordHeader};
-pub use self::serial::Serial;
+#[cfg(feature = "std")]
+pub use self::rrset::Rrset;
+pub use self::serial::{Serial, Timestamp};
 
 //--- Modules
 
@@ -128,5 +132,7 @@ pub mod opt;
 pub mod question;
 pub mod rdata;
 pub mod record;
+#[cfg(feature = "std")]
+pub mod rrset;
 pub mod serial;
 pub mod str;
@@ -263,6 +263,36 @@ impl<Builder: OctetsBuilder> DnameBuilder<Builder> {
         Ok(())
     }
 
+    /// Appends a complete name from a sequence of characters.
+    ///
+    /// This is the helper behind the `FromStr` implementations of the
+    /// various domain name types and should only be used on an empty
+    /// builder. Unlike [`append_chars`][Self::append_chars], it accepts a
+    /// single dot as the representation of the root name and rejects an
+    /// empty sequence of characters.
+    ///
+    /// Returns whether the name is absolute, i.e., whether the sequence
+    /// ended in a dot.
+    pub(super) fn append_name_chars<C: IntoIterator<Item = char>>(
+        &mut self,
+        chars: C,
+    ) -> Result<bool, FromStrError> {
+        let mut chars = chars.into_iter().peekable();
+        match chars.peek() {
+            None => return Err(FromStrError::EmptyName),
+            Some('.') => {
+                chars.next();
+                if chars.peek().is_none() {
+                    return Ok(true);
+                }
+                return Err(FromStrError::EmptyLabel);
+            }
+            _ => {}
+        }
+        self.append_chars(chars)?;
+        Ok(!self.in_label())
+    }
+
     /// Finishes building the name and returns the resulting relative name.
     ///
     /// If there currently is a label being built, ends the label first
@@ -458,6 +488,14 @@ pub enum FromStrError {
     /// An empty label was encountered.
     EmptyLabel,
 
+    /// The string was empty.
+    ///
+    /// The root name has to be given as a single dot.
+    EmptyName,
+
+    /// An absolute name was encountered where a relative one was expected.
+    AbsoluteName,
+
     /// A binary label was encountered.
     BinaryLabel,
 
@@ -515,6 +553,12 @@ impl fmt::Display for FromStrError {
             FromStrError::EmptyLabel => {
                 f.write_str("an empty label was encountered")
             }
+            FromStrError::EmptyName => {
+                f.write_str("an empty domain name was encountered")
+            }
+            FromStrError::AbsoluteName => {
+                f.write_str("an absolute domain name was encountered")
+            }
             FromStrError::BinaryLabel => {
                 f.write_str("a binary label was encountered")
             }
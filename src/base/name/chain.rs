@@ -24,6 +24,13 @@ use core::{fmt, iter};
 /// A chain on an uncertain name is special in that the second name is only
 /// used if the uncertain name is relative.
 ///
+/// When composed, the labels of both names are written directly into the
+/// target, i.e., no intermediary name is ever assembled. An absolute chain
+/// can also be given to an octets builder’s
+/// [`append_compressed_dname`][crate::base::octets::OctetsBuilder::append_compressed_dname]
+/// for name compression, while a relative chain, like any relative name,
+/// is never compressed.
+///
 /// [`RelativeDname`]: struct.RelativeDname.html#method.chain
 /// [`Chain`]: #method.chain
 /// [`ToDname`]: trait.ToDname.html
@@ -522,4 +529,76 @@ mod test {
             .unwrap();
         assert_eq!(buf, b"\x07example\x03com\x00");
     }
+
+    /// Tests that compressing a chain in a message works as expected.
+    #[test]
+    #[cfg(feature = "std")]
+    fn compose_compressed() {
+        use crate::base::iana::{Class, Rtype};
+        use crate::base::message::Message;
+        use crate::base::message_builder::{
+            MessageBuilder, StaticCompressor, TreeCompressor,
+        };
+        use crate::rdata::Cname;
+        use std::string::ToString;
+        use std::vec::Vec;
+
+        fn check<Target>(target: Target) -> Vec<u8>
+        where
+            Target: crate::base::octets::OctetsBuilder,
+            Target::Octets: AsRef<[u8]>,
+        {
+            let w = RelativeDname::from_octets(b"\x03www".as_ref()).unwrap();
+            let ecr = Dname::from_octets(b"\x07example\x03com\x00".as_ref())
+                .unwrap();
+            let mut msg =
+                MessageBuilder::from_target(target).unwrap().answer();
+            msg.push((
+                ecr.clone(),
+                Class::In,
+                3600,
+                Cname::new(w.clone().chain(ecr.clone()).unwrap()),
+            ))
+            .unwrap();
+            msg.push((
+                w.chain(ecr.clone()).unwrap(),
+                Class::In,
+                3600,
+                Cname::new(ecr),
+            ))
+            .unwrap();
+            msg.finish().as_ref().to_vec()
+        }
+
+        for msg in [
+            check(StaticCompressor::new(Vec::new())),
+            check(TreeCompressor::new(Vec::new())),
+        ]
+        .iter()
+        {
+            // Header, then the first record with the full owner at 12 and
+            // the target compressed to `www` and a pointer to the owner,
+            // then the second record with the owner as a pointer to the
+            // target and the target as a pointer to the first owner.
+            assert_eq!(
+                &msg[12..],
+                b"\x07example\x03com\x00\
+                  \x00\x05\x00\x01\x00\x00\x0e\x10\x00\x06\
+                  \x03www\xc0\x0c\
+                  \xc0\x23\
+                  \x00\x05\x00\x01\x00\x00\x0e\x10\x00\x02\
+                  \xc0\x0c"
+                    .as_ref()
+            );
+
+            let msg = Message::from_octets(msg.as_slice()).unwrap();
+            let mut answer = msg.answer().unwrap().limit_to::<Cname<_>>();
+            let first = answer.next().unwrap().unwrap();
+            assert_eq!(first.rtype(), Rtype::Cname);
+            assert_eq!(first.data().cname().to_string(), "www.example.com");
+            let second = answer.next().unwrap().unwrap();
+            assert_eq!(second.owner().to_string(), "www.example.com");
+            assert_eq!(second.data().cname().to_string(), "example.com");
+        }
+    }
 }
@@ -8,13 +8,9 @@ use super::label::{Label, LabelTypeError, SplitLabelError};
 use super::relative::{DnameIter, RelativeDname};
 use super::traits::{ToDname, ToLabelIter};
 #[cfg(feature = "master")]
-use super::uncertain::UncertainDname;
-#[cfg(feature = "master")]
-use crate::master::scan::{
-    CharSource, Scan, ScanError, Scanner, SyntaxError,
-};
+use crate::master::scan::{CharSource, Scan, ScanError, Scanner};
 #[cfg(feature = "bytes")]
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::str::FromStr;
 /// Uncompressed, absolute domain names.
 ///
@@ -89,6 +85,9 @@ impl<Octets> Dname<Octets> {
     /// anyway. In most cases, this is likely what you want. If it isn’t,
     /// though, use [`UncertainDname`] instead to be able to check.
     ///
+    /// The root name is represented by a single dot. An empty sequence is
+    /// not a valid name and results in an error.
+    ///
     /// [`UncertainDname`]: enum.UncertainDname.html
     pub fn from_chars<C>(chars: C) -> Result<Self, FromStrError>
     where
@@ -97,7 +96,7 @@ impl<Octets> Dname<Octets> {
         C: IntoIterator<Item = char>,
     {
         let mut builder = DnameBuilder::<Octets::Builder>::new();
-        builder.append_chars(chars)?;
+        builder.append_name_chars(chars)?;
         builder.into_dname().map_err(Into::into)
     }
 
@@ -149,6 +148,10 @@ impl Dname<[u8]> {
             if label.is_root() {
                 if tail.is_empty() {
                     break;
+                } else if Label::split_from(tail).is_ok() {
+                    // The root label is followed by more labels, so it
+                    // really is an empty label in the middle of the name.
+                    return Err(DnameError::EmptyLabel);
                 } else {
                     return Err(DnameError::TrailingData);
                 }
@@ -167,6 +170,22 @@ impl Dname<&'static [u8]> {
     pub fn root_ref() -> Self {
         Self::root()
     }
+
+    /// Creates a domain name from a static octets slice.
+    ///
+    /// This is intended for domain name constants and test fixtures given
+    /// in wire format, such as `b"\x07example\x03com\0"`.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `octets` does not contain a correctly
+    /// encoded absolute domain name.
+    pub fn from_static(octets: &'static [u8]) -> Self {
+        match Self::from_octets(octets) {
+            Ok(name) => name,
+            Err(err) => panic!("invalid static domain name: {}", err),
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -193,6 +212,22 @@ impl Dname<Bytes> {
     pub fn bytes_from_str(s: &str) -> Result<Self, FromStrError> {
         FromStr::from_str(s)
     }
+
+    /// Creates a hostname atop a Bytes from its string representation.
+    ///
+    /// In addition to parsing the string like
+    /// [`bytes_from_str`][Self::bytes_from_str], the method requires the
+    /// resulting name to be a valid hostname as determined by
+    /// [`is_hostname`][Self::is_hostname]. This is useful for applications
+    /// that want to be strict about their input.
+    pub fn vetted(s: &str) -> Result<Self, NameError> {
+        let res = Self::bytes_from_str(s)?;
+        if res.is_hostname() {
+            Ok(res)
+        } else {
+            Err(NameError::NotHostname)
+        }
+    }
 }
 
 /// # Conversions
@@ -252,6 +287,85 @@ impl<Octets: AsRef<[u8]> + ?Sized> Dname<Octets> {
     pub fn is_root(&self) -> bool {
         self.0.as_ref().len() == 1
     }
+
+    /// Returns whether the name is a valid hostname.
+    ///
+    /// Hostnames as defined in RFC 952 and RFC 1123 are a stricter subset
+    /// of domain names: all labels but the final root label must be valid
+    /// hostname labels as determined by [`Label::is_hostname_label`]. The
+    /// root name itself is not considered a hostname.
+    ///
+    /// Note that this is a matter of convention only. Any name that fails
+    /// this test is still a perfectly valid domain name.
+    pub fn is_hostname(&self) -> bool {
+        !self.is_root()
+            && self
+                .iter()
+                .filter(|label| !label.is_root())
+                .all(Label::is_hostname_label)
+    }
+
+    /// Returns whether the name is a service name.
+    ///
+    /// A service name starts with one or more service labels as determined
+    /// by [`Label::is_service_label`], such as `_443._tcp.example.com` or
+    /// `_dmarc.example.com`. All remaining labels but the final root label
+    /// must be valid hostname labels.
+    pub fn is_service_name(&self) -> bool {
+        let mut labels = self.iter().filter(|label| !label.is_root());
+        match labels.next() {
+            Some(label) if label.is_service_label() => {}
+            _ => return false,
+        }
+        let mut labels = labels.skip_while(|label| label.is_service_label());
+        labels.all(Label::is_hostname_label)
+    }
+}
+
+/// # Case Randomization
+///
+/// In order to make spoofing of responses harder, a resolver can randomly
+/// change the case of the ASCII letters in a query name and check that the
+/// response repeats the name exactly. This technique is commonly known as
+/// ‘0x20 encoding’ after the bit that distinguishes upper and lower case
+/// letters in ASCII.
+///
+/// Since all other comparisons of domain names ignore ASCII case, the
+/// randomized name can be used in place of the original name everywhere.
+/// Only the check of the response requires the exact comparison provided by
+/// [`matches_0x20`][Self::matches_0x20].
+impl<Octets: AsRef<[u8]> + ?Sized> Dname<Octets> {
+    /// Returns a copy of the name with the case of letters randomized.
+    ///
+    /// The case of each ASCII letter in the name is flipped or left alone
+    /// at random using `rng`. All other octets remain unchanged.
+    #[cfg(feature = "bytes")]
+    pub fn randomize_case<R: rand::Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Dname<Bytes> {
+        let mut octets = BytesMut::from(self.as_slice());
+        // See `RelativeDname::to_lowercase` for why we can simply look at
+        // all the octets.
+        for ch in octets.iter_mut() {
+            if ch.is_ascii_alphabetic() && rng.gen() {
+                *ch ^= 0x20
+            }
+        }
+        unsafe { Dname::from_octets_unchecked(octets.freeze()) }
+    }
+
+    /// Returns whether the name is equal to another name including case.
+    ///
+    /// Unlike the comparison via `PartialEq`, this method compares the
+    /// names octet by octet without ignoring ASCII case. It can be used to
+    /// check that a response to a query with a [randomized
+    /// name][Self::randomize_case] repeats the name exactly.
+    pub fn matches_0x20<N: ToDname + ?Sized>(&self, other: &N) -> bool {
+        self.iter()
+            .map(Label::as_slice)
+            .eq(other.iter_labels().map(Label::as_slice))
+    }
 }
 
 /// # Working with Labels
@@ -309,6 +423,38 @@ impl<Octets: AsRef<[u8]> + ?Sized> Dname<Octets> {
         <Self as ToLabelIter>::ends_with(self, base)
     }
 
+    /// Returns the closest enclosing name out of a set of candidates.
+    ///
+    /// Given a set of names, typically the apexes of known zones, the
+    /// method returns the longest name that is a suffix of `self`, i.e.,
+    /// the deepest zone the name belongs to. Since the comparison of names
+    /// ignores ASCII case, two candidates can only be of equal length and
+    /// both be a suffix if they are the same name. In this case, the first
+    /// of them is returned.
+    ///
+    /// Returns `None` if none of the candidates is a suffix of `self`.
+    pub fn closest_enclosing<'a, N, I>(
+        &'a self,
+        candidates: I,
+    ) -> Option<&'a N>
+    where
+        N: ToLabelIter<'a> + ?Sized + 'a,
+        I: IntoIterator<Item = &'a N>,
+    {
+        let mut res: Option<(&'a N, usize)> = None;
+        for candidate in candidates {
+            if !self.ends_with(candidate) {
+                continue;
+            }
+            let count = candidate.iter_labels().count();
+            match res {
+                Some((_, best)) if best >= count => {}
+                _ => res = Some((candidate, count)),
+            }
+        }
+        res.map(|(name, _)| name)
+    }
+
     /// Returns whether an index points to the first byte of a non-root label.
     pub fn is_label_start(&self, mut index: usize) -> bool {
         if index == 0 {
@@ -316,7 +462,7 @@ impl<Octets: AsRef<[u8]> + ?Sized> Dname<Octets> {
         }
         let mut tmp = self.as_slice();
         while !tmp.is_empty() {
-            let (label, tail) = Label::split_from(tmp).unwrap();
+            let (label, tail) = Label::split_from_valid(tmp);
             let len = label.len() + 1;
             if index < len || len == 1 {
                 // length 1: root label.
@@ -592,6 +738,43 @@ impl<Octets: AsRef<[u8]>> Dname<Octets> {
             Err(self)
         }
     }
+
+    /// Applies the substitution of a DNAME record to the name.
+    ///
+    /// The DNAME record is given through its `owner` and `target`. If the
+    /// name is below the owner, the owner suffix is replaced by `target`
+    /// and the resulting name is returned. As described in RFC 6672, the
+    /// owner itself is not substituted.
+    ///
+    /// Returns `None` if the name isn’t below `owner` or if the resulting
+    /// name would be longer than 255 octets.
+    #[cfg(feature = "bytes")]
+    pub fn apply_dname<N, T>(
+        &self,
+        owner: &N,
+        target: &T,
+    ) -> Option<Dname<Bytes>>
+    where
+        N: ToDname + ?Sized,
+        T: ToDname + ?Sized,
+    {
+        let slice = self.0.as_ref();
+        let owner_len = owner.len();
+        if slice.len() <= owner_len || !self.ends_with(owner) {
+            return None;
+        }
+        let prefix = &slice[..slice.len() - owner_len];
+        if prefix.len() + target.len() > 255 {
+            return None;
+        }
+        let mut res =
+            bytes::BytesMut::with_capacity(prefix.len() + target.len());
+        res.extend_from_slice(prefix);
+        for label in target.iter_labels() {
+            label.compose(&mut res).ok()?;
+        }
+        Some(unsafe { Dname::from_octets_unchecked(res.freeze()) })
+    }
 }
 
 //--- Deref and AsRef
@@ -637,6 +820,9 @@ where
     /// whether it ends in a dot or not. If you need to be able to distinguish
     /// between those two cases, you can use [`UncertainDname`] instead.
     ///
+    /// The string `"."` results in the root name while an empty string is
+    /// rejected with [`FromStrError::EmptyName`].
+    ///
     /// [`UncertainDname`]: struct.UncertainDname.html
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::from_chars(s.chars())
@@ -810,18 +996,7 @@ impl Scan for Dname<Bytes> {
     fn scan<C: CharSource>(
         scanner: &mut Scanner<C>,
     ) -> Result<Self, ScanError> {
-        let pos = scanner.pos();
-        let name = match UncertainDname::scan(scanner)? {
-            UncertainDname::Relative(name) => name,
-            UncertainDname::Absolute(name) => return Ok(name),
-        };
-        let origin = match *scanner.origin() {
-            Some(ref origin) => origin,
-            None => return Err((SyntaxError::NoOrigin, pos).into()),
-        };
-        name.into_builder()
-            .append_origin(origin)
-            .map_err(|err| (SyntaxError::from(err), pos).into())
+        scanner.scan_dname()
     }
 }
 
@@ -907,6 +1082,13 @@ pub enum DnameError {
     /// The name did not end with the root label.
     RelativeName,
 
+    /// The name contained an empty label before its end.
+    ///
+    /// Only the final root label of a name may be empty. A zero octet
+    /// followed by more labels is therefore reported as this error rather
+    /// than as trailing data.
+    EmptyLabel,
+
     /// There was more data after the root label was encountered.
     TrailingData,
 
@@ -939,6 +1121,7 @@ impl From<DnameError> for FormError {
             DnameError::CompressedName => "compressed domain name",
             DnameError::LongName => "long domain name",
             DnameError::RelativeName => "relative domain name",
+            DnameError::EmptyLabel => "empty label in domain name",
             DnameError::TrailingData => "trailing data in buffer",
             DnameError::ShortInput => "unexpected end of buffer",
         })
@@ -965,6 +1148,7 @@ impl fmt::Display for DnameError {
             }
             DnameError::LongName => f.write_str("long domain name"),
             DnameError::RelativeName => f.write_str("relative name"),
+            DnameError::EmptyLabel => f.write_str("empty label"),
             DnameError::TrailingData => f.write_str("trailing data"),
             DnameError::ShortInput => ParseError::ShortInput.fmt(f),
         }
@@ -974,6 +1158,40 @@ impl fmt::Display for DnameError {
 #[cfg(feature = "std")]
 impl std::error::Error for DnameError {}
 
+//------------ NameError -----------------------------------------------------
+
+/// A string could not be converted into a hostname.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NameError {
+    /// The string could not be parsed into a domain name.
+    Parse(FromStrError),
+
+    /// The string is a domain name but not a valid hostname.
+    NotHostname,
+}
+
+//--- From
+
+impl From<FromStrError> for NameError {
+    fn from(err: FromStrError) -> NameError {
+        NameError::Parse(err)
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NameError::Parse(ref err) => err.fmt(f),
+            NameError::NotHostname => f.write_str("not a valid hostname"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NameError {}
+
 //============ Testing =======================================================
 //
 // Some of the helper functions herein are resused by the tests of other
@@ -1027,12 +1245,71 @@ pub(crate) mod test {
         assert_eq!(Dname::root_slice().as_slice(), b"\0");
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_static() {
+        let name = Dname::from_static(b"\x03www\x07example\x03com\0");
+        assert_eq!(name, Dname::vec_from_str("www.example.com").unwrap());
+        assert_eq!(Dname::from_static(b"\0"), Dname::root_ref());
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_static_relative() {
+        let _ = Dname::from_static(b"\x03www");
+    }
+
     #[cfg(feature = "bytes")]
     #[test]
     fn root_bytes() {
         assert_eq!(Dname::root_bytes().as_slice(), b"\0");
     }
 
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn randomize_case() {
+        use rand::rngs::mock::StepRng;
+        use rand::SeedableRng;
+
+        let name = Dname::bytes_from_str("www.example-1.com").unwrap();
+
+        // A mock RNG that always returns true flips every letter.
+        let flipped = name.randomize_case(&mut StepRng::new(!0, 0));
+        assert_eq!(flipped.as_slice(), b"\x03WWW\x09EXAMPLE-1\x03COM\0");
+        assert_eq!(flipped, name);
+        assert!(!flipped.matches_0x20(&name));
+        assert!(flipped.matches_0x20(
+            &Dname::bytes_from_str("WWW.EXAMPLE-1.COM").unwrap()
+        ));
+
+        // A real RNG keeps the name intact apart from case.
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x20);
+        for _ in 0..16 {
+            let random = name.randomize_case(&mut rng);
+            assert_eq!(random, name);
+            assert_eq!(random.as_slice().len(), name.as_slice().len());
+            assert!(random.matches_0x20(&random.clone()));
+        }
+
+        assert!(Dname::root_bytes().randomize_case(&mut rng).is_root());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn matches_0x20() {
+        let name = Dname::vec_from_str("WwW.example.com").unwrap();
+        assert!(name.matches_0x20(&name));
+        assert!(name
+            .matches_0x20(&Dname::vec_from_str("WwW.example.com").unwrap()));
+        assert!(!name
+            .matches_0x20(&Dname::vec_from_str("www.example.com").unwrap()));
+        assert!(!name
+            .matches_0x20(&Dname::vec_from_str("WwW.example.org").unwrap()));
+        assert!(
+            !name.matches_0x20(&Dname::vec_from_str("example.com").unwrap())
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn from_slice() {
@@ -1077,7 +1354,28 @@ pub(crate) mod test {
         assert!(Dname::from_slice(&buf).is_err());
 
         // trailing data
-        assert!(Dname::from_slice(b"\x03com\0\x03www\0").is_err());
+        assert_eq!(
+            Dname::from_slice(b"\x03com\0\xc0"),
+            Err(DnameError::TrailingData)
+        );
+
+        // empty labels other than the root label
+        assert_eq!(
+            Dname::from_slice(b"\x03com\0\x03www\0"),
+            Err(DnameError::EmptyLabel)
+        );
+        assert_eq!(
+            Dname::from_slice(b"\x00\x03com"),
+            Err(DnameError::EmptyLabel)
+        );
+        assert_eq!(
+            Dname::from_slice(b"\x00\x03com\x00"),
+            Err(DnameError::EmptyLabel)
+        );
+        assert_eq!(
+            Dname::from_octets(b"\x00\x03com".as_ref()),
+            Err(DnameError::EmptyLabel)
+        );
 
         // bad label heads: compressed, other types.
         assert_eq!(
@@ -1112,6 +1410,43 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    fn is_hostname() {
+        assert!(Dname::from_slice(b"\x03www\x07example\x03com\0")
+            .unwrap()
+            .is_hostname());
+        assert!(Dname::from_slice(b"\x04www1\x06ex-ple\x03com\0")
+            .unwrap()
+            .is_hostname());
+        assert!(!Dname::from_slice(b"\x03w_w\x07example\x03com\0")
+            .unwrap()
+            .is_hostname());
+        assert!(!Dname::from_slice(b"\x04-www\x07example\x03com\0")
+            .unwrap()
+            .is_hostname());
+        assert!(!Dname::root_slice().is_hostname());
+    }
+
+    #[test]
+    fn is_service_name() {
+        let dmarc =
+            Dname::from_slice(b"\x06_dmarc\x07example\x03com\0").unwrap();
+        assert!(dmarc.is_service_name());
+        assert!(!dmarc.is_hostname());
+        let smtp =
+            Dname::from_slice(b"\x03_25\x04_tcp\x04mail\x07example\x03com\0")
+                .unwrap();
+        assert!(smtp.is_service_name());
+        assert!(!smtp.is_hostname());
+        assert!(!Dname::from_slice(b"\x03www\x07example\x03com\0")
+            .unwrap()
+            .is_service_name());
+        assert!(!Dname::from_slice(b"\x04_tcp\x03w_w\x03com\0")
+            .unwrap()
+            .is_service_name());
+        assert!(!Dname::root_slice().is_service_name());
+    }
+
     #[test]
     fn is_root() {
         assert_eq!(Dname::from_slice(b"\0").unwrap().is_root(), true);
@@ -1291,6 +1626,30 @@ pub(crate) mod test {
         }
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn closest_enclosing() {
+        let com = Dname::vec_from_str("com").unwrap();
+        let example = Dname::vec_from_str("EXAMPLE.com").unwrap();
+        let org = Dname::vec_from_str("example.org").unwrap();
+        let name = Dname::vec_from_str("a.b.example.com").unwrap();
+
+        assert_eq!(
+            name.closest_enclosing(&[com.clone(), example.clone()]),
+            Some(&example)
+        );
+        assert_eq!(
+            name.closest_enclosing(&[example.clone(), com.clone()]),
+            Some(&example)
+        );
+        assert_eq!(
+            name.closest_enclosing(&[com.clone(), org.clone()]),
+            Some(&com)
+        );
+        assert_eq!(name.closest_enclosing(&[org]), None);
+        assert_eq!(com.closest_enclosing(&[example]), None);
+    }
+
     #[test]
     fn is_label_start() {
         let wecr = Dname::from_slice(b"\x03www\x07example\x03com\0").unwrap();
@@ -1314,6 +1673,12 @@ pub(crate) mod test {
         assert!(wecr.is_label_start(16)); // \0
         assert!(!wecr.is_label_start(17)); //
         assert!(!wecr.is_label_start(18)); //
+        assert!(!wecr.is_label_start(usize::MAX));
+
+        let root = Dname::root_slice();
+        assert!(root.is_label_start(0));
+        assert!(!root.is_label_start(1));
+        assert!(!root.is_label_start(usize::MAX));
     }
 
     #[test]
@@ -1592,6 +1957,70 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn vetted() {
+        assert_eq!(
+            Dname::vetted("www.example.com").unwrap().as_slice(),
+            b"\x03www\x07example\x03com\0"
+        );
+        assert_eq!(
+            Dname::vetted("_443._tcp.example.com"),
+            Err(NameError::NotHostname)
+        );
+        assert_eq!(Dname::vetted("."), Err(NameError::NotHostname));
+        assert_eq!(
+            Dname::vetted("www..example.com"),
+            Err(NameError::Parse(FromStrError::EmptyLabel))
+        );
+        assert_eq!(
+            Dname::vetted(""),
+            Err(NameError::Parse(FromStrError::EmptyName))
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "bytes", feature = "std"))]
+    fn apply_dname() {
+        let owner = Dname::bytes_from_str("example.com").unwrap();
+        let target = Dname::bytes_from_str("example.net").unwrap();
+
+        assert_eq!(
+            Dname::bytes_from_str("foo.example.com")
+                .unwrap()
+                .apply_dname(&owner, &target)
+                .unwrap()
+                .as_slice(),
+            b"\x03foo\x07example\x03net\0"
+        );
+        assert_eq!(
+            Dname::bytes_from_str("a.b.example.com")
+                .unwrap()
+                .apply_dname(&owner, &Dname::root_slice())
+                .unwrap()
+                .as_slice(),
+            b"\x01a\x01b\0"
+        );
+
+        // The owner itself and names outside it aren’t substituted.
+        assert!(owner.apply_dname(&owner, &target).is_none());
+        assert!(Dname::bytes_from_str("foo.example.org")
+            .unwrap()
+            .apply_dname(&owner, &target)
+            .is_none());
+
+        // The result must not exceed 255 octets.
+        let mut long = std::string::String::new();
+        for _ in 0..30 {
+            long.push_str("1234567.");
+        }
+        long.push_str("example.com");
+        let long = Dname::bytes_from_str(&long).unwrap();
+        assert!(long.apply_dname(&owner, &owner).is_some());
+        let longer = Dname::bytes_from_str("longer-example.net").unwrap();
+        assert!(long.apply_dname(&owner, &longer).is_none());
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn parse() {
@@ -1681,6 +2110,30 @@ pub(crate) mod test {
                 .as_slice(),
             b"\x03www\x07example\x03com\0"
         );
+        assert_eq!(
+            Dname::<Vec<u8>>::from_str("com").unwrap().as_slice(),
+            b"\x03com\0"
+        );
+        assert_eq!(
+            Dname::<Vec<u8>>::from_str("com.").unwrap().as_slice(),
+            b"\x03com\0"
+        );
+        assert_eq!(
+            Dname::<Vec<u8>>::from_str(".").unwrap().as_slice(),
+            b"\0"
+        );
+        assert_eq!(
+            Dname::<Vec<u8>>::from_str(""),
+            Err(FromStrError::EmptyName)
+        );
+        assert_eq!(
+            Dname::<Vec<u8>>::from_str(".."),
+            Err(FromStrError::EmptyLabel)
+        );
+        assert_eq!(
+            Dname::<Vec<u8>>::from_str(".com"),
+            Err(FromStrError::EmptyLabel)
+        );
     }
 
     #[test]
@@ -34,6 +34,14 @@ use core::{borrow, cmp, fmt, hash, ops};
 /// `Label` differs from an octets slice in how it compares: as labels are to
 /// be case-insensitive, all the comparison traits as well as `Hash` are
 /// implemented ignoring ASCII-case.
+///
+/// The `Display` implementation formats the label in presentation format.
+/// Following the rules of RFC 1035, the space, dot, and backslash
+/// characters are escaped with a backslash and all octets outside the
+/// printable ASCII range of 0x20 to 0x7E are escaped as a backslash
+/// followed by their three digit decimal value. This makes sure the output
+/// can always be turned back into the same label by the `FromStr`
+/// implementations of the domain name types.
 pub struct Label([u8]);
 
 /// # Creation
@@ -136,6 +144,26 @@ impl Label {
         ))
     }
 
+    /// Splits a label from the beginning of the octets of a valid name.
+    ///
+    /// This is used by the domain name types to walk over their own octets.
+    /// If a name was created through one of its safe constructors, these
+    /// octets are a sequence of complete normal labels, so splitting off a
+    /// label from a non-empty remainder of them never fails. The invariant
+    /// can only be violated by misusing one of the unsafe
+    /// `from_octets_unchecked` functions.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `slice` doesn’t start with a complete normal
+    /// label, i.e., if the invariant has been violated.
+    pub(super) fn split_from_valid(slice: &[u8]) -> (&Self, &[u8]) {
+        match Self::split_from(slice) {
+            Ok(res) => res,
+            Err(err) => panic!("invalid label in domain name: {}", err),
+        }
+    }
+
     /// Iterates over the labels in some part of an octets slice.
     ///
     /// The first label is assumed to start at index `start`.
@@ -233,6 +261,43 @@ impl Label {
         self.0.len() == 1 && self.0[0] == b'*'
     }
 
+    /// Returns whether the label is a valid hostname label.
+    ///
+    /// Following RFC 952 and RFC 1123, a hostname label is non-empty and
+    /// consists of ASCII letters, digits, and hyphens only – often called
+    /// ‘LDH’ – and must neither start nor end with a hyphen.
+    pub fn is_hostname_label(&self) -> bool {
+        match (self.0.first(), self.0.last()) {
+            (Some(&first), Some(&last)) => {
+                first != b'-'
+                    && last != b'-'
+                    && self
+                        .0
+                        .iter()
+                        .all(|&ch| ch.is_ascii_alphanumeric() || ch == b'-')
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the label is a service label.
+    ///
+    /// Service labels are used by protocols such as SRV, TLSA, or DMARC to
+    /// form names like `_443._tcp.example.com`. By convention, they start
+    /// with an underscore followed by at least one ASCII letter, digit, or
+    /// hyphen. Such labels are not valid hostname labels.
+    pub fn is_service_label(&self) -> bool {
+        match self.0.split_first() {
+            Some((b'_', tail)) => {
+                !tail.is_empty()
+                    && tail
+                        .iter()
+                        .all(|&ch| ch.is_ascii_alphanumeric() || ch == b'-')
+            }
+            _ => false,
+        }
+    }
+
     /// Returns the length of the composed version of the label.
     ///
     /// This length is one more than the length of the label as their is a
@@ -403,6 +468,7 @@ impl fmt::Debug for Label {
 //
 //  This keeps the label in wire format, so the first octet is the length
 //  octet, the remainder is the content.
+#[derive(Clone, Copy)]
 pub struct OwnedLabel([u8; 64]);
 
 impl OwnedLabel {
@@ -534,6 +600,20 @@ impl hash::Hash for OwnedLabel {
     }
 }
 
+//--- Display and Debug
+
+impl fmt::Display for OwnedLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_label().fmt(f)
+    }
+}
+
+impl fmt::Debug for OwnedLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("OwnedLabel").field(&self.as_label()).finish()
+    }
+}
+
 //------------ SliceLabelsIter -----------------------------------------------
 
 /// An iterator over the labels in an octets slice.
@@ -607,6 +687,14 @@ pub enum LabelTypeError {
     Extended(u8),
 }
 
+//--- From
+
+impl From<LabelTypeError> for ParseError {
+    fn from(_: LabelTypeError) -> ParseError {
+        ParseError::Form(FormError::new("invalid label type"))
+    }
+}
+
 //--- Display and Error
 
 impl fmt::Display for LabelTypeError {
@@ -629,6 +717,14 @@ impl std::error::Error for LabelTypeError {}
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct LongLabelError;
 
+//--- From
+
+impl From<LongLabelError> for ParseError {
+    fn from(_: LongLabelError) -> ParseError {
+        ParseError::Form(FormError::new("long label"))
+    }
+}
+
 //--- Display and Error
 
 impl fmt::Display for LongLabelError {
@@ -761,6 +857,30 @@ mod test {
         );
     }
 
+    #[test]
+    fn is_hostname_label() {
+        assert!(Label::from_slice(b"www").unwrap().is_hostname_label());
+        assert!(Label::from_slice(b"x-1").unwrap().is_hostname_label());
+        assert!(Label::from_slice(b"1").unwrap().is_hostname_label());
+        assert!(!Label::root().is_hostname_label());
+        assert!(!Label::wildcard().is_hostname_label());
+        assert!(!Label::from_slice(b"-x").unwrap().is_hostname_label());
+        assert!(!Label::from_slice(b"x-").unwrap().is_hostname_label());
+        assert!(!Label::from_slice(b"_x").unwrap().is_hostname_label());
+        assert!(!Label::from_slice(b"x.y").unwrap().is_hostname_label());
+    }
+
+    #[test]
+    fn is_service_label() {
+        assert!(Label::from_slice(b"_tcp").unwrap().is_service_label());
+        assert!(Label::from_slice(b"_443").unwrap().is_service_label());
+        assert!(Label::from_slice(b"_dmarc").unwrap().is_service_label());
+        assert!(!Label::from_slice(b"_").unwrap().is_service_label());
+        assert!(!Label::from_slice(b"tcp").unwrap().is_service_label());
+        assert!(!Label::from_slice(b"_t_p").unwrap().is_service_label());
+        assert!(!Label::root().is_service_label());
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn compose() {
@@ -4,6 +4,7 @@
 //!
 //! Main types: [`Dname`], [`RelativeDname`], [`ParsedDname`],
 //! [`UncertainDname`], [`DnameBuilder`].<br/>
+//! Collections: [`NameTree`].<br/>
 //! Main traits: [`ToDname`], [`ToRelativeDname`].
 //!
 //! Domain names are a sequence of *labels* which are in turn a sequence of
@@ -51,6 +52,7 @@
 //! [`Dname`]: struct.Dname.html
 //! [`DnameBuilder`]: struct.DnameBuilder.html
 //! [`FromStr`]: ../../../std/str/trait.FromStr.html
+//! [`NameTree`]: struct.NameTree.html
 //! [`ParsedDname`]: struct.ParsedDname.html
 //! [`RelativeDname`]: struct.RelativeDname.html
 //! [`ToDname`]: trait.ToDname.html
@@ -61,18 +63,22 @@ pub use self::builder::{
     DnameBuilder, FromStrError, PushError, PushNameError,
 };
 pub use self::chain::{Chain, ChainIter, LongChainError, UncertainChainIter};
-pub use self::dname::{Dname, DnameError};
+pub use self::dname::{Dname, DnameError, NameError};
 pub use self::label::{
     Label, LabelTypeError, LongLabelError, OwnedLabel, SliceLabelsIter,
     SplitLabelError,
 };
-pub use self::parsed::{ParsedDname, ParsedDnameIter, ParsedSuffixIter};
+pub use self::parsed::{
+    ParsedDname, ParsedDnameError, ParsedDnameIter, ParsedSuffixIter,
+};
 pub use self::relative::{
     DnameIter, RelativeDname, RelativeDnameError, StripSuffixError,
 };
 pub use self::traits::{
     ToDname, ToEitherDname, ToLabelIter, ToRelativeDname,
 };
+#[cfg(feature = "std")]
+pub use self::tree::NameTree;
 pub use self::uncertain::UncertainDname;
 
 mod builder;
@@ -82,4 +88,5 @@ mod label;
 mod parsed;
 mod relative;
 mod traits;
+mod tree;
 mod uncertain;
@@ -78,6 +78,43 @@ impl<Ref> ParsedDname<Ref> {
     }
 }
 
+/// # Access to the Wire Format
+///
+impl<Ref: AsRef<[u8]>> ParsedDname<Ref> {
+    /// Returns the octets of the name exactly as they appear in the message.
+    ///
+    /// Parsing never alters the case of a name, so the returned slice
+    /// reflects the original case of all labels. Case is only ignored when
+    /// comparing names.
+    ///
+    /// If the name is not compressed, the slice contains the complete
+    /// name. If it is compressed, the slice ends with the first compression
+    /// pointer. If the name started with a compression pointer, the slice
+    /// starts at the position the pointer referred to.
+    pub fn as_wire(&self) -> &[u8] {
+        // Parsing already skips over leading compression pointers but a
+        // name can still start with one if it was created differently. The
+        // name has been checked, so the pointers lead to a label.
+        let mut slice = self.parser.peek_all();
+        while slice[0] & 0xC0 == 0xC0 {
+            let pos =
+                usize::from(slice[0] & 0x3F) << 8 | usize::from(slice[1]);
+            slice = &self.parser.as_slice()[pos..];
+        }
+        let mut pos = 0;
+        loop {
+            let head = slice[pos];
+            if head == 0 {
+                return &slice[..=pos];
+            }
+            if head & 0xC0 == 0xC0 {
+                return &slice[..pos + 2];
+            }
+            pos += usize::from(head) + 1;
+        }
+    }
+}
+
 /// # Working with Labels
 ///
 impl<Ref: AsRef<[u8]>> ParsedDname<Ref> {
@@ -698,6 +735,41 @@ mod test {
 
     static WECR: &[u8] = b"\x03www\x07example\x03com\0";
 
+    #[test]
+    fn as_wire() {
+        assert_eq!(name!(root).as_wire(), b"\0");
+        assert_eq!(name!(flat).as_wire(), WECR);
+        assert_eq!(name!(copy).as_wire(), WECR);
+        assert_eq!(name!(once).as_wire(), b"\x03www\x07example\xc0\0");
+        assert_eq!(name!(twice).as_wire(), b"\x03www\xc0\x05");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn preserve_case() {
+        use std::string::ToString;
+        use std::vec::Vec;
+
+        let wire = b"\x03WwW\x07ExAmPlE\x03CoM\0\x04MaIl\xc0\x04";
+        let mut parser = Parser::from_ref(wire.as_ref());
+        let www = ParsedDname::parse(&mut parser).unwrap();
+        let mail = ParsedDname::parse(&mut parser).unwrap();
+
+        assert_eq!(www.as_wire(), &wire[..17]);
+        assert_eq!(mail.as_wire(), b"\x04MaIl\xc0\x04");
+
+        let mut buf = Vec::new();
+        www.compose(&mut buf).unwrap();
+        assert_eq!(buf, &wire[..17]);
+        let mut buf = Vec::new();
+        mail.compose(&mut buf).unwrap();
+        assert_eq!(buf.as_slice(), b"\x04MaIl\x07ExAmPlE\x03CoM\0");
+
+        // Comparison ignores the case.
+        assert_eq!(www, Dname::from_slice(WECR).unwrap());
+        assert_eq!(www.to_string(), "WwW.ExAmPlE.CoM");
+    }
+
     #[test]
     fn len() {
         assert_eq!(name!(root).len(), 1);
@@ -1,19 +1,19 @@
 use super::super::octets::{
-    Compose, IntoBuilder, OctetsBuilder, OctetsExt, OctetsFrom, OctetsRef,
-    ParseError, ShortBuf,
+    Compose, EmptyBuilder, FormError, FromBuilder, IntoBuilder,
+    OctetsBuilder, OctetsExt, OctetsFrom, OctetsRef, ParseError, ShortBuf,
 };
-use super::builder::{DnameBuilder, PushError};
+use super::builder::{DnameBuilder, FromStrError, PushError};
 use super::chain::{Chain, LongChainError};
 use super::dname::Dname;
 use super::label::{Label, LabelTypeError, SplitLabelError};
 use super::traits::{ToEitherDname, ToLabelIter, ToRelativeDname};
 #[cfg(feature = "bytes")]
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use core::cmp::Ordering;
 /// Uncompressed, relative domain names.
 ///
 /// This is a private module. Its public types are re-exported by the parent.
-use core::{cmp, fmt, hash, ops};
+use core::{cmp, fmt, hash, ops, str};
 #[cfg(feature = "std")]
 use std::vec::Vec;
 
@@ -88,6 +88,36 @@ impl<Octets> RelativeDname<Octets> {
             RelativeDname::from_octets_unchecked(b"\x01*".as_ref().into())
         }
     }
+
+    /// Creates a relative domain name from a sequence of characters.
+    ///
+    /// The sequence must result in a domain name in master format
+    /// representation. That is, its labels should be separated by dots.
+    /// Actual dots, white space and backslashes should be escaped by a
+    /// preceeding backslash, and any byte value that is not a printable
+    /// ASCII character should be encoded by a backslash followed by its
+    /// three digit decimal value.
+    ///
+    /// An empty sequence results in the empty relative name. If the last
+    /// character in the sequence is a dot, the name is absolute and the
+    /// function fails with [`FromStrError::AbsoluteName`]. This includes
+    /// the root name given as a single dot.
+    pub fn from_chars<C>(chars: C) -> Result<Self, FromStrError>
+    where
+        Octets: FromBuilder,
+        <Octets as FromBuilder>::Builder: EmptyBuilder,
+        C: IntoIterator<Item = char>,
+    {
+        let mut chars = chars.into_iter().peekable();
+        if chars.peek().is_none() {
+            return Ok(DnameBuilder::<Octets::Builder>::new().finish());
+        }
+        let mut builder = DnameBuilder::<Octets::Builder>::new();
+        if builder.append_name_chars(chars)? {
+            return Err(FromStrError::AbsoluteName);
+        }
+        Ok(builder.finish())
+    }
 }
 
 impl RelativeDname<[u8]> {
@@ -209,6 +239,30 @@ impl<Octets: ?Sized> RelativeDname<Octets> {
     }
 }
 
+#[cfg(feature = "bytes")]
+impl<Octets: AsRef<[u8]> + ?Sized> RelativeDname<Octets> {
+    /// Returns a copy of the name with all ASCII letters in lowercase.
+    ///
+    /// All other octets, including those outside the ASCII range, are left
+    /// untouched.
+    pub fn to_lowercase(&self) -> RelativeDname<Bytes> {
+        let mut res = BytesMut::from(self.as_slice());
+        // Label length octets are at most 63 and thus never ASCII letters.
+        res.make_ascii_lowercase();
+        unsafe { RelativeDname::from_octets_unchecked(res.freeze()) }
+    }
+
+    /// Returns a copy of the name with all ASCII letters in uppercase.
+    ///
+    /// All other octets, including those outside the ASCII range, are left
+    /// untouched.
+    pub fn to_uppercase(&self) -> RelativeDname<Bytes> {
+        let mut res = BytesMut::from(self.as_slice());
+        res.make_ascii_uppercase();
+        unsafe { RelativeDname::from_octets_unchecked(res.freeze()) }
+    }
+}
+
 impl<Octets> RelativeDname<Octets> {
     /// Converts the name into a domain name builder for appending data.
     ///
@@ -319,13 +373,17 @@ impl<Octets: AsRef<[u8]> + ?Sized> RelativeDname<Octets> {
     }
 
     /// Returns whether an index points to the first octet of a label.
+    ///
+    /// An index equal to the length of the name is considered the start of
+    /// the (empty) remainder and thus returns `true`. Any index beyond that
+    /// returns `false`.
     pub fn is_label_start(&self, mut index: usize) -> bool {
         if index == 0 {
             return true;
         }
         let mut tmp = self.as_slice();
         while !tmp.is_empty() {
-            let (label, tail) = Label::split_from(tmp).unwrap();
+            let (label, tail) = Label::split_from_valid(tmp);
             let len = label.len() + 1;
             match index.cmp(&len) {
                 Ordering::Less => return false,
@@ -625,6 +683,25 @@ where
     }
 }
 
+//--- FromStr
+
+impl<Octets> str::FromStr for RelativeDname<Octets>
+where
+    Octets: FromBuilder,
+    <Octets as FromBuilder>::Builder: EmptyBuilder,
+{
+    type Err = FromStrError;
+
+    /// Parses a string into a relative domain name.
+    ///
+    /// An empty string results in the empty relative name. Strings ending
+    /// in a dot, including the root name `"."`, are absolute names and are
+    /// rejected with [`FromStrError::AbsoluteName`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_chars(s.chars())
+    }
+}
+
 //--- ToLabelIter and ToRelativeDname
 
 impl<'a, Octets> ToLabelIter<'a> for RelativeDname<Octets>
@@ -734,6 +811,11 @@ impl<Octets: AsRef<[u8]> + ?Sized> hash::Hash for RelativeDname<Octets> {
 //--- Display and Debug
 
 impl<Octets: AsRef<[u8]> + ?Sized> fmt::Display for RelativeDname<Octets> {
+    /// Formats the name in presentation format.
+    ///
+    /// The labels are separated by dots and escaped as described for the
+    /// `Display` implementation of [`Label`]. The output can be parsed back
+    /// into the same name via `FromStr`.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut iter = self.iter();
         match iter.next() {
@@ -757,6 +839,9 @@ impl<Octets: AsRef<[u8]> + ?Sized> fmt::Debug for RelativeDname<Octets> {
 //------------ DnameIter -----------------------------------------------------
 
 /// An iterator over the labels in an uncompressed name.
+///
+/// The iterator can be used from both ends. For names created through the
+/// safe constructors of [`Dname`] or [`RelativeDname`], it never panics.
 #[derive(Clone, Debug)]
 pub struct DnameIter<'a> {
     slice: &'a [u8],
@@ -788,7 +873,7 @@ impl<'a> DoubleEndedIterator for DnameIter<'a> {
         }
         let mut tmp = self.slice;
         loop {
-            let (label, tail) = Label::split_from(tmp).unwrap();
+            let (label, tail) = Label::split_from_valid(tmp);
             if tail.is_empty() {
                 let end = self.slice.len() - (label.len() + 1);
                 self.slice = &self.slice[..end];
@@ -841,6 +926,27 @@ impl From<SplitLabelError> for RelativeDnameError {
     }
 }
 
+impl From<RelativeDnameError> for FormError {
+    fn from(err: RelativeDnameError) -> FormError {
+        FormError::new(match err {
+            RelativeDnameError::BadLabel(_) => "unknown label type",
+            RelativeDnameError::CompressedName => "compressed domain name",
+            RelativeDnameError::ShortInput => "unexpected end of buffer",
+            RelativeDnameError::LongName => "long domain name",
+            RelativeDnameError::AbsoluteName => "absolute domain name",
+        })
+    }
+}
+
+impl From<RelativeDnameError> for ParseError {
+    fn from(err: RelativeDnameError) -> ParseError {
+        match err {
+            RelativeDnameError::ShortInput => ParseError::ShortInput,
+            other => ParseError::Form(other.into()),
+        }
+    }
+}
+
 //--- Display and Error
 
 impl fmt::Display for RelativeDnameError {
@@ -1020,6 +1126,41 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_str() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            RelativeDname::<Vec<u8>>::from_str("com")
+                .unwrap()
+                .as_slice(),
+            b"\x03com"
+        );
+        assert_eq!(
+            RelativeDname::<Vec<u8>>::from_str("www.example.com")
+                .unwrap()
+                .as_slice(),
+            b"\x03www\x07example\x03com"
+        );
+        assert_eq!(
+            RelativeDname::<Vec<u8>>::from_str("").unwrap().as_slice(),
+            b""
+        );
+        assert_eq!(
+            RelativeDname::<Vec<u8>>::from_str("com."),
+            Err(FromStrError::AbsoluteName)
+        );
+        assert_eq!(
+            RelativeDname::<Vec<u8>>::from_str("."),
+            Err(FromStrError::AbsoluteName)
+        );
+        assert_eq!(
+            RelativeDname::<Vec<u8>>::from_str(".com"),
+            Err(FromStrError::EmptyLabel)
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn into_absolute() {
@@ -1261,6 +1402,90 @@ mod test {
         assert!(!wec.is_label_start(15)); // m
         assert!(wec.is_label_start(16)); // empty label
         assert!(!wec.is_label_start(17)); //
+        assert!(!wec.is_label_start(usize::MAX));
+
+        let empty = RelativeDname::empty_slice();
+        assert!(empty.is_label_start(0));
+        assert!(!empty.is_label_start(1));
+        assert!(!empty.is_label_start(usize::MAX));
+
+        let mut long = [b'a'; 64];
+        long[0] = 63;
+        let long = RelativeDname::from_slice(&long[..]).unwrap();
+        assert!(long.is_label_start(0));
+        assert!(!long.is_label_start(1));
+        assert!(!long.is_label_start(63));
+        assert!(long.is_label_start(64));
+        assert!(!long.is_label_start(65));
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn case_conversion() {
+        let name = RelativeDname::from_slice(b"\x03WwW\x07Example\x02\xC4z")
+            .unwrap();
+        assert_eq!(
+            name.to_lowercase().as_slice(),
+            b"\x03www\x07example\x02\xC4z"
+        );
+        assert_eq!(
+            name.to_uppercase().as_slice(),
+            b"\x03WWW\x07EXAMPLE\x02\xC4Z"
+        );
+        assert_eq!(
+            RelativeDname::empty_slice().to_lowercase().as_slice(),
+            b""
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_escapes() {
+        use core::str::FromStr;
+        use std::string::ToString;
+
+        let name =
+            RelativeDname::from_slice(b"\x05a\0.\\ \x03\x7F\xFFb").unwrap();
+        let s = name.to_string();
+        assert_eq!(s, "a\\000\\.\\\\\\ .\\127\\255b");
+        assert_eq!(
+            RelativeDname::<Vec<u8>>::from_str(&s).unwrap().as_slice(),
+            name.as_slice()
+        );
+
+        // Every possible octet survives the round trip.
+        for ch in 0..=255u8 {
+            let octets = [1, ch];
+            let name = RelativeDname::from_slice(&octets).unwrap();
+            let parsed =
+                RelativeDname::<Vec<u8>>::from_str(&name.to_string())
+                    .unwrap();
+            assert_eq!(parsed.as_slice(), &octets, "octet {}", ch);
+        }
+    }
+
+    #[test]
+    fn iter_both_ends() {
+        let wec =
+            RelativeDname::from_slice(b"\x03www\x07example\x03com").unwrap();
+
+        // Both ends meet in the middle.
+        let mut iter = wec.iter();
+        assert_eq!(iter.next_back().unwrap().as_slice(), b"com");
+        assert_eq!(iter.next().unwrap().as_slice(), b"www");
+        assert_eq!(iter.next_back().unwrap().as_slice(), b"example");
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        // Root and maximum length labels.
+        let mut long = [b'a'; 65];
+        long[0] = 63;
+        long[64] = 0;
+        let long = Dname::from_slice(&long[..]).unwrap();
+        let mut iter = long.iter();
+        assert!(iter.next_back().unwrap().is_root());
+        assert_eq!(iter.next_back().unwrap().len(), 63);
+        assert_eq!(iter.next_back(), None);
     }
 
     #[test]
@@ -0,0 +1,179 @@
+//! A tree of values keyed by domain names.
+//!
+//! This is a private module. Its public types are re-exported by the parent.
+#![cfg(feature = "std")]
+
+use super::label::{Label, OwnedLabel};
+use super::traits::ToDname;
+use std::collections::BTreeMap;
+
+//------------ NameTree ------------------------------------------------------
+
+/// A tree of values keyed by absolute domain names.
+///
+/// The tree mirrors the structure of the domain name space: each node
+/// represents a name and has a child node for each label that can be
+/// prepended to this name. A value can be stored with each node. Labels are
+/// compared ignoring ASCII case, so `example.com` and `EXAMPLE.com` refer to
+/// the same node.
+///
+/// Because of this structure, finding the value for a name as well as
+/// finding the value of the longest suffix of a name that has a value both
+/// take time proportional to the number of labels in the name. The latter
+/// is available through [`closest_encloser`][Self::closest_encloser] and is
+/// useful, for instance, to find the zone or zone cut a name belongs to.
+#[derive(Clone, Debug)]
+pub struct NameTree<T> {
+    /// The value for the name represented by this node, if any.
+    value: Option<T>,
+
+    /// The children of the node keyed by their left-most label.
+    children: BTreeMap<OwnedLabel, NameTree<T>>,
+}
+
+impl<T> NameTree<T> {
+    /// Creates a new, empty tree.
+    pub fn new() -> Self {
+        NameTree {
+            value: None,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Returns whether the tree doesn’t contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.values().all(Self::is_empty)
+    }
+
+    /// Inserts a value for the given name.
+    ///
+    /// If there already was a value for `name`, it is replaced and the old
+    /// value is returned.
+    pub fn insert<N: ToDname>(&mut self, name: &N, value: T) -> Option<T> {
+        let mut node = self;
+        for label in Self::tree_labels(name) {
+            node = node.children.entry(label.into()).or_default();
+        }
+        node.value.replace(value)
+    }
+
+    /// Returns a reference to the value for exactly the given name.
+    pub fn get<N: ToDname>(&self, name: &N) -> Option<&T> {
+        let mut node = self;
+        for label in Self::tree_labels(name) {
+            node = node.children.get(label)?;
+        }
+        node.value.as_ref()
+    }
+
+    /// Returns a mutable reference to the value for exactly the given name.
+    pub fn get_mut<N: ToDname>(&mut self, name: &N) -> Option<&mut T> {
+        let mut node = self;
+        for label in Self::tree_labels(name) {
+            node = node.children.get_mut(label)?;
+        }
+        node.value.as_mut()
+    }
+
+    /// Returns the value for the longest suffix of the name that has one.
+    ///
+    /// The name itself is considered, too, so if there is a value for
+    /// `name`, this value is returned. Otherwise, the value of the closest
+    /// ancestor with a value is returned. If not even the root has a value,
+    /// returns `None`.
+    ///
+    /// In addition to the value, the number of labels of the matching
+    /// suffix, not including the root label, is returned.
+    pub fn closest_encloser<N: ToDname>(
+        &self,
+        name: &N,
+    ) -> Option<(usize, &T)> {
+        let mut node = self;
+        let mut res = node.value.as_ref().map(|value| (0, value));
+        for (depth, label) in Self::tree_labels(name).enumerate() {
+            node = match node.children.get(label) {
+                Some(node) => node,
+                None => break,
+            };
+            if let Some(ref value) = node.value {
+                res = Some((depth + 1, value))
+            }
+        }
+        res
+    }
+
+    /// Returns the labels of a name in tree order.
+    ///
+    /// These are the labels from right to left, skipping the root label.
+    fn tree_labels<N: ToDname>(name: &N) -> impl Iterator<Item = &Label> {
+        name.iter_labels().rev().skip(1)
+    }
+}
+
+//--- Default
+
+impl<T> Default for NameTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::name::Dname;
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    fn name(s: &str) -> Dname<Vec<u8>> {
+        Dname::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn insert_and_get() {
+        let mut tree = NameTree::new();
+        assert!(tree.is_empty());
+        assert_eq!(tree.insert(&name("example.com"), 1), None);
+        assert_eq!(tree.insert(&name("a.example.com"), 2), None);
+        assert_eq!(tree.insert(&name("A.Example.COM"), 3), Some(2));
+        assert!(!tree.is_empty());
+
+        assert_eq!(tree.get(&name("example.com")), Some(&1));
+        assert_eq!(tree.get(&name("a.example.com")), Some(&3));
+        assert_eq!(tree.get(&name("com")), None);
+        assert_eq!(tree.get(&name("x.a.example.com")), None);
+        assert_eq!(tree.get(&Dname::root_slice()), None);
+
+        *tree.get_mut(&name("example.com")).unwrap() = 4;
+        assert_eq!(tree.get(&name("EXAMPLE.com")), Some(&4));
+    }
+
+    #[test]
+    fn closest_encloser() {
+        let mut tree = NameTree::new();
+        tree.insert(&name("example.com"), "example");
+        tree.insert(&name("a.example.com"), "a");
+
+        assert_eq!(
+            tree.closest_encloser(&name("x.a.example.com")),
+            Some((3, &"a"))
+        );
+        assert_eq!(
+            tree.closest_encloser(&name("a.example.com")),
+            Some((3, &"a"))
+        );
+        assert_eq!(
+            tree.closest_encloser(&name("b.example.com")),
+            Some((2, &"example"))
+        );
+        assert_eq!(tree.closest_encloser(&name("example.org")), None);
+
+        tree.insert(&Dname::root_slice(), "root");
+        assert_eq!(
+            tree.closest_encloser(&name("example.org")),
+            Some((0, &"root"))
+        );
+    }
+}
@@ -72,7 +72,8 @@ impl<Octets> UncertainDname<Octets> {
     /// three digit decimal value.
     ///
     /// If the last character is a dot, the name will be absolute, otherwise
-    /// it will be relative.
+    /// it will be relative. A single dot results in the root name while an
+    /// empty sequence results in the empty relative name.
     ///
     /// If you have a string, you can also use the `FromStr` trait, which
     /// really does the same thing.
@@ -82,13 +83,16 @@ impl<Octets> UncertainDname<Octets> {
         <Octets as FromBuilder>::Builder: EmptyBuilder,
         C: IntoIterator<Item = char>,
     {
+        let mut chars = chars.into_iter().peekable();
         let mut builder =
             DnameBuilder::<<Octets as FromBuilder>::Builder>::new();
-        builder.append_chars(chars)?;
-        if builder.in_label() || builder.is_empty() {
-            Ok(builder.finish().into())
-        } else {
+        if chars.peek().is_none() {
+            return Ok(builder.finish().into());
+        }
+        if builder.append_name_chars(chars)? {
             Ok(builder.into_dname()?.into())
+        } else {
+            Ok(builder.finish().into())
         }
     }
 }
@@ -464,6 +468,8 @@ mod test {
             name("www.example.com.").as_absolute().unwrap().as_slice(),
             b"\x03www\x07example\x03com\0"
         );
+        assert_eq!(name(".").as_absolute().unwrap().as_slice(), b"\0");
+        assert_eq!(name("").as_relative().unwrap().as_slice(), b"");
 
         assert_eq!(
             name(r"www\.example.com").as_slice(),
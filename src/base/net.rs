@@ -6,6 +6,10 @@
 //!
 //! The `no_std` version currently is only the bare minimum implementation
 //! and doesn’t provide all the features the `std` version has.
+//!
+//! In addition, the module defines [`IpPrefix`] for IP address prefixes.
+
+use core::fmt;
 
 #[cfg(feature = "std")]
 pub use std::net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr};
@@ -206,3 +210,326 @@ mod nostd {
     #[derive(Clone, Copy, Debug)]
     pub struct AddrParseError;
 }
+
+//------------ IpPrefix ------------------------------------------------------
+
+/// An IP address prefix.
+///
+/// A prefix consists of an IPv4 or IPv6 address and a prefix length giving
+/// the number of leading bits of the address that are significant. The
+/// remaining bits of the address are always zero. For instance, the prefix
+/// `192.0.2.0/24` contains all addresses from `192.0.2.0` to `192.0.2.255`.
+///
+/// Prefixes are used by the [client subnet option] and are also useful for
+/// things like access control lists.
+///
+/// [client subnet option]: super::opt::rfc7871::ClientSubnet
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IpPrefix {
+    /// The address with all bits beyond the prefix length cleared.
+    addr: IpAddr,
+
+    /// The prefix length.
+    len: u8,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl IpPrefix {
+    /// Creates a new prefix from an address and prefix length.
+    ///
+    /// Any bits of the address beyond the prefix length are cleared. If the
+    /// prefix length is larger than the length of the address, i.e., 32 for
+    /// IPv4 or 128 for IPv6, returns an error.
+    pub fn new(addr: IpAddr, len: u8) -> Result<Self, PrefixLenError> {
+        if len > max_prefix_len(addr) {
+            return Err(PrefixLenError);
+        }
+        Ok(Self::new_saturating(addr, len))
+    }
+
+    /// Creates a new prefix, limiting the prefix length to its maximum.
+    ///
+    /// Any bits of the address beyond the prefix length are cleared. If the
+    /// prefix length is larger than the length of the address, the length
+    /// of the address is used instead, resulting in a prefix containing
+    /// exactly the address.
+    pub fn new_saturating(addr: IpAddr, len: u8) -> Self {
+        let len = core::cmp::min(len, max_prefix_len(addr));
+        IpPrefix {
+            addr: apply_mask(addr, len).0,
+            len,
+        }
+    }
+
+    /// Returns the address of the prefix.
+    ///
+    /// All bits beyond the prefix length are zero.
+    pub fn addr(self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length.
+    pub fn len(self) -> u8 {
+        self.len
+    }
+
+    /// Returns whether this is an IPv4 prefix.
+    pub fn is_v4(self) -> bool {
+        matches!(self.addr, IpAddr::V4(_))
+    }
+
+    /// Returns whether this is an IPv6 prefix.
+    pub fn is_v6(self) -> bool {
+        matches!(self.addr, IpAddr::V6(_))
+    }
+
+    /// Returns whether the prefix contains the given address.
+    ///
+    /// An IPv4 prefix never contains an IPv6 address and vice versa, even
+    /// if it is an IPv4-mapped address.
+    pub fn contains(self, addr: IpAddr) -> bool {
+        match (self.addr, addr) {
+            (IpAddr::V4(_), IpAddr::V4(_))
+            | (IpAddr::V6(_), IpAddr::V6(_)) => {
+                apply_mask(addr, self.len).0 == self.addr
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the given address has bits set beyond `len`.
+    ///
+    /// If `len` is larger than the length of the address, returns `false`.
+    pub(crate) fn has_host_bits(addr: IpAddr, len: u8) -> bool {
+        apply_mask(addr, len).1
+    }
+}
+
+//--- FromStr
+
+#[cfg(feature = "std")]
+impl core::str::FromStr for IpPrefix {
+    type Err = ParsePrefixError;
+
+    /// Parses a prefix in the common `address/length` notation.
+    ///
+    /// Any bits of the address beyond the prefix length are cleared.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '/');
+        let addr = parts.next().unwrap_or("");
+        let len = parts.next().ok_or(ParsePrefixError::MissingLen)?;
+        let addr = IpAddr::from_str(addr).map_err(ParsePrefixError::Addr)?;
+        let len = u8::from_str(len).map_err(|_| ParsePrefixError::Len)?;
+        Self::new(addr, len).map_err(|_| ParsePrefixError::Len)
+    }
+}
+
+//--- Display
+
+impl fmt::Display for IpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.addr {
+            IpAddr::V4(addr) => write!(f, "{}/{}", addr, self.len),
+            IpAddr::V6(addr) => write!(f, "{}/{}", addr, self.len),
+        }
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns the maximum prefix length for the given address.
+fn max_prefix_len(addr: IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// Clears all bits beyond the prefix length `len` of an address.
+///
+/// Returns the new address and whether any bits were cleared.
+fn apply_mask(addr: IpAddr, len: u8) -> (IpAddr, bool) {
+    match addr {
+        IpAddr::V4(addr) => {
+            let mut array = addr.octets();
+            let modified = apply_bit_mask(&mut array, len.into());
+            (array.into(), modified)
+        }
+        IpAddr::V6(addr) => {
+            let mut array = addr.octets();
+            let modified = apply_bit_mask(&mut array, len.into());
+            (array.into(), modified)
+        }
+    }
+}
+
+/// Clears all bits in `buf` beyond the first `mask` bits.
+///
+/// Returns whether the buffer has been modified.
+fn apply_bit_mask(buf: &mut [u8], mask: usize) -> bool {
+    let mut modified = false;
+
+    // skip full bytes covered by prefix length
+    let mut p = mask / 8;
+    if p >= buf.len() {
+        return modified;
+    }
+
+    // clear extra bits in a byte
+    let bits = mask % 8;
+    if bits != 0 {
+        if buf[p].trailing_zeros() < (8 - bits) as u32 {
+            buf[p] &= 0xff << (8 - bits);
+            modified = true;
+        }
+        p += 1;
+    }
+
+    // clear the rest bytes
+    while p < buf.len() {
+        if buf[p] != 0 {
+            buf[p] = 0;
+            modified = true;
+        }
+        p += 1;
+    }
+
+    modified
+}
+
+//============ Error Types ===================================================
+
+//------------ PrefixLenError ------------------------------------------------
+
+/// A prefix length was too long for the address family.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PrefixLenError;
+
+impl fmt::Display for PrefixLenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("prefix length too long")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PrefixLenError {}
+
+//------------ ParsePrefixError ----------------------------------------------
+
+/// An error happened while parsing an IP prefix from a string.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParsePrefixError {
+    /// The address part was invalid.
+    Addr(AddrParseError),
+
+    /// The prefix length was missing.
+    MissingLen,
+
+    /// The prefix length was invalid or too long.
+    Len,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParsePrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParsePrefixError::Addr(ref err) => err.fmt(f),
+            ParsePrefixError::MissingLen => {
+                f.write_str("missing prefix length")
+            }
+            ParsePrefixError::Len => f.write_str("invalid prefix length"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePrefixError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::string::ToString;
+
+    fn prefix(s: &str) -> IpPrefix {
+        IpPrefix::from_str(s).unwrap()
+    }
+
+    fn addr(s: &str) -> IpAddr {
+        IpAddr::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn masking() {
+        let p = IpPrefix::new(addr("192.0.2.77"), 24).unwrap();
+        assert_eq!(p.addr(), addr("192.0.2.0"));
+        assert_eq!(p.len(), 24);
+        let p = IpPrefix::new(addr("192.0.2.77"), 22).unwrap();
+        assert_eq!(p.addr(), addr("192.0.0.0"));
+        let p = IpPrefix::new(addr("192.0.2.77"), 0).unwrap();
+        assert_eq!(p.addr(), addr("0.0.0.0"));
+        let p = IpPrefix::new(addr("2001:db8:ffff::1"), 36).unwrap();
+        assert_eq!(p.addr(), addr("2001:db8:f000::"));
+
+        assert_eq!(IpPrefix::new(addr("192.0.2.1"), 33), Err(PrefixLenError));
+        assert!(IpPrefix::new(addr("2001:db8::1"), 128).is_ok());
+        assert_eq!(
+            IpPrefix::new(addr("2001:db8::1"), 129),
+            Err(PrefixLenError)
+        );
+        let p = IpPrefix::new_saturating(addr("192.0.2.1"), 100);
+        assert_eq!(p, prefix("192.0.2.1/32"));
+
+        assert!(IpPrefix::has_host_bits(addr("192.0.2.1"), 24));
+        assert!(!IpPrefix::has_host_bits(addr("192.0.2.0"), 24));
+        assert!(!IpPrefix::has_host_bits(addr("192.0.2.1"), 40));
+    }
+
+    #[test]
+    fn contains_v4() {
+        let p = prefix("192.0.2.0/24");
+        assert!(p.is_v4());
+        assert!(p.contains(addr("192.0.2.0")));
+        assert!(p.contains(addr("192.0.2.255")));
+        assert!(!p.contains(addr("192.0.3.0")));
+        assert!(!p.contains(addr("::ffff:192.0.2.1")));
+        assert!(prefix("0.0.0.0/0").contains(addr("198.51.100.1")));
+    }
+
+    #[test]
+    fn contains_v6() {
+        let p = prefix("2001:db8::/32");
+        assert!(p.is_v6());
+        assert!(p.contains(addr("2001:db8::1")));
+        assert!(p.contains(addr("2001:db8:ffff:ffff::")));
+        assert!(!p.contains(addr("2001:db9::")));
+        assert!(!p.contains(addr("192.0.2.1")));
+    }
+
+    #[test]
+    fn from_str_and_display() {
+        assert_eq!(prefix("192.0.2.0/24").to_string(), "192.0.2.0/24");
+        assert_eq!(prefix("192.0.2.1/24").to_string(), "192.0.2.0/24");
+        assert_eq!(prefix("2001:db8::/32").to_string(), "2001:db8::/32");
+        assert_eq!(
+            IpPrefix::from_str("192.0.2.0"),
+            Err(ParsePrefixError::MissingLen)
+        );
+        assert_eq!(
+            IpPrefix::from_str("192.0.2.0/33"),
+            Err(ParsePrefixError::Len)
+        );
+        assert_eq!(
+            IpPrefix::from_str("192.0.2.0/x"),
+            Err(ParsePrefixError::Len)
+        );
+        assert!(matches!(
+            IpPrefix::from_str("192.0.2/24"),
+            Err(ParsePrefixError::Addr(_))
+        ));
+    }
+}
@@ -129,9 +129,12 @@
 //! many cases get away with using a octets array as the basis for an octets
 //! sequence. The crate provides a macro [`octets_array!`] to define such a
 //! type for specific array length. The octets module also contains a number
-//! of types defined via that module for typical array sizes.
+//! of types defined via that module for typical array sizes. Alternatively,
+//! [`ArrayTarget`] allows composing directly into a buffer provided by the
+//! caller.
 //!
 //!
+//! [`ArrayTarget`]: struct.ArrayTarget.html
 //! [`Compose`]: trait.Compose.html
 //! [`EmptyBuilder`]: trait.EmptyBuilder.html
 //! [`Octets`]: trait.Octets.html
@@ -142,6 +145,7 @@
 //! [`Parser`]: struct.Parser.html
 //! [`ShortBuf`]: struct.ShortBuf.html
 
+use super::charstr::CharStr;
 use super::name::ToDname;
 use super::net::{Ipv4Addr, Ipv6Addr};
 #[cfg(feature = "bytes")]
@@ -773,6 +777,32 @@ impl<Ref> Parser<Ref> {
         }
     }
 
+    /// Creates a new parser limited to a range of an octets sequence.
+    ///
+    /// The parser will start parsing at index `start` and will not allow
+    /// parsing beyond index `end`. However, it can still be repositioned
+    /// to anywhere before `start`. This is useful when parsing data that
+    /// is part of a larger message, such as the data of a record, which may
+    /// contain compressed domain names pointing to earlier parts of the
+    /// message.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `start` is larger than `end` or if `end` is
+    /// beyond the end of `octets`.
+    pub fn with_range(octets: Ref, start: usize, end: usize) -> Self
+    where
+        Ref: AsRef<[u8]>,
+    {
+        assert!(start <= end);
+        assert!(end <= octets.as_ref().len());
+        Parser {
+            pos: start,
+            len: end,
+            octets,
+        }
+    }
+
     /// Returns the wrapped reference to the underlying octets sequence.
     pub fn octets_ref(&self) -> Ref
     where
@@ -848,15 +878,33 @@ impl<Ref: AsRef<[u8]>> Parser<Ref> {
     }
 
     /// Returns a slice of the data left to parse.
+    ///
+    /// If the parser is currently limited to a block, the slice ends at
+    /// the end of the block.
     pub fn peek_all(&self) -> &[u8] {
-        &self.octets.as_ref()[self.pos..]
+        &self.octets.as_ref()[self.pos..self.len]
     }
 
     /// Repositions the parser to the given index.
     ///
-    /// It is okay to reposition anywhere within the sequence. However,
-    /// if `pos` is larger than the length of the sequence, an error is
-    /// returned.
+    /// It is okay to reposition anywhere within the sequence, including
+    /// before the position the parser started at. However, if `pos` is
+    /// larger than the length of the sequence – or the end of the range for
+    /// a parser created via [`with_range`][Self::with_range] –, an error is
+    /// returned and the position is left unchanged.
+    ///
+    /// Together with [`pos`][Self::pos], this allows looking ahead and then
+    /// rewinding to re-read data:
+    ///
+    /// ```
+    /// use domain::base::octets::Parser;
+    ///
+    /// let mut parser = Parser::from_static(b"\x00\x02ab");
+    /// let start = parser.pos();
+    /// assert_eq!(parser.parse_u16().unwrap(), 2);
+    /// parser.seek(start).unwrap();
+    /// assert_eq!(parser.parse_u8().unwrap(), 0);
+    /// ```
     pub fn seek(&mut self, pos: usize) -> Result<(), ParseError> {
         if pos > self.len {
             Err(ParseError::ShortInput)
@@ -866,6 +914,22 @@ impl<Ref: AsRef<[u8]>> Parser<Ref> {
         }
     }
 
+    /// Returns a copy of the parser positioned at the given index.
+    ///
+    /// The new parser shares the underlying octets and limit with `self`
+    /// which remains unchanged. This allows branching off to parse data
+    /// elsewhere in the sequence, for instance to follow a pointer. Like
+    /// with [`seek`][Self::seek], an error is returned if `pos` is beyond
+    /// the end of the parser.
+    pub fn clone_at(&self, pos: usize) -> Result<Self, ParseError>
+    where
+        Ref: Clone,
+    {
+        let mut res = self.clone();
+        res.seek(pos)?;
+        Ok(res)
+    }
+
     /// Advances the parser‘s position by `len` octets.
     ///
     /// If this would take the parser beyond its end, an error is returned.
@@ -998,6 +1062,50 @@ impl<Ref: AsRef<[u8]>> Parser<Ref> {
         Ok(u32::from_be_bytes(res))
     }
 
+    /// Takes a 24 bit unsigned integer from the beginning of the parser.
+    ///
+    /// The value is converted from network byte order into the system’s own
+    /// byte order if necessary and returned as a `u32`. The parser is
+    /// advanced by three octets. If there aren’t enough octets left, leaves
+    /// the parser untouched and returns an error instead.
+    pub fn parse_u24(&mut self) -> Result<u32, ParseError> {
+        let mut res = [0; 4];
+        self.parse_buf(&mut res[1..])?;
+        Ok(u32::from_be_bytes(res))
+    }
+
+    /// Takes a 48 bit unsigned integer from the beginning of the parser.
+    ///
+    /// The value is converted from network byte order into the system’s own
+    /// byte order if necessary and returned as a `u64`. The parser is
+    /// advanced by six octets. If there aren’t enough octets left, leaves
+    /// the parser untouched and returns an error instead.
+    pub fn parse_u48(&mut self) -> Result<u64, ParseError> {
+        let mut res = [0; 8];
+        self.parse_buf(&mut res[2..])?;
+        Ok(u64::from_be_bytes(res))
+    }
+
+    /// Takes a character string from the beginning of the parser.
+    ///
+    /// A character string as defined in RFC 1035 consists of a length
+    /// octet followed by that many octets of content. The parser is
+    /// advanced past the complete character string. If there aren’t enough
+    /// octets left, leaves the parser untouched and returns an error
+    /// instead.
+    pub fn parse_char_str(
+        &mut self,
+    ) -> Result<CharStr<Ref::Range>, ParseError>
+    where
+        Ref: OctetsRef,
+    {
+        let len = usize::from(self.peek(1)?[0]);
+        self.check_len(len + 1)?;
+        self.pos += 1;
+        let res = self.parse_octets(len)?;
+        Ok(unsafe { CharStr::from_octets_unchecked(res) })
+    }
+
     /// Parses a given amount of octets through a closure.
     ///
     /// Parses a block of `limit` octets and moves the parser to the end of
@@ -1301,6 +1409,182 @@ impl Compose for Ipv6Addr {
     }
 }
 
+//------------ U24 -----------------------------------------------------------
+
+/// A 24 bit unsigned integer.
+///
+/// Values of this type are encoded as three octets in network byte order.
+/// Since the type can only be created from values within its range,
+/// composing a value never produces a truncated integer.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
+pub struct U24(u32);
+
+impl U24 {
+    /// The largest value representable by the type.
+    pub const MAX: U24 = U24(0x00FF_FFFF);
+
+    /// Creates a value from a `u32` if it is within range.
+    ///
+    /// Returns `None` if any of the upper eight bits of `value` are set.
+    pub fn from_u32(value: u32) -> Option<Self> {
+        if value > Self::MAX.0 {
+            None
+        } else {
+            Some(U24(value))
+        }
+    }
+
+    /// Returns the value as a `u32`.
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+}
+
+//--- From
+
+impl From<u8> for U24 {
+    fn from(value: u8) -> Self {
+        U24(value.into())
+    }
+}
+
+impl From<u16> for U24 {
+    fn from(value: u16) -> Self {
+        U24(value.into())
+    }
+}
+
+impl From<U24> for u32 {
+    fn from(value: U24) -> Self {
+        value.0
+    }
+}
+
+//--- Parse and Compose
+
+impl<T: AsRef<[u8]>> Parse<T> for U24 {
+    fn parse(parser: &mut Parser<T>) -> Result<Self, ParseError> {
+        parser.parse_u24().map(U24)
+    }
+
+    fn skip(parser: &mut Parser<T>) -> Result<(), ParseError> {
+        parser.advance(3)
+    }
+}
+
+impl Compose for U24 {
+    fn compose<T: OctetsBuilder>(
+        &self,
+        target: &mut T,
+    ) -> Result<(), ShortBuf> {
+        target.append_slice(&self.0.to_be_bytes()[1..])
+    }
+}
+
+//--- Display
+
+impl fmt::Display for U24 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+//------------ U48 -----------------------------------------------------------
+
+/// A 48 bit unsigned integer.
+///
+/// Values of this type are encoded as six octets in network byte order.
+/// Since the type can only be created from values within its range,
+/// composing a value never produces a truncated integer.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
+pub struct U48(u64);
+
+impl U48 {
+    /// The largest value representable by the type.
+    pub const MAX: U48 = U48(0x0000_FFFF_FFFF_FFFF);
+
+    /// Creates a value from a `u64` if it is within range.
+    ///
+    /// Returns `None` if any of the upper sixteen bits of `value` are set.
+    pub fn from_u64(value: u64) -> Option<Self> {
+        if value > Self::MAX.0 {
+            None
+        } else {
+            Some(U48(value))
+        }
+    }
+
+    /// Returns the value as a `u64`.
+    pub fn to_u64(self) -> u64 {
+        self.0
+    }
+}
+
+//--- From
+
+impl From<u8> for U48 {
+    fn from(value: u8) -> Self {
+        U48(value.into())
+    }
+}
+
+impl From<u16> for U48 {
+    fn from(value: u16) -> Self {
+        U48(value.into())
+    }
+}
+
+impl From<u32> for U48 {
+    fn from(value: u32) -> Self {
+        U48(value.into())
+    }
+}
+
+impl From<U24> for U48 {
+    fn from(value: U24) -> Self {
+        U48(value.0.into())
+    }
+}
+
+impl From<U48> for u64 {
+    fn from(value: U48) -> Self {
+        value.0
+    }
+}
+
+//--- Parse and Compose
+
+impl<T: AsRef<[u8]>> Parse<T> for U48 {
+    fn parse(parser: &mut Parser<T>) -> Result<Self, ParseError> {
+        parser.parse_u48().map(U48)
+    }
+
+    fn skip(parser: &mut Parser<T>) -> Result<(), ParseError> {
+        parser.advance(6)
+    }
+}
+
+impl Compose for U48 {
+    fn compose<T: OctetsBuilder>(
+        &self,
+        target: &mut T,
+    ) -> Result<(), ShortBuf> {
+        target.append_slice(&self.0.to_be_bytes()[2..])
+    }
+}
+
+//--- Display
+
+impl fmt::Display for U48 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 //------------ octets_array --------------------------------------------------
 
 #[macro_export]
@@ -1497,6 +1781,123 @@ octets_array!(pub Octets1024 => 1024);
 octets_array!(pub Octets2048 => 2048);
 octets_array!(pub Octets4096 => 4096);
 
+//------------ ArrayTarget ---------------------------------------------------
+
+/// An octets builder atop a borrowed fixed-size buffer.
+///
+/// The type allows composing data directly into a buffer provided by the
+/// caller, typically an array on the stack, without any allocation. It
+/// keeps track of how much of the buffer has already been written to and
+/// returns a [`ShortBuf`] error if appending data would exceed the buffer.
+///
+/// In contrast to the types created via [`octets_array!`], the buffer is
+/// not owned by the target, so it can be of any size and the composed data
+/// can be accessed through the buffer after the target has been dropped.
+/// Freezing the target returns the slice of the buffer that has been
+/// written to.
+///
+/// ```
+/// use domain::base::iana::Rtype;
+/// use domain::base::name::Dname;
+/// use domain::base::octets::ArrayTarget;
+/// use domain::base::MessageBuilder;
+///
+/// let mut buf = [0u8; 512];
+/// let target = ArrayTarget::new(&mut buf);
+/// let mut msg = MessageBuilder::from_target(target).unwrap().question();
+/// msg.push((Dname::root_slice(), Rtype::Ns)).unwrap();
+/// let msg = msg.into_message();
+/// assert_eq!(msg.as_slice().len(), 17);
+/// ```
+pub struct ArrayTarget<'a> {
+    /// The buffer to write to.
+    buf: &'a mut [u8],
+
+    /// The number of octets already written to the start of `buf`.
+    len: usize,
+}
+
+impl<'a> ArrayTarget<'a> {
+    /// Creates a new, empty target atop the given buffer.
+    ///
+    /// The current content of the buffer is ignored and will be
+    /// overwritten.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        ArrayTarget { buf, len: 0 }
+    }
+
+    /// Returns the total number of octets the target can hold.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns the number of octets that can still be appended.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.len
+    }
+
+    /// Returns the data written so far as an octets slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the data written so far as a mutable octets slice.
+    pub fn as_slice_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.len]
+    }
+}
+
+//--- AsRef and AsMut
+
+impl<'a> AsRef<[u8]> for ArrayTarget<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<'a> AsMut<[u8]> for ArrayTarget<'a> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_slice_mut()
+    }
+}
+
+//--- OctetsBuilder
+
+impl<'a> OctetsBuilder for ArrayTarget<'a> {
+    type Octets = &'a [u8];
+
+    fn append_slice(&mut self, slice: &[u8]) -> Result<(), ShortBuf> {
+        if slice.len() > self.remaining() {
+            return Err(ShortBuf);
+        }
+        let end = self.len + slice.len();
+        self.buf[self.len..end].copy_from_slice(slice);
+        self.len = end;
+        Ok(())
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.len = len
+        }
+    }
+
+    fn freeze(self) -> Self::Octets {
+        let buf: &'a [u8] = self.buf;
+        &buf[..self.len]
+    }
+}
+
+//--- Debug
+
+impl<'a> fmt::Debug for ArrayTarget<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ArrayTarget")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
 //------------ OctetsVec -----------------------------------------------------
 
 /// A octets vector that doesn’t allocate for small sizes.
@@ -1531,6 +1932,15 @@ impl std::error::Error for ShortBuf {}
 //--------- ParseError -------------------------------------------------------
 
 /// An error happened while parsing data.
+///
+/// This is the error type returned by all parsing functions. The more
+/// specific error types produced by the various types of this crate, such
+/// as [`DnameError`] or [`RelativeDnameError`], all convert into this type,
+/// so that the question mark operator can be used across all of them when
+/// parsing entire messages.
+///
+/// [`DnameError`]: ../name/enum.DnameError.html
+/// [`RelativeDnameError`]: ../name/enum.RelativeDnameError.html
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ParseError {
     /// An attempt was made to go beyond the end of the parser.
@@ -1622,6 +2032,101 @@ mod test {
         assert_eq!(parser.remaining(), 0);
     }
 
+    #[test]
+    fn array_target() {
+        use crate::base::iana::Rtype;
+        use crate::base::name::Dname;
+        use crate::base::MessageBuilder;
+        use crate::rdata::A;
+
+        let mut buf = [0u8; 512];
+        let mut msg = MessageBuilder::from_target(ArrayTarget::new(&mut buf))
+            .unwrap()
+            .question();
+        msg.push((Dname::root_slice(), Rtype::A)).unwrap();
+        let msg = msg.finish();
+        assert_eq!(msg.len(), 17);
+        assert_eq!(&buf[12..17], b"\0\0\x01\0\x01");
+
+        // Appending fails if the data doesn’t fit and leaves the target
+        // unchanged.
+        let mut buf = [0u8; 512];
+        let mut target = ArrayTarget::new(&mut buf);
+        assert_eq!(target.append_slice(&[0xAA; 500]), Ok(()));
+        assert_eq!(target.remaining(), 12);
+        assert_eq!(target.append_slice(&[0xBB; 13]), Err(ShortBuf));
+        assert_eq!(target.len(), 500);
+
+        // An oversized message. Each record takes 15 octets, so only 33 of
+        // them fit after the 12 octet header.
+        let mut msg = MessageBuilder::from_target(target).unwrap().answer();
+        let record = (Dname::root_slice(), 0, A::from_octets(192, 0, 2, 1));
+        for _ in 0..33 {
+            msg.push(&record).unwrap();
+        }
+        assert_eq!(msg.push(&record), Err(ShortBuf));
+        assert_eq!(msg.finish().len(), 12 + 33 * 15);
+    }
+
+    #[test]
+    fn seek_back_and_clone_at() {
+        let mut parser = Parser::from_static(b"\x00\x03abc\x00\x01x");
+
+        // Look ahead at the length, then rewind and re-read the field.
+        let start = parser.pos();
+        let len = parser.parse_u16().unwrap();
+        assert_eq!(parser.parse_octets(len.into()).unwrap(), b"abc");
+        assert_eq!(parser.seek(start), Ok(()));
+        assert_eq!(parser.parse_u16().unwrap(), 3);
+
+        // Branch off without disturbing the original parser.
+        let mut branch = parser.clone_at(5).unwrap();
+        assert_eq!(branch.parse_u16().unwrap(), 1);
+        assert_eq!(branch.peek_all(), b"x");
+        assert_eq!(parser.pos(), 2);
+        assert_eq!(parser.peek_all(), b"abc\x00\x01x");
+        assert!(parser.clone_at(8).is_ok());
+        assert!(parser.clone_at(9).is_err());
+
+        // A parser limited to a range can’t be cloned beyond its end.
+        let parser = Parser::with_range(b"0123456789".as_ref(), 2, 6);
+        assert_eq!(parser.clone_at(0).unwrap().peek_all(), b"012345");
+        assert!(parser.clone_at(7).is_err());
+    }
+
+    #[test]
+    fn with_range() {
+        let mut parser = Parser::with_range(b"0123456789".as_ref(), 2, 6);
+        assert_eq!(parser.pos(), 2);
+        assert_eq!(parser.remaining(), 4);
+        assert_eq!(parser.peek(4), Ok(b"2345".as_ref()));
+        assert_eq!(parser.peek(5), Err(ParseError::ShortInput));
+        assert_eq!(parser.seek(0), Ok(()));
+        assert_eq!(parser.seek(7), Err(ParseError::ShortInput));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn with_range_compressed_name() {
+        use crate::base::name::{Dname, ToDname};
+        use crate::rdata::Mx;
+        use std::vec::Vec;
+
+        // A header, a question for example.com. IN MX, and the data of an
+        // MX record whose exchange name points to the question’s name.
+        let msg = b"\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\
+                    \x07example\x03com\x00\x00\x0f\x00\x01\
+                    \x00\x0a\x04mail\xc0\x0c";
+        let mut parser = Parser::with_range(msg.as_ref(), 29, msg.len());
+        let mx = Mx::parse(&mut parser).unwrap();
+        assert_eq!(parser.remaining(), 0);
+        assert_eq!(mx.preference(), 10);
+        assert_eq!(
+            mx.exchange().to_dname::<Vec<u8>>().unwrap(),
+            Dname::vec_from_str("mail.example.com.").unwrap()
+        );
+    }
+
     #[test]
     fn peek_check_len() {
         let mut parser = Parser::from_static(b"0123456789");
@@ -1646,6 +2151,13 @@ mod test {
         assert_eq!(parser.peek_all(), b"0123456789");
         parser.advance(2).unwrap();
         assert_eq!(parser.peek_all(), b"23456789");
+        parser
+            .parse_block(3, |parser| {
+                assert_eq!(parser.peek_all(), b"234");
+                parser.advance(3)
+            })
+            .unwrap();
+        assert_eq!(parser.peek_all(), b"56789");
     }
 
     #[test]
@@ -1735,4 +2247,141 @@ mod test {
         assert_eq!(parser.parse_u32(), Ok(0xfd78a84e));
         assert_eq!(parser.parse_u32(), Err(ParseError::ShortInput));
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn parse_error_from() {
+        use crate::base::charstr::CharStrError;
+        use crate::base::iana::OptionCode;
+        use crate::base::message::{SanityError, Section};
+        use crate::base::name::{
+            DnameError, LabelTypeError, LongLabelError, ParsedDnameError,
+            RelativeDnameError, SplitLabelError,
+        };
+        use crate::base::opt::OptValidationError;
+        use crate::base::record::RecordParseError;
+        use std::string::ToString;
+
+        fn form(err: impl Into<ParseError>, msg: &str) {
+            let err = err.into();
+            assert!(matches!(err, ParseError::Form(_)));
+            assert_eq!(err.to_string(), msg);
+        }
+
+        fn short(err: impl Into<ParseError>) {
+            assert_eq!(err.into(), ParseError::ShortInput);
+        }
+
+        form(FormError::new("foo"), "foo");
+        form(DnameError::LongName, "long domain name");
+        short(DnameError::ShortInput);
+        form(RelativeDnameError::AbsoluteName, "absolute domain name");
+        form(
+            RelativeDnameError::BadLabel(LabelTypeError::Undefined),
+            "unknown label type",
+        );
+        short(RelativeDnameError::ShortInput);
+        form(ParsedDnameError::LongName, "long domain name");
+        form(LabelTypeError::Extended(0x41), "invalid label type");
+        form(LongLabelError, "long label");
+        form(SplitLabelError::Pointer(12), "compressed domain name");
+        short(SplitLabelError::ShortInput);
+        form(CharStrError, "illegal character string");
+        form(
+            OptValidationError::Duplicate(OptionCode::Cookie),
+            "duplicate option",
+        );
+        form(
+            RecordParseError::<DnameError, CharStrError>::Name(
+                DnameError::CompressedName,
+            ),
+            "compressed domain name",
+        );
+        form(
+            RecordParseError::<DnameError, CharStrError>::Data(CharStrError),
+            "illegal character string",
+        );
+        short(RecordParseError::<DnameError, CharStrError>::ShortBuf);
+        form(SanityError::TrailingData, "trailing data");
+        short(SanityError::Record(Section::Answer, ParseError::ShortInput));
+
+        // The question mark operator works across all of them.
+        fn parse_all() -> Result<(), ParseError> {
+            Err(DnameError::LongName)?;
+            Err(CharStrError)?;
+            Ok(())
+        }
+        assert!(parse_all().is_err());
+    }
+
+    #[test]
+    fn parse_u24() {
+        let mut parser = Parser::from_static(b"\x12\x34\x56\xfd\x78\xa8\0\0");
+        assert_eq!(parser.parse_u24(), Ok(0x123456));
+        assert_eq!(parser.parse_u24(), Ok(0xfd78a8));
+        assert_eq!(parser.parse_u24(), Err(ParseError::ShortInput));
+        assert_eq!(parser.remaining(), 2);
+    }
+
+    #[test]
+    fn parse_char_str() {
+        let mut parser = Parser::from_static(b"\x03foo\x00\x03ba");
+        assert_eq!(parser.parse_char_str().unwrap().as_slice(), b"foo");
+        assert_eq!(parser.parse_char_str().unwrap().as_slice(), b"");
+        assert_eq!(parser.parse_char_str(), Err(ParseError::ShortInput));
+        assert_eq!(parser.remaining(), 3);
+        assert_eq!(parser.peek_all(), b"\x03ba");
+        parser.advance_to_end();
+        assert_eq!(parser.parse_char_str(), Err(ParseError::ShortInput));
+    }
+
+    #[test]
+    fn parse_u48() {
+        let mut parser =
+            Parser::from_static(b"\x12\x34\x56\x78\x9a\xbc\xfd\x78\xa8\x4e");
+        assert_eq!(parser.parse_u48(), Ok(0x123456789abc));
+        assert_eq!(parser.parse_u48(), Err(ParseError::ShortInput));
+        assert_eq!(parser.remaining(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn u24_round_trip() {
+        for &value in &[0, 1, 0xFFFF, 0x01_0000, 0x80_0000, 0xFF_FFFF] {
+            let value = U24::from_u32(value).unwrap();
+            let mut buf = Vec::new();
+            value.compose(&mut buf).unwrap();
+            assert_eq!(buf.len(), 3);
+            let mut parser = Parser::from_ref(buf.as_slice());
+            assert_eq!(U24::parse(&mut parser), Ok(value));
+            assert_eq!(parser.remaining(), 0);
+        }
+        assert_eq!(U24::from_u32(0xFF_FFFF), Some(U24::MAX));
+        assert_eq!(U24::from_u32(0x100_0000), None);
+        assert_eq!(U24::from_u32(u32::MAX), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn u48_round_trip() {
+        for &value in &[
+            0,
+            1,
+            0xFFFF_FFFF,
+            0x1_0000_0000,
+            0x8000_0000_0000,
+            0xFFFF_FFFF_FFFF,
+        ] {
+            let value = U48::from_u64(value).unwrap();
+            let mut buf = Vec::new();
+            value.compose(&mut buf).unwrap();
+            assert_eq!(buf.len(), 6);
+            let mut parser = Parser::from_ref(buf.as_slice());
+            assert_eq!(U48::parse(&mut parser), Ok(value));
+            assert_eq!(parser.remaining(), 0);
+        }
+        assert_eq!(U48::from_u64(0xFFFF_FFFF_FFFF), Some(U48::MAX));
+        assert_eq!(U48::from_u64(0x1_0000_0000_0000), None);
+        assert_eq!(U48::from_u64(u64::MAX), None);
+    }
 }
@@ -43,15 +43,31 @@ use super::header::Header;
 use super::iana::{OptRcode, OptionCode, Rtype};
 use super::name::ToDname;
 use super::octets::{
-    Compose, OctetsBuilder, OctetsFrom, OctetsRef, Parse, ParseError, Parser,
-    ShortBuf,
+    Compose, FormError, OctetsBuilder, OctetsFrom, OctetsRef, Parse,
+    ParseError, Parser, ShortBuf,
 };
 use super::rdata::RtypeRecordData;
-use super::record::Record;
+use super::record::{ParsedRecord, Record};
+#[cfg(feature = "bytes")]
+use bytes::BytesMut;
 use core::cmp::Ordering;
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 use core::marker::PhantomData;
 use core::{fmt, hash, mem, ops};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+//------------ DEFAULT_UDP_PAYLOAD_SIZE --------------------------------------
+
+/// The UDP payload size advertised by default.
+///
+/// This is the value used for a newly created OPT record unless explicitly
+/// changed. Following the recommendation of the [DNS Flag Day 2020], it is
+/// 1232 bytes which is small enough to avoid IP fragmentation on almost all
+/// current networks.
+///
+/// [DNS Flag Day 2020]: https://www.dnsflagday.net/2020/
+pub const DEFAULT_UDP_PAYLOAD_SIZE: u16 = 1232;
 
 //------------ Opt -----------------------------------------------------------
 
@@ -80,6 +96,9 @@ impl<Octets: AsRef<[u8]>> Opt<Octets> {
     ///
     /// The function checks whether the octets contain a sequence of
     /// options. It does not check whether the options themselves are valid.
+    ///
+    /// Empty octets are valid and result in OPT data without any options
+    /// as is commonly used to merely signal support for EDNS.
     pub fn from_octets(octets: Octets) -> Result<Self, ParseError> {
         let mut parser = Parser::from_ref(octets.as_ref());
         while parser.remaining() > 0 {
@@ -90,6 +109,24 @@ impl<Octets: AsRef<[u8]>> Opt<Octets> {
         Ok(Opt { octets })
     }
 
+    /// Returns the length of the record data in octets.
+    pub fn len(&self) -> usize {
+        self.octets.as_ref().len()
+    }
+
+    /// Returns whether the record data is empty, i.e., has no options.
+    pub fn is_empty(&self) -> bool {
+        self.octets.as_ref().is_empty()
+    }
+
+    /// Returns the number of options regardless of their type.
+    ///
+    /// The method walks over the option headers skipping the option data
+    /// according to their length.
+    pub fn option_count(&self) -> usize {
+        self.raw_options().count()
+    }
+
     /// Returns an iterator over options of a given type.
     ///
     /// The returned iterator will return only options represented by type
@@ -101,8 +138,116 @@ impl<Octets: AsRef<[u8]>> Opt<Octets> {
     {
         OptIter::new(&self.octets)
     }
+
+    /// Returns the first option of a given type.
+    ///
+    /// This is useful for options that only ever appear once in a message.
+    /// Options not represented by `Data` are skipped and parsing stops after
+    /// the first option of type `Data` has been found. Returns `None` if
+    /// there is no such option or an error if parsing the option failed.
+    pub fn first<Data>(&self) -> Option<Result<Data, ParseError>>
+    where
+        for<'a> &'a Octets: OctetsRef,
+        Data: for<'a> ParseOptData<&'a Octets>,
+    {
+        self.iter().next()
+    }
+
+    /// Returns an iterator over the raw options.
+    ///
+    /// The iterator returns pairs of the option code and a slice with the
+    /// option’s data, i.e., without the option header. This allows
+    /// looking at options without having to parse them into their types.
+    pub fn raw_options(&self) -> RawOptIter<'_> {
+        RawOptIter::new(self.octets.as_ref())
+    }
+
+    /// Returns an iterator over all options with their headers.
+    ///
+    /// The iterator returns the header of each option and a slice with the
+    /// option’s data. This is similar to
+    /// [`raw_options`][Self::raw_options] but provides the complete header
+    /// which is useful when passing options on untouched. As with that
+    /// method, the iterator quietly ends if the record data turns out to be
+    /// malformed.
+    pub fn iter_all(
+        &self,
+    ) -> impl Iterator<Item = (OptionHeader, &[u8])> + '_ {
+        self.raw_options().map(|(code, data)| {
+            // The length was parsed from a u16, so it fits.
+            (OptionHeader::new(code.to_int(), data.len() as u16), data)
+        })
+    }
+
+    /// Checks that the options are well-formed and not duplicated.
+    ///
+    /// The method walks over all options and checks that each option’s
+    /// data fits into the record data. It also checks that options whose
+    /// codes are listed in [`SINGLE_INSTANCE_OPTIONS`] appear at most once.
+    ///
+    /// The content of the options is not checked.
+    ///
+    /// [`SINGLE_INSTANCE_OPTIONS`]: constant.SINGLE_INSTANCE_OPTIONS.html
+    pub fn validate(&self) -> Result<(), OptValidationError> {
+        let mut parser = Parser::from_ref(self.octets.as_ref());
+        while parser.remaining() > 0 {
+            let code = OptionCode::from_int(
+                parser
+                    .parse_u16()
+                    .map_err(|_| OptValidationError::ShortOption)?,
+            );
+            let len = parser
+                .parse_u16()
+                .map_err(|_| OptValidationError::ShortOption)?;
+            parser
+                .advance(len.into())
+                .map_err(|_| OptValidationError::ShortOption)?;
+            if SINGLE_INSTANCE_OPTIONS.contains(&code)
+                && Self::contains_code(parser.peek_all(), code)
+            {
+                return Err(OptValidationError::Duplicate(code));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the options in `slice` contain an option of `code`.
+    ///
+    /// Stops quietly at the first option that doesn’t fit into the slice.
+    fn contains_code(slice: &[u8], code: OptionCode) -> bool {
+        let mut parser = Parser::from_ref(slice);
+        while let (Ok(this), Ok(len)) =
+            (parser.parse_u16(), parser.parse_u16())
+        {
+            if OptionCode::from_int(this) == code {
+                return true;
+            }
+            if parser.advance(len.into()).is_err() {
+                break;
+            }
+        }
+        false
+    }
 }
 
+/// The option codes that may appear at most once in an OPT record.
+///
+/// These are the options whose specification states that a message must
+/// not contain more than one instance or where multiple instances simply
+/// don’t make sense. [`Opt::validate`] uses this list to detect duplicates.
+///
+/// [`Opt::validate`]: struct.Opt.html#method.validate
+pub const SINGLE_INSTANCE_OPTIONS: &[OptionCode] = &[
+    OptionCode::Nsid,
+    OptionCode::ClientSubnet,
+    OptionCode::Expire,
+    OptionCode::Cookie,
+    OptionCode::TcpKeepalive,
+    OptionCode::Padding,
+    OptionCode::Chain,
+    OptionCode::KeyTag,
+];
+
 //--- OctetsFrom
 
 impl<Octets, SrcOctets> OctetsFrom<Opt<SrcOctets>> for Opt<Octets>
@@ -303,13 +448,37 @@ impl OptHeader {
             self.inner[7] &= 0x7F
         }
     }
+
+    /// Appends the header and the given record data length to a target.
+    ///
+    /// The [`Compose`] implementation only appends the header itself, i.e.,
+    /// everything up to but not including the RDLEN field. This method
+    /// appends the RDLEN field as well, so that only the record data itself
+    /// has to be appended afterwards. If the target runs out of space,
+    /// nothing is appended.
+    pub fn compose_with_rdlen<T: OctetsBuilder>(
+        &self,
+        rdlen: u16,
+        target: &mut T,
+    ) -> Result<(), ShortBuf> {
+        target.append_all(|target| {
+            self.compose(target)?;
+            rdlen.compose(target)
+        })
+    }
 }
 
 impl Default for OptHeader {
+    /// Returns a header with the default UDP payload size.
+    ///
+    /// The UDP payload size is set to [`DEFAULT_UDP_PAYLOAD_SIZE`], all
+    /// other fields are zero.
     fn default() -> Self {
-        OptHeader {
+        let mut res = OptHeader {
             inner: [0, 0, 41, 0, 0, 0, 0, 0, 0],
-        }
+        };
+        res.set_udp_payload_size(DEFAULT_UDP_PAYLOAD_SIZE);
+        res
     }
 }
 
@@ -418,6 +587,23 @@ impl<Octets, N: ToDname> From<Record<N, Opt<Octets>>> for OptRecord<Octets> {
     }
 }
 
+//--- TryFrom
+
+impl<Ref: OctetsRef> TryFrom<ParsedRecord<Ref>> for OptRecord<Ref::Range> {
+    type Error = ParseError;
+
+    /// Converts a parsed record into an OPT record.
+    ///
+    /// Returns an error if the record isn’t of type OPT or its record data
+    /// cannot be parsed.
+    fn try_from(record: ParsedRecord<Ref>) -> Result<Self, Self::Error> {
+        match record.into_record::<Opt<_>>()? {
+            Some(record) => Ok(Self::from_record(record)),
+            None => Err(FormError::new("not an OPT record").into()),
+        }
+    }
+}
+
 //--- OctetsFrom
 
 impl<Octets, SrcOctets> OctetsFrom<OptRecord<SrcOctets>> for OptRecord<Octets>
@@ -451,6 +637,161 @@ impl<Octets> AsRef<Opt<Octets>> for OptRecord<Octets> {
     }
 }
 
+//------------ OptRecordBuilder ----------------------------------------------
+
+/// A builder for an entire OPT record.
+///
+/// While [`OptBuilder`] adds an OPT record directly to a message that is
+/// being built, this type creates a stand-alone [`OptRecord`]. The header
+/// values are kept in an [`OptHeader`] and can be changed through the
+/// builder’s methods. Options are appended to the record data kept in an
+/// octets builder via [`push`][Self::push] or
+/// [`push_raw_option`][Self::push_raw_option]. Finally,
+/// [`finish`][Self::finish] converts the builder into the OPT record.
+///
+/// [`OptBuilder`]: ../message_builder/struct.OptBuilder.html
+#[derive(Clone, Debug)]
+pub struct OptRecordBuilder<Target> {
+    /// The header of the record.
+    header: OptHeader,
+
+    /// The record data assembled so far.
+    target: Target,
+}
+
+impl<Target: OctetsBuilder> OptRecordBuilder<Target> {
+    /// Creates a new builder atop the given octets builder.
+    ///
+    /// The octets builder is truncated to zero length before use. The
+    /// header of the record starts out with the values of
+    /// [`OptHeader::default`].
+    pub fn from_target(mut target: Target) -> Self {
+        target.truncate(0);
+        OptRecordBuilder {
+            header: OptHeader::default(),
+            target,
+        }
+    }
+
+    /// Appends an option to the record data.
+    pub fn push<Data: OptData>(
+        &mut self,
+        opt: &Data,
+    ) -> Result<(), ShortBuf> {
+        self.push_raw_option(opt.code(), |target| opt.compose(target))
+    }
+
+    /// Appends a raw option to the record data.
+    ///
+    /// The method will append an option with the given option code. The
+    /// data of the option will be written via the closure `op`. If the
+    /// record data would grow beyond 65,535 octets, nothing is appended and
+    /// an error is returned.
+    pub fn push_raw_option<F>(
+        &mut self,
+        code: OptionCode,
+        op: F,
+    ) -> Result<(), ShortBuf>
+    where
+        F: FnOnce(&mut Target) -> Result<(), ShortBuf>,
+    {
+        let pos = self.target.as_ref().len();
+        self.target.append_all(|target| {
+            code.compose(target)?;
+            target.u16_len_prefixed(|target| op(target))
+        })?;
+        if self.target.as_ref().len() > usize::from(u16::MAX) {
+            self.target.truncate(pos);
+            return Err(ShortBuf);
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the header of the record.
+    pub fn header(&self) -> &OptHeader {
+        &self.header
+    }
+
+    /// Returns a mutable reference to the header of the record.
+    pub fn header_mut(&mut self) -> &mut OptHeader {
+        &mut self.header
+    }
+
+    /// Sets the UDP payload size field of the record.
+    pub fn set_udp_payload_size(&mut self, value: u16) {
+        self.header.set_udp_payload_size(value)
+    }
+
+    /// Sets the upper bits of the extended rcode.
+    ///
+    /// The lower bits of the rcode need to be set in the message header.
+    pub fn set_rcode(&mut self, rcode: OptRcode) {
+        self.header.set_rcode(rcode)
+    }
+
+    /// Sets the EDNS version of the record.
+    pub fn set_version(&mut self, version: u8) {
+        self.header.set_version(version)
+    }
+
+    /// Sets the DNSSEC OK (DO) bit to the given value.
+    pub fn set_dnssec_ok(&mut self, value: bool) {
+        self.header.set_dnssec_ok(value)
+    }
+
+    /// Converts the builder into the final OPT record.
+    pub fn finish(self) -> OptRecord<Target::Octets> {
+        let inner = self.header.inner;
+        OptRecord {
+            udp_payload_size: self.header.udp_payload_size(),
+            ext_rcode: inner[5],
+            version: inner[6],
+            flags: u16::from_be_bytes([inner[7], inner[8]]),
+            data: Opt {
+                octets: self.target.freeze(),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl OptRecordBuilder<Vec<u8>> {
+    /// Creates a new builder atop a `Vec<u8>`.
+    pub fn new_vec() -> Self {
+        Self::from_target(Vec::new())
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl OptRecordBuilder<BytesMut> {
+    /// Creates a new builder atop a bytes value.
+    pub fn new_bytes() -> Self {
+        Self::from_target(BytesMut::new())
+    }
+}
+
+//------------ check_edns_version --------------------------------------------
+
+/// Checks that the EDNS version of an OPT record is supported.
+///
+/// Only EDNS version 0 is currently defined. According to [RFC 6891],
+/// section 6.1.3, a server receiving a request with a higher version must
+/// respond with the extended rcode BADVERS. This function encapsulates this
+/// check: it returns `Ok(())` if the version is supported and
+/// `Err(OptRcode::BadVers)` otherwise so that the error can be used as the
+/// rcode of the response directly.
+///
+/// [RFC 6891]: https://tools.ietf.org/html/rfc6891
+pub fn check_edns_version<Octets>(
+    opt: &OptRecord<Octets>,
+) -> Result<(), OptRcode> {
+    if opt.version() == 0 {
+        Ok(())
+    } else {
+        Err(OptRcode::BadVers)
+    }
+}
+
 //------------ OptionHeader --------------------------------------------------
 
 /// The header of an OPT option.
@@ -458,7 +799,7 @@ impl<Octets> AsRef<Opt<Octets>> for OptRecord<Octets> {
 /// This header contains a 16 bit option code identifying the kind of option
 /// we are dealing with and a 16 bit length describing the lenngth in octets
 /// of the option data.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct OptionHeader {
     /// The option code.
     code: u16,
@@ -572,6 +913,56 @@ where
     }
 }
 
+//------------ RawOptIter ----------------------------------------------------
+
+/// An iterator over the raw options of an OPT record.
+///
+/// The iterator returns the option code and the option data of each option
+/// in the record. If the record data turns out to be malformed, the
+/// iterator quietly ends.
+///
+/// You can get a value of this type via [`Opt::raw_options`].
+///
+/// [`Opt::raw_options`]: struct.Opt.html#method.raw_options
+#[derive(Clone, Debug)]
+pub struct RawOptIter<'a> {
+    /// A parser for the OPT record data.
+    parser: Parser<&'a [u8]>,
+}
+
+impl<'a> RawOptIter<'a> {
+    /// Creates an iterator from the OPT record data.
+    fn new(octets: &'a [u8]) -> Self {
+        RawOptIter {
+            parser: Parser::from_ref(octets),
+        }
+    }
+
+    /// Returns the next option from the parser.
+    fn next_step(&mut self) -> Result<(OptionCode, &'a [u8]), ParseError> {
+        let code = self.parser.parse_u16()?.into();
+        let len = self.parser.parse_u16()? as usize;
+        Ok((code, self.parser.parse_octets(len)?))
+    }
+}
+
+impl<'a> Iterator for RawOptIter<'a> {
+    type Item = (OptionCode, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.parser.remaining() == 0 {
+            return None;
+        }
+        match self.next_step() {
+            Ok(res) => Some(res),
+            Err(_) => {
+                self.parser.advance_to_end();
+                None
+            }
+        }
+    }
+}
+
 //------------ OptData -------------------------------------------------------
 
 /// A type representing an OPT option.
@@ -780,16 +1171,102 @@ where
     }
 }
 
+//============ Error Types ===================================================
+
+//------------ OptValidationError --------------------------------------------
+
+/// The OPT record data failed validation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OptValidationError {
+    /// An option that may only appear once appeared more than once.
+    Duplicate(OptionCode),
+
+    /// The data of an option exceeded the record data.
+    ShortOption,
+}
+
+//--- From
+
+impl From<OptValidationError> for ParseError {
+    fn from(err: OptValidationError) -> Self {
+        ParseError::Form(FormError::new(match err {
+            OptValidationError::Duplicate(_) => "duplicate option",
+            OptValidationError::ShortOption => "option exceeds record data",
+        }))
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for OptValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OptValidationError::Duplicate(code) => {
+                write!(f, "duplicate option {}", code)
+            }
+            OptValidationError::ShortOption => {
+                f.write_str("option exceeds record data")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OptValidationError {}
+
 //============ Tests =========================================================
 
+/// Parses `octets` as the complete data of an option.
+///
+/// This is a helper for the tests of the individual option modules. It
+/// panics if the option doesn’t consume all of `octets`.
+#[cfg(test)]
+fn parse_all<'a, Data: Parse<&'a [u8]>>(
+    octets: &'a [u8],
+) -> Result<Data, ParseError> {
+    let mut parser = Parser::from_ref(octets);
+    let res = Data::parse(&mut parser)?;
+    assert_eq!(parser.remaining(), 0);
+    Ok(res)
+}
+
+/// Creates a message with an OPT record built by `op`.
+///
+/// This is a helper for the tests of the individual option modules.
+#[cfg(test)]
+#[cfg(feature = "std")]
+fn opt_with<F>(op: F) -> super::Message<Vec<u8>>
+where
+    F: FnOnce(
+        &mut super::message_builder::OptBuilder<Vec<u8>>,
+    ) -> Result<(), ShortBuf>,
+{
+    let mut mb = super::MessageBuilder::new_vec().additional();
+    mb.opt(op).unwrap();
+    mb.into_message()
+}
+
 #[cfg(test)]
 #[cfg(feature = "std")]
 mod test {
     use super::*;
-    use crate::base::record::ParsedRecord;
     use crate::base::{opt, MessageBuilder};
     use std::vec::Vec;
 
+    #[test]
+    fn edns_version() {
+        let mut opt = OptRecord {
+            udp_payload_size: DEFAULT_UDP_PAYLOAD_SIZE,
+            ext_rcode: 0,
+            version: 0,
+            flags: 0,
+            data: Opt::from_octets(b"".as_ref()).unwrap(),
+        };
+        assert_eq!(check_edns_version(&opt), Ok(()));
+        opt.version = 1;
+        assert_eq!(check_edns_version(&opt), Err(OptRcode::BadVers));
+    }
+
     #[test]
     fn opt_record_header() {
         let mut header = OptHeader::default();
@@ -808,30 +1285,365 @@ mod test {
             .unwrap();
         let record = OptRecord::from_record(record);
         assert_eq!(record.udp_payload_size(), 0x1234);
+        assert_eq!(record.ext_rcode, 1);
         assert_eq!(record.ext_rcode, OptRcode::BadVers.ext());
         assert_eq!(record.version(), 0xbd);
         assert!(record.dnssec_ok());
     }
 
     #[test]
-    fn opt_iter() {
-        // Push two options and check that both are parseable
-        let nsid = opt::Nsid::from_octets(&b"example"[..]);
-        let cookie = opt::Cookie::new(1234u64.to_be_bytes());
+    fn opt_header_compose_with_rdlen() {
+        let mut header = OptHeader::default();
+        header.set_version(1);
+        let mut buf = Vec::new();
+        header.compose_with_rdlen(4, &mut buf).unwrap();
+        buf.extend_from_slice(b"\x00\x0c\x00\x00");
+        assert_eq!(buf.len(), 15);
+
+        let mut parser = Parser::from_ref(buf.as_slice());
+        let record =
+            OptRecord::try_from(ParsedRecord::parse(&mut parser).unwrap())
+                .unwrap();
+        assert_eq!(parser.remaining(), 0);
+        assert_eq!(record.udp_payload_size(), DEFAULT_UDP_PAYLOAD_SIZE);
+        assert_eq!(record.version(), 1);
+        let mut options = record.raw_options();
+        assert_eq!(options.next(), Some((OptionCode::Padding, &b""[..])));
+        assert!(options.next().is_none());
+    }
+
+    #[test]
+    fn opt_record_builder() {
+        let mut builder = OptRecordBuilder::new_vec();
+        builder.set_udp_payload_size(4096);
+        builder.set_dnssec_ok(true);
+        builder.set_rcode(OptRcode::BadVers);
+        builder
+            .push(&opt::Nsid::from_octets(b"ns1.example".as_ref()))
+            .unwrap();
+        assert_eq!(builder.header().udp_payload_size(), 4096);
+        let record = builder.finish();
+
+        assert_eq!(record.udp_payload_size(), 4096);
+        assert!(record.dnssec_ok());
+        assert_eq!(record.version(), 0);
+        assert_eq!(
+            record.rcode(Header::new()),
+            OptRcode::from_parts(crate::base::iana::Rcode::NoError, 1)
+        );
+        assert_eq!(
+            record.raw_options().collect::<Vec<_>>(),
+            [(OptionCode::Nsid, b"ns1.example".as_ref())]
+        );
+    }
+
+    #[test]
+    fn opt_record_try_from() {
+        use crate::base::name::Dname;
+        use crate::rdata::A;
+
         let msg = {
             let mut mb = MessageBuilder::new_vec().additional();
-            mb.opt(|mb| {
-                mb.push(&nsid)?;
-                mb.push(&cookie)?;
+            mb.push((
+                Dname::root_slice(),
+                3600,
+                A::from_octets(192, 0, 2, 1),
+            ))
+            .unwrap();
+            mb.opt(|opt| {
+                opt.set_udp_payload_size(4096);
+                opt.set_dnssec_ok(true);
                 Ok(())
             })
             .unwrap();
             mb.into_message()
         };
+        let mut section = msg.additional().unwrap();
+
+        let a = section.next().unwrap().unwrap();
+        assert!(OptRecord::try_from(a).is_err());
+
+        let opt =
+            OptRecord::try_from(section.next().unwrap().unwrap()).unwrap();
+        assert_eq!(opt.udp_payload_size(), 4096);
+        assert_eq!(opt.version(), 0);
+        assert!(opt.dnssec_ok());
+        assert!(section.next().is_none());
+    }
+
+    #[test]
+    fn empty_opt() {
+        let data = Opt::from_octets(&b""[..]).unwrap();
+        assert_eq!(data.raw_options().next(), None);
+        assert!(data.iter::<opt::Nsid<_>>().next().is_none());
+        assert!(data.validate().is_ok());
+
+        // A bare OPT record only signalling EDNS support.
+        let msg = opt_with(|opt| {
+            opt.set_udp_payload_size(1232);
+            Ok(())
+        });
+        assert_eq!(
+            &msg.as_slice()[12..],
+            b"\x00\x00\x29\x04\xd0\x00\x00\x00\x00\x00\x00"
+        );
+
+        let opt = msg.opt().unwrap();
+        assert_eq!(opt.udp_payload_size(), 1232);
+        assert!(opt.as_opt().octets.is_empty());
+        assert_eq!(opt.as_opt().raw_options().next(), None);
+        assert!(opt.iter::<opt::Nsid<_>>().next().is_none());
+        assert!(opt.iter::<opt::Cookie>().next().is_none());
+    }
+
+    #[test]
+    fn opt_iter() {
+        // Push two options and check that both are parseable
+        let nsid = opt::Nsid::from_octets(&b"example"[..]);
+        let cookie = opt::Cookie::new(1234u64.to_be_bytes());
+        let msg = opt_with(|mb| {
+            mb.push(&nsid)?;
+            mb.push(&cookie)?;
+            Ok(())
+        });
 
         // Parse both into specialized types
         let opt = msg.opt().unwrap();
         assert_eq!(Some(Ok(nsid)), opt.iter::<opt::Nsid<_>>().next());
         assert_eq!(Some(Ok(cookie)), opt.iter::<opt::Cookie>().next());
     }
+
+    #[test]
+    fn first() {
+        let mut buf = Vec::new();
+        OptionHeader::new(65001, 0).compose(&mut buf).unwrap();
+        OptionHeader::new(3, 7).compose(&mut buf).unwrap();
+        buf.extend_from_slice(b"example");
+        OptionHeader::new(3, 5).compose(&mut buf).unwrap();
+        buf.extend_from_slice(b"other");
+        let opt = Opt::from_octets(buf.as_slice()).unwrap();
+        assert_eq!(
+            opt.first::<opt::Nsid<_>>(),
+            Some(Ok(opt::Nsid::from_octets(&b"example"[..])))
+        );
+        assert_eq!(opt.first::<opt::Cookie>(), None);
+
+        // A broken option results in an error.
+        let opt = Opt::from_octets(&b"\x00\x0a\x00\x02ab"[..]).unwrap();
+        assert!(opt.first::<opt::Cookie>().unwrap().is_err());
+    }
+
+    #[test]
+    fn iter_all() {
+        let mut buf = Vec::new();
+        OptionHeader::new(3, 7).compose(&mut buf).unwrap();
+        buf.extend_from_slice(b"example");
+        OptionHeader::new(65001, 0).compose(&mut buf).unwrap();
+        let opt = Opt::from_octets(buf.as_slice()).unwrap();
+        let mut iter = opt.iter_all();
+        assert_eq!(
+            iter.next(),
+            Some((OptionHeader::new(3, 7), b"example".as_ref()))
+        );
+        assert_eq!(
+            iter.next(),
+            Some((OptionHeader::new(65001, 0), b"".as_ref()))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn count_and_len() {
+        let mut buf = Vec::new();
+        OptionHeader::new(3, 7).compose(&mut buf).unwrap();
+        buf.extend_from_slice(b"example");
+        OptionHeader::new(65001, 0).compose(&mut buf).unwrap();
+        OptionHeader::new(12, 2).compose(&mut buf).unwrap();
+        buf.extend_from_slice(b"\0\0");
+        let opt = Opt::from_octets(buf.as_slice()).unwrap();
+        assert_eq!(opt.option_count(), 3);
+        assert_eq!(opt.len(), 21);
+        assert!(!opt.is_empty());
+
+        let opt = Opt::from_octets(b"".as_ref()).unwrap();
+        assert_eq!(opt.option_count(), 0);
+        assert_eq!(opt.len(), 0);
+        assert!(opt.is_empty());
+    }
+
+    #[test]
+    fn raw_options() {
+        let mut buf = Vec::new();
+        OptionHeader::new(3, 7).compose(&mut buf).unwrap();
+        buf.extend_from_slice(b"example");
+        OptionHeader::new(65001, 0).compose(&mut buf).unwrap();
+        let opt = Opt::from_octets(buf.as_slice()).unwrap();
+        let mut iter = opt.raw_options();
+        assert_eq!(iter.next(), Some((OptionCode::Nsid, &b"example"[..])));
+        assert_eq!(iter.next(), Some((OptionCode::Int(65001), &b""[..])));
+        assert_eq!(iter.next(), None);
+
+        // A short option ends the iterator.
+        let opt = Opt {
+            octets: &b"\x00\x03\x00\x05abc"[..],
+        };
+        assert_eq!(opt.raw_options().next(), None);
+    }
+
+    #[test]
+    fn validate() {
+        // NSID, COOKIE, and an unknown option 65001 twice.
+        let mut buf = Vec::new();
+        OptionHeader::new(3, 0).compose(&mut buf).unwrap();
+        OptionHeader::new(10, 8).compose(&mut buf).unwrap();
+        buf.extend_from_slice(&1234u64.to_be_bytes());
+        OptionHeader::new(65001, 1).compose(&mut buf).unwrap();
+        buf.push(1);
+        OptionHeader::new(65001, 1).compose(&mut buf).unwrap();
+        buf.push(2);
+        assert_eq!(
+            Opt::from_octets(buf.as_slice()).unwrap().validate(),
+            Ok(())
+        );
+
+        // Add a second COOKIE.
+        OptionHeader::new(10, 8).compose(&mut buf).unwrap();
+        buf.extend_from_slice(&5678u64.to_be_bytes());
+        assert_eq!(
+            Opt::from_octets(buf.as_slice()).unwrap().validate(),
+            Err(OptValidationError::Duplicate(OptionCode::Cookie))
+        );
+
+        // Bypass the check in from_octets to get a short option.
+        let opt = Opt {
+            octets: &b"\x00\x03\x00\x05abc"[..],
+        };
+        assert_eq!(opt.validate(), Err(OptValidationError::ShortOption));
+    }
+
+    /// Feeds arbitrary data to all the ways OPT record data can be parsed.
+    ///
+    /// The only requirement is that nothing panics.
+    fn fuzz_opt(data: &[u8]) {
+        fn iter_all<'x, D>(opt: &Opt<&'x [u8]>) -> usize
+        where
+            D: for<'a> ParseOptData<&'a &'x [u8]>,
+        {
+            opt.iter::<D>().count()
+        }
+
+        let _ = Opt::parse(&mut Parser::from_ref(data));
+        let opt = match Opt::from_octets(data) {
+            Ok(opt) => opt,
+            Err(_) => return,
+        };
+        let _ = opt.validate();
+        for (_, value) in opt.raw_options() {
+            assert!(value.len() <= usize::from(u16::MAX));
+        }
+        for item in opt.iter::<AllOptData<_>>() {
+            if let Ok(AllOptData::KeyTag(tags)) = item {
+                tags.iter().count();
+            }
+        }
+        for item in opt.iter::<opt::Dau<_>>().flatten() {
+            item.iter().count();
+        }
+        for item in opt.iter::<opt::ClientSubnet>().flatten() {
+            let _ = item.addr();
+        }
+        iter_all::<opt::Nsid<_>>(&opt);
+        iter_all::<opt::Dhu<_>>(&opt);
+        iter_all::<opt::N3u<_>>(&opt);
+        iter_all::<opt::Expire>(&opt);
+        iter_all::<opt::TcpKeepalive>(&opt);
+        iter_all::<opt::Padding>(&opt);
+        iter_all::<opt::Cookie>(&opt);
+        iter_all::<opt::Chain<_>>(&opt);
+        iter_all::<opt::ExtendedError<_>>(&opt);
+        iter_all::<UnknownOptData<_>>(&opt);
+    }
+
+    /// Returns the seed corpus for `fuzz_opt`.
+    ///
+    /// This contains one valid OPT record data with each known option as
+    /// well as a few broken ones.
+    fn fuzz_opt_corpus() -> Vec<Vec<u8>> {
+        let mut res = Vec::new();
+        let mut all = Vec::new();
+        for (code, data) in &[
+            (3u16, &b"nsid"[..]),
+            (5, b"\x08\x0d"),
+            (6, b"\x01"),
+            (7, b"\x02"),
+            (8, b"\x00\x01\x18\x00\xc0\x00\x02"),
+            (8, b"\x00\x02\x30\x00\x20\x01\x0d\xb8\x00\x00"),
+            (8, b"\x00\x03\x00\x00"),
+            (9, b""),
+            (9, b"\x00\x00\x0e\x10"),
+            (10, b"\x01\x02\x03\x04\x05\x06\x07\x08"),
+            (11, b"\x00\x64"),
+            (12, b"\x00\x00\x00"),
+            (13, b"\x07example\x03com\x00"),
+            (14, b"\x4f\x66\x12\x34"),
+            (15, b"\x00\x06bogus"),
+            (15, b"\x00\x06\xff"),
+            (65001, b"\x01\x02\x03"),
+        ] {
+            let mut opt = Vec::new();
+            OptionHeader::new(*code, data.len() as u16)
+                .compose(&mut opt)
+                .unwrap();
+            opt.extend_from_slice(data);
+            all.extend_from_slice(&opt);
+            res.push(opt);
+        }
+        res.push(all);
+        res.push(b"\x00\x0d\x00\x02\xc0\x00".to_vec());
+        res.push(b"\x00\x0e\x00\x03\x00\x01\x02".to_vec());
+        res.push(b"\x00\x0a\xff\xff".to_vec());
+        res
+    }
+
+    #[test]
+    fn fuzz_opt_truncated() {
+        // All seeds, all their truncations, and all single octet changes.
+        for seed in fuzz_opt_corpus() {
+            for len in 0..=seed.len() {
+                fuzz_opt(&seed[..len]);
+            }
+            for pos in 0..seed.len() {
+                for value in &[0u8, 1, 2, 0x3f, 0x40, 0x80, 0xc0, 0xff] {
+                    let mut data = seed.clone();
+                    data[pos] = *value;
+                    fuzz_opt(&data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_opt_random() {
+        // Random option data with a plausible option header and a simple
+        // xorshift generator to keep things reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..10_000 {
+            let mut data = Vec::new();
+            for _ in 0..(next() % 4) {
+                let code = (next() % 17) as u16;
+                let len = (next() % 24) as u16;
+                OptionHeader::new(code, len).compose(&mut data).unwrap();
+                for _ in 0..len {
+                    data.push(next() as u8);
+                }
+            }
+            fuzz_opt(&data);
+        }
+    }
 }
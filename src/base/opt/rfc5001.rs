@@ -6,7 +6,7 @@ use super::super::message_builder::OptBuilder;
 use super::super::octets::{
     Compose, OctetsBuilder, OctetsRef, Parse, ParseError, Parser, ShortBuf
 };
-use super::CodeOptData;
+use super::{CodeOptData, Opt};
 
 
 //------------ Nsid ---------------------------------------------------------/
@@ -14,7 +14,7 @@ use super::CodeOptData;
 /// The Name Server Identifier (NSID) Option.
 ///
 /// Specified in RFC 5001.
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Nsid<Octets> {
     octets: Octets,
 }
@@ -23,6 +23,33 @@ impl<Octets> Nsid<Octets> {
     pub fn from_octets(octets: Octets) -> Self {
         Nsid { octets }
     }
+
+    /// Returns whether the option is an NSID request.
+    ///
+    /// A client requests the server’s identifier by including an empty
+    /// NSID option in its query.
+    pub fn is_request(&self) -> bool
+    where Octets: AsRef<[u8]> {
+        self.octets.as_ref().is_empty()
+    }
+
+    /// Returns the identifier as a string if it is printable ASCII.
+    ///
+    /// While the identifier can be arbitrary data, it often is a
+    /// human-readable string. If all octets are printable ASCII
+    /// characters, including the space, they are returned as a string.
+    /// Otherwise, `None` is returned.
+    pub fn as_str(&self) -> Option<&str>
+    where Octets: AsRef<[u8]> {
+        let octets = self.octets.as_ref();
+        if octets.iter().all(|&ch| (0x20..0x7F).contains(&ch)) {
+            // Printable ASCII is always valid UTF-8.
+            core::str::from_utf8(octets).ok()
+        }
+        else {
+            None
+        }
+    }
 }
 
 impl Nsid<()> {
@@ -36,6 +63,31 @@ impl Nsid<()> {
             target.append_slice(data)
         })
     }
+
+    /// Adds the server’s identifier to a response if it was requested.
+    ///
+    /// Checks whether the OPT record data of a query given via `request`
+    /// contains an empty NSID option. If so, pushes an NSID option with
+    /// `data` as the server’s identifier to `builder` and returns `true`.
+    /// Otherwise leaves the builder untouched and returns `false`.
+    pub fn push_response<Target, Octets, Data>(
+        builder: &mut OptBuilder<Target>,
+        request: &Opt<Octets>,
+        data: &Data
+    ) -> Result<bool, ShortBuf>
+    where
+        Target: OctetsBuilder,
+        Octets: AsRef<[u8]>,
+        Data: AsRef<[u8]>,
+    {
+        let requested = request.raw_options().any(|(code, value)| {
+            code == OptionCode::Nsid && value.is_empty()
+        });
+        if requested {
+            Self::push(builder, data)?;
+        }
+        Ok(requested)
+    }
 }
 
 impl<Ref: OctetsRef> Parse<Ref> for Nsid<Ref::Range> {
@@ -71,10 +123,115 @@ impl<Octets: AsRef<[u8]>> fmt::Display for Nsid<Octets> {
         // | User interfaces MUST read and write the contents of the NSID
         // | option as a sequence of hexadecimal digits, two digits per
         // | payload octet.
+        //
+        // Like dig, we add the identifier as a string if it is printable.
         for v in self.octets.as_ref() {
-            write!(f, "{:X}", *v)?
+            write!(f, "{:02x}", *v)?
         }
-        Ok(())
+        match self.as_str() {
+            Some(s) if !s.is_empty() => write!(f, " (\"{}\")", s),
+            _ => Ok(())
+        }
+    }
+}
+
+
+impl<Octets: AsRef<[u8]>> fmt::Debug for Nsid<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Nsid(")?;
+        for v in self.octets.as_ref() {
+            write!(f, "{:02x}", *v)?
+        }
+        f.write_str(")")
     }
 }
 
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use crate::base::iana::{Rcode, Rtype};
+    use crate::base::message::Message;
+    use crate::base::message_builder::MessageBuilder;
+    use crate::base::name::Dname;
+    use std::vec::Vec;
+
+    fn query(nsid: bool) -> Message<Vec<u8>> {
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push((Dname::vec_from_str("example.com").unwrap(), Rtype::A))
+            .unwrap();
+        let mut msg = msg.additional();
+        msg.opt(|opt| {
+            if nsid {
+                Nsid::push(opt, b"")?;
+            }
+            Ok(())
+        }).unwrap();
+        msg.into_message()
+    }
+
+    fn respond(query: &Message<Vec<u8>>) -> (bool, Message<Vec<u8>>) {
+        let request = query.opt().unwrap();
+        let mut msg = MessageBuilder::new_vec()
+            .start_answer(query, Rcode::NoError).unwrap()
+            .additional();
+        let pushed = msg.opt(|opt| {
+            Nsid::push_response(opt, request.as_opt(), b"ns1.example")
+        }).unwrap();
+        (pushed, msg.into_message())
+    }
+
+    #[test]
+    fn push_response() {
+        let query = query(true);
+        let request = query.opt().unwrap();
+        let mut iter = request.iter::<Nsid<_>>();
+        assert!(iter.next().unwrap().unwrap().is_request());
+
+        let (pushed, response) = respond(&query);
+        assert!(pushed);
+        let opt = response.opt().unwrap();
+        let mut iter = opt.iter::<Nsid<_>>();
+        let nsid = iter.next().unwrap().unwrap();
+        assert!(!nsid.is_request());
+        assert_eq!(nsid, Nsid::from_octets(b"ns1.example".as_ref()));
+        assert!(iter.next().is_none());
+
+        let (pushed, response) = respond(&self::query(false));
+        assert!(!pushed);
+        assert!(response.opt().unwrap().iter::<Nsid<_>>().next().is_none());
+    }
+
+    #[test]
+    fn nsid_debug() {
+        use std::format;
+
+        assert_eq!(
+            format!("{:?}", Nsid::from_octets(b"ns1\x00".as_ref())),
+            "Nsid(6e733100)"
+        );
+        assert_eq!(format!("{:?}", Nsid::from_octets(b"".as_ref())), "Nsid()");
+    }
+
+    #[test]
+    fn nsid_display() {
+        use std::string::ToString;
+
+        let nsid = Nsid::from_octets(b"ns1.example".as_ref());
+        assert_eq!(nsid.as_str(), Some("ns1.example"));
+        assert_eq!(
+            nsid.to_string(), "6e73312e6578616d706c65 (\"ns1.example\")"
+        );
+
+        let nsid = Nsid::from_octets(b"\x01\xaans".as_ref());
+        assert_eq!(nsid.as_str(), None);
+        assert_eq!(nsid.to_string(), "01aa6e73");
+
+        let nsid = Nsid::from_octets(b"".as_ref());
+        assert_eq!(nsid.as_str(), Some(""));
+        assert_eq!(nsid.to_string(), "");
+    }
+}
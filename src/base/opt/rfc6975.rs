@@ -1,6 +1,6 @@
 //! EDNS Options from RFC 6975.
 
-use core::slice;
+use core::{fmt, slice};
 use super::super::iana::{OptionCode, SecAlg};
 use super::super::message_builder::OptBuilder;
 use super::super::octets::{
@@ -13,7 +13,7 @@ use super::CodeOptData;
 
 macro_rules! option_type {
     ( $name:ident ) => {
-        #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        #[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
         pub struct $name<Octets> {
             octets: Octets,
         }
@@ -30,6 +30,13 @@ macro_rules! option_type {
         }
 
         impl $name<()> {
+            /// Appends an option with the given algorithms to an OPT record.
+            ///
+            /// Each algorithm is written as its single octet value.
+            ///
+            /// # Panics
+            ///
+            /// The function panics if there are more than 65,535 algorithms.
             pub fn push<Target: OctetsBuilder>(
                 builder: &mut OptBuilder<Target>,
                 algs: &[SecAlg]
@@ -77,6 +84,15 @@ macro_rules! option_type {
         }
 
         
+        //--- Debug
+
+        impl<Octets: AsRef<[u8]>> fmt::Debug for $name<Octets> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.iter()).finish()
+            }
+        }
+
+
         //--- IntoIter
 
         impl<'a, Octets: AsRef<[u8]>> IntoIterator for &'a $name<Octets> {
@@ -97,6 +113,7 @@ option_type!(N3u);
 
 //------------ SecAlgsIter ---------------------------------------------------
 
+#[derive(Clone)]
 pub struct SecAlgsIter<'a>(slice::Iter<'a, u8>);
 
 impl<'a> SecAlgsIter<'a> {
@@ -112,3 +129,73 @@ impl<'a> Iterator for SecAlgsIter<'a> {
         self.0.next().map(|x| SecAlg::from_int(*x))
     }
 }
+
+impl<'a> fmt::Debug for SecAlgsIter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use super::super::opt_with;
+    use std::format;
+    use std::vec::Vec;
+
+    #[test]
+    fn known_algs() {
+        let dau = Dau::from_octets(b"\x0d\x63".as_ref());
+        let mut iter = dau.iter();
+        let alg = iter.next().unwrap();
+        assert_eq!(alg, SecAlg::EcdsaP256Sha256);
+        assert!(alg.is_known());
+        let alg = iter.next().unwrap();
+        assert_eq!(alg, SecAlg::Int(99));
+        assert!(!alg.is_known());
+        assert!(iter.next().is_none());
+        assert!(SecAlg::Int(13).is_known());
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", Dau::from_octets(b"\x08\x0d".as_ref())),
+            "Dau([RsaSha256, EcdsaP256Sha256])"
+        );
+        assert_eq!(
+            format!("{:?}", Dhu::from_octets(b"\xc8".as_ref())),
+            "Dhu([Int(200)])"
+        );
+        assert_eq!(
+            format!("{:?}", N3u::from_octets(b"".as_ref())), "N3u([])"
+        );
+    }
+
+    #[test]
+    fn push_and_parse() {
+        let msg = opt_with(|opt| {
+            Dau::push(opt, &[SecAlg::RsaSha256, SecAlg::Ed25519])?;
+            Dhu::push(opt, &[])?;
+            N3u::push(opt, &[SecAlg::Int(1)])
+        });
+        let opt = msg.opt().unwrap();
+        assert_eq!(
+            opt.first::<Dau<_>>().unwrap().unwrap().iter()
+                .collect::<Vec<_>>(),
+            [SecAlg::RsaSha256, SecAlg::Ed25519]
+        );
+        assert_eq!(
+            opt.first::<Dhu<_>>().unwrap().unwrap().iter().next(), None
+        );
+        assert_eq!(
+            opt.first::<N3u<_>>().unwrap().unwrap().iter()
+                .collect::<Vec<_>>(),
+            [SecAlg::Int(1)]
+        );
+    }
+}
@@ -10,6 +10,11 @@ use super::CodeOptData;
 
 //------------ Expire --------------------------------------------------------
 
+/// The EDNS EXPIRE option.
+///
+/// In a query, the option is empty to ask for the expire timer of a zone.
+/// In a response, it contains the expire timer as a 32 bit value. This is
+/// represented by an optional value that is `None` for the empty option.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Expire(Option<u32>);
 
@@ -25,9 +30,17 @@ impl Expire {
         builder.push(&Self::new(expire))
     }
 
+    /// Returns the expire timer in seconds if present.
     pub fn expire(self) -> Option<u32> {
         self.0
     }
+
+    /// Returns the expire timer in seconds if present.
+    ///
+    /// This is the same as [`expire`][Self::expire].
+    pub fn ttl(self) -> Option<u32> {
+        self.0
+    }
 }
 
 
@@ -72,3 +85,37 @@ impl CodeOptData for Expire {
     const CODE: OptionCode = OptionCode::Expire;
 }
 
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::super::octets::Octets64;
+
+    fn parse(octets: &[u8]) -> Result<Expire, ParseError> {
+        super::super::parse_all(octets)
+    }
+
+    #[test]
+    fn empty() {
+        let expire = parse(b"").unwrap();
+        assert_eq!(expire, Expire::new(None));
+        assert_eq!(expire.expire(), None);
+        assert_eq!(expire.ttl(), None);
+        let mut buf = Octets64::new();
+        expire.compose(&mut buf).unwrap();
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn with_timer() {
+        let expire = parse(b"\x00\x01\x51\x80").unwrap();
+        assert_eq!(expire.expire(), Some(86400));
+        assert_eq!(expire.ttl(), Some(86400));
+        let mut buf = Octets64::new();
+        expire.compose(&mut buf).unwrap();
+        assert_eq!(buf.as_slice(), b"\x00\x01\x51\x80");
+        assert!(parse(b"\x00\x01").is_err());
+    }
+}
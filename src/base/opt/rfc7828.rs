@@ -10,22 +10,29 @@ use super::CodeOptData;
 
 //------------ TcpKeepalive --------------------------------------------------
 
+/// The edns-tcp-keepalive option.
+///
+/// In a query, the option is empty and merely signals that the client
+/// supports keepalive. In a response, it contains the idle timeout in
+/// units of 100 milliseconds. The timeout is therefore optional and `None`
+/// for the empty option.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct TcpKeepalive(u16);
+pub struct TcpKeepalive(Option<u16>);
 
 impl TcpKeepalive {
-    pub fn new(timeout: u16) -> Self {
+    pub fn new(timeout: Option<u16>) -> Self {
         TcpKeepalive(timeout)
     }
 
     pub fn push<Target: OctetsBuilder>(
         builder: &mut OptBuilder<Target>,
-        timeout: u16
+        timeout: Option<u16>
     ) -> Result<(), ShortBuf> {
         builder.push(&Self::new(timeout))
     }
 
-    pub fn timeout(self) -> u16 {
+    /// Returns the idle timeout in units of 100 milliseconds if present.
+    pub fn timeout(self) -> Option<u16> {
         self.0
     }
 }
@@ -35,11 +42,21 @@ impl TcpKeepalive {
 
 impl<Ref: AsRef<[u8]>> Parse<Ref> for TcpKeepalive {
     fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
-        u16::parse(parser).map(Self::new)
+        if parser.remaining() == 0 {
+            Ok(Self::new(None))
+        }
+        else {
+            u16::parse(parser).map(|res| Self::new(Some(res)))
+        }
     }
 
     fn skip(parser: &mut Parser<Ref>) -> Result<(), ParseError> {
-        u16::skip(parser)
+        if parser.remaining() == 0 {
+            Ok(())
+        }
+        else {
+            u16::skip(parser)
+        }
     }
 }
 
@@ -48,7 +65,10 @@ impl Compose for TcpKeepalive {
         &self,
         target: &mut T
     ) -> Result<(), ShortBuf> {
-        self.0.compose(target)
+        if let Some(value) = self.0 {
+            value.compose(target)?;
+        }
+        Ok(())
     }
 }
 
@@ -59,3 +79,34 @@ impl CodeOptData for TcpKeepalive {
     const CODE: OptionCode = OptionCode::TcpKeepalive;
 }
 
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::super::octets::Octets64;
+
+    fn parse(octets: &[u8]) -> Result<TcpKeepalive, ParseError> {
+        super::super::parse_all(octets)
+    }
+
+    #[test]
+    fn query_form() {
+        let keepalive = parse(b"").unwrap();
+        assert_eq!(keepalive.timeout(), None);
+        let mut buf = Octets64::new();
+        keepalive.compose(&mut buf).unwrap();
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn response_form() {
+        let keepalive = parse(b"\x01\x2c").unwrap();
+        assert_eq!(keepalive, TcpKeepalive::new(Some(300)));
+        let mut buf = Octets64::new();
+        keepalive.compose(&mut buf).unwrap();
+        assert_eq!(buf.as_slice(), b"\x01\x2c");
+        assert!(parse(b"\x01").is_err());
+    }
+}
@@ -1,10 +1,11 @@
 //! EDNS Options from RFC 7830
 
+use core::fmt;
 use rand::random;
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
 use super::super::octets::{
-    Compose, OctetsBuilder, Parse, ParseError, Parser, ShortBuf
+    Compose, FormError, OctetsBuilder, Parse, ParseError, Parser, ShortBuf
 };
 use super::CodeOptData;
 
@@ -39,6 +40,51 @@ impl Padding {
         builder.push(&Self::new(len, mode))
     }
 
+    /// Appends padding so the message length is a multiple of `block_len`.
+    ///
+    /// This implements the Block-Length Padding strategy of RFC 8467. The
+    /// length of the padding is chosen so that the complete message,
+    /// including the option header of the padding option, ends at the next
+    /// block boundary. RFC 8467 recommends a block length of 128 octets for
+    /// queries and 468 octets for responses.
+    ///
+    /// Since the padding has to be the last option of the OPT record, this
+    /// should be called after all other options have been pushed.
+    pub fn push_block<Target: OctetsBuilder + AsRef<[u8]>>(
+        builder: &mut OptBuilder<Target>,
+        block_len: u16,
+        mode: PaddingMode
+    ) -> Result<(), ShortBuf> {
+        let block_len = usize::from(block_len);
+        let len = if block_len == 0 {
+            0
+        }
+        else {
+            // Four octets for option code and length.
+            let msg_len = builder.as_target().as_ref().len() + 4;
+            (block_len - msg_len % block_len) % block_len
+        };
+        Self::push(builder, len as u16, mode)
+    }
+
+    /// Parses padding and checks that all padding octets are zero.
+    ///
+    /// RFC 7830 recommends zero octets for padding but allows receivers to
+    /// accept any content, which is what the [`Parse`] implementation does.
+    /// This function can be used instead if non-zero padding should be
+    /// rejected. Like the `Parse` implementation, it consumes all remaining
+    /// data of the parser.
+    pub fn parse_all_strict<Ref: AsRef<[u8]>>(
+        parser: &mut Parser<Ref>
+    ) -> Result<Self, PaddingParseError> {
+        let len = parser.remaining();
+        if parser.peek_all().iter().any(|&octet| octet != 0) {
+            return Err(PaddingParseError::NonZeroPadding)
+        }
+        parser.advance_to_end();
+        Ok(Padding::new(len as u16, PaddingMode::Zero))
+    }
+
     pub fn len(self) -> u16 {
         self.len
     }
@@ -57,7 +103,8 @@ impl Padding {
 
 impl<Ref: AsRef<[u8]>> Parse<Ref> for Padding {
     fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
-        // XXX Check whether there really are all zeros.
+        // The content of the padding is not checked. Use
+        // `Padding::parse_all_strict` if you want this.
         let len = parser.remaining();
         parser.advance(len)?;
         Ok(Padding::new(len as u16, PaddingMode::Zero))
@@ -96,3 +143,78 @@ impl CodeOptData for Padding {
     const CODE: OptionCode = OptionCode::Padding;
 }
 
+
+//============ Error Types ===================================================
+
+//------------ PaddingParseError ---------------------------------------------
+
+/// Strictly parsing padding failed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaddingParseError {
+    /// The padding contained octets other than zero.
+    NonZeroPadding,
+}
+
+//--- From
+
+impl From<PaddingParseError> for ParseError {
+    fn from(err: PaddingParseError) -> Self {
+        match err {
+            PaddingParseError::NonZeroPadding => {
+                ParseError::Form(FormError::new("non-zero padding"))
+            }
+        }
+    }
+}
+
+//--- Display and Error
+
+impl fmt::Display for PaddingParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PaddingParseError::NonZeroPadding => {
+                f.write_str("non-zero padding")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PaddingParseError {}
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_all_strict() {
+        let mut parser = Parser::from_static(b"\0\0\0\0");
+        assert_eq!(
+            Padding::parse_all_strict(&mut parser),
+            Ok(Padding::new(4, PaddingMode::Zero))
+        );
+        assert_eq!(parser.remaining(), 0);
+
+        let mut parser = Parser::from_static(b"");
+        assert_eq!(
+            Padding::parse_all_strict(&mut parser),
+            Ok(Padding::new(0, PaddingMode::Zero))
+        );
+
+        let mut parser = Parser::from_static(b"\0\0\x01\0");
+        assert_eq!(
+            Padding::parse_all_strict(&mut parser),
+            Err(PaddingParseError::NonZeroPadding)
+        );
+        assert_eq!(parser.remaining(), 4);
+
+        // The lenient parser accepts any content.
+        assert_eq!(
+            Padding::parse(&mut parser),
+            Ok(Padding::new(4, PaddingMode::Zero))
+        );
+    }
+}
@@ -2,7 +2,7 @@
 
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
-use super::super::net::IpAddr;
+use super::super::net::{IpAddr, IpPrefix, PrefixLenError};
 use super::super::octets::{
     Compose, FormError, OctetsBuilder, Parse, ParseError, Parser, ShortBuf,
 };
@@ -11,6 +11,12 @@ use super::CodeOptData;
 //------------ ClientSubnet --------------------------------------------------
 
 const ERR_ADDR_LEN: &str = "invalid address length in client subnet option";
+const ERR_SOURCE_LEN: &str =
+    "source prefix length too long in client subnet option";
+const ERR_SCOPE_LEN: &str =
+    "scope prefix length too long in client subnet option";
+const ERR_HOST_BITS: &str =
+    "address bits beyond source prefix in client subnet option";
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct ClientSubnet {
@@ -25,17 +31,37 @@ impl ClientSubnet {
         scope_prefix_len: u8,
         addr: IpAddr,
     ) -> ClientSubnet {
-        let source_prefix_len = normalize_prefix_len(addr, source_prefix_len);
-        let scope_prefix_len = normalize_prefix_len(addr, scope_prefix_len);
-        let (addr, _) = addr_apply_mask(addr, source_prefix_len);
+        let source = IpPrefix::new_saturating(addr, source_prefix_len);
+        let scope = IpPrefix::new_saturating(addr, scope_prefix_len);
 
         ClientSubnet {
-            source_prefix_len,
-            scope_prefix_len,
-            addr,
+            source_prefix_len: source.len(),
+            scope_prefix_len: scope.len(),
+            addr: source.addr(),
         }
     }
 
+    /// Creates a new option checking the prefix lengths.
+    ///
+    /// In contrast to [`new`][Self::new] which limits the prefix lengths to
+    /// the length of the address, this function returns an error if either
+    /// prefix length is longer than 32 bits for an IPv4 address or 128 bits
+    /// for an IPv6 address. Any bits of the address beyond the source
+    /// prefix length are cleared.
+    pub fn try_new(
+        source_prefix_len: u8,
+        scope_prefix_len: u8,
+        addr: IpAddr,
+    ) -> Result<ClientSubnet, PrefixLenError> {
+        let source = IpPrefix::new(addr, source_prefix_len)?;
+        let scope = IpPrefix::new(addr, scope_prefix_len)?;
+        Ok(ClientSubnet {
+            source_prefix_len: source.len(),
+            scope_prefix_len: scope.len(),
+            addr: source.addr(),
+        })
+    }
+
     pub fn push<Target: OctetsBuilder>(
         builder: &mut OptBuilder<Target>,
         source_prefix_len: u8,
@@ -54,6 +80,11 @@ impl ClientSubnet {
     pub fn addr(&self) -> IpAddr {
         self.addr
     }
+
+    /// Returns the source prefix as an IP prefix.
+    pub fn source_prefix(&self) -> IpPrefix {
+        IpPrefix::new_saturating(self.addr, self.source_prefix_len)
+    }
 }
 
 //--- Parse and Compose
@@ -70,39 +101,17 @@ impl<Ref: AsRef<[u8]>> Parse<Ref> for ClientSubnet {
         // | IPv6 address, depending on FAMILY, which MUST be truncated to
         // | the number of bits indicated by the SOURCE PREFIX-LENGTH field,
         // | padding with 0 bits to pad to the end of the last octet needed.
-        let prefix_bytes = prefix_bytes(usize::from(source_prefix_len));
-
         let addr = match family {
-            1 => {
-                let mut buf = [0; 4];
-                if prefix_bytes > buf.len() {
-                    return Err(ParseError::form_error(ERR_ADDR_LEN));
-                }
-                parser
-                    .parse_buf(&mut buf[..prefix_bytes])
-                    .map_err(|_| ParseError::form_error(ERR_ADDR_LEN))?;
-
-                if parser.remaining() != 0 {
-                    return Err(ParseError::form_error(ERR_ADDR_LEN));
-                }
-
-                IpAddr::from(buf)
-            }
-            2 => {
-                let mut buf = [0; 16];
-                if prefix_bytes > buf.len() {
-                    return Err(ParseError::form_error(ERR_ADDR_LEN));
-                }
-                parser
-                    .parse_buf(&mut buf[..prefix_bytes])
-                    .map_err(|_| ParseError::form_error(ERR_ADDR_LEN))?;
-
-                if parser.remaining() != 0 {
-                    return Err(ParseError::form_error(ERR_ADDR_LEN));
-                }
-
-                IpAddr::from(buf)
-            }
+            1 => IpAddr::from(parse_addr::<_, [u8; 4]>(
+                parser,
+                source_prefix_len,
+                scope_prefix_len,
+            )?),
+            2 => IpAddr::from(parse_addr::<_, [u8; 16]>(
+                parser,
+                source_prefix_len,
+                scope_prefix_len,
+            )?),
             _ => {
                 return Err(FormError::new(
                     "invalid client subnet address family",
@@ -113,9 +122,8 @@ impl<Ref: AsRef<[u8]>> Parse<Ref> for ClientSubnet {
 
         // If the trailing bits beyond prefix length are not zero,
         // return form error.
-        let (addr, modified) = addr_apply_mask(addr, source_prefix_len);
-        if modified {
-            return Err(ParseError::form_error(ERR_ADDR_LEN));
+        if IpPrefix::has_host_bits(addr, source_prefix_len) {
+            return Err(ParseError::form_error(ERR_HOST_BITS));
         }
 
         // no need to pass the normalizer in constructor again
@@ -168,62 +176,30 @@ fn prefix_bytes(bits: usize) -> usize {
     (bits + 7) / 8
 }
 
-// Apply a prefix bit mask indicated by its length to the provided
-// buffer, clear rest of the buffer which is not covered by the mask.
-// Reture whether or not the buffer has been modified.
-fn apply_bit_mask(buf: &mut [u8], mask: usize) -> bool {
-    let mut modified = false;
-
-    // skip full bytes covered by prefix length
-    let mut p = mask / 8;
-    if p >= buf.len() {
-        return modified;
+/// Parses a truncated address into an array of octets.
+///
+/// The array is the full address and its length determines the address
+/// family. The parser must contain exactly the octets covered by the source
+/// prefix length. Missing octets are filled with zeros.
+fn parse_addr<Ref: AsRef<[u8]>, A: AsMut<[u8]> + Default>(
+    parser: &mut Parser<Ref>,
+    source_prefix_len: u8,
+    scope_prefix_len: u8,
+) -> Result<A, ParseError> {
+    let mut buf = A::default();
+    let max_len = buf.as_mut().len() * 8;
+    if usize::from(source_prefix_len) > max_len {
+        return Err(ParseError::form_error(ERR_SOURCE_LEN));
     }
-
-    // clear extra bits in a byte
-    let bits = mask % 8;
-    if bits != 0 {
-        if buf[p].trailing_zeros() < (8 - bits) as u32 {
-            buf[p] &= 0xff << (8 - bits);
-            modified = true;
-        }
-        p += 1;
+    if usize::from(scope_prefix_len) > max_len {
+        return Err(ParseError::form_error(ERR_SCOPE_LEN));
     }
-
-    // clear the rest bytes
-    while p < buf.len() {
-        if buf[p] != 0 {
-            buf[p] = 0;
-            modified = true;
-        }
-        p += 1;
-    }
-
-    modified
-}
-
-fn addr_apply_mask(addr: IpAddr, len: u8) -> (IpAddr, bool) {
-    match addr {
-        IpAddr::V4(a) => {
-            let mut array = a.octets();
-            let m = apply_bit_mask(&mut array, len as usize);
-            (array.into(), m)
-        }
-        IpAddr::V6(a) => {
-            let mut array = a.octets();
-            let m = apply_bit_mask(&mut array, len as usize);
-            (array.into(), m)
-        }
+    let prefix_bytes = prefix_bytes(usize::from(source_prefix_len));
+    if parser.remaining() != prefix_bytes {
+        return Err(ParseError::form_error(ERR_ADDR_LEN));
     }
-}
-
-fn normalize_prefix_len(addr: IpAddr, len: u8) -> u8 {
-    let max = match addr {
-        IpAddr::V4(_) => 32,
-        IpAddr::V6(_) => 128,
-    };
-
-    core::cmp::min(len, max)
+    parser.parse_buf(&mut buf.as_mut()[..prefix_bytes])?;
+    Ok(buf)
 }
 
 //--- CodeOptData
@@ -260,6 +236,76 @@ mod tests {
         };
     }
 
+    #[test]
+    fn try_new() {
+        let addr = "192.0.2.1".parse().unwrap();
+        let opt = ClientSubnet::try_new(24, 0, addr).unwrap();
+        assert_eq!(opt.addr(), "192.0.2.0".parse::<IpAddr>().unwrap());
+        let mut buf = Octets512::new();
+        opt.compose(&mut buf).unwrap();
+        assert_eq!(buf.as_slice(), b"\x00\x01\x18\x00\xc0\x00\x02");
+
+        assert_eq!(
+            ClientSubnet::try_new(33, 0, "192.0.2.0".parse().unwrap()),
+            Err(PrefixLenError)
+        );
+        assert_eq!(
+            ClientSubnet::try_new(24, 33, "192.0.2.0".parse().unwrap()),
+            Err(PrefixLenError)
+        );
+        assert!(
+            ClientSubnet::try_new(128, 0, "2001:db8::1".parse().unwrap())
+                .is_ok()
+        );
+        assert_eq!(
+            ClientSubnet::try_new(129, 0, "2001:db8::".parse().unwrap()),
+            Err(PrefixLenError)
+        );
+    }
+
+    #[test]
+    fn parse_truncated() {
+        fn parse(octets: &[u8]) -> Result<ClientSubnet, ParseError> {
+            crate::base::opt::parse_all(octets)
+        }
+
+        let opt = parse(b"\x00\x01\x18\x00\xc0\x00\x02").unwrap();
+        assert_eq!(opt.source_prefix_len(), 24);
+        assert_eq!(opt.addr(), "192.0.2.0".parse::<IpAddr>().unwrap());
+
+        let opt = parse(b"\x00\x02\x10\x30\x20\x01").unwrap();
+        assert_eq!(opt.source_prefix_len(), 16);
+        assert_eq!(opt.scope_prefix_len(), 48);
+        assert_eq!(opt.addr(), "2001::".parse::<IpAddr>().unwrap());
+
+        let opt = parse(b"\x00\x01\x00\x00").unwrap();
+        assert_eq!(opt.addr(), "0.0.0.0".parse::<IpAddr>().unwrap());
+
+        // Too few and too many address octets.
+        assert_eq!(
+            parse(b"\x00\x01\x18\x00\xc0\x00").unwrap_err(),
+            ParseError::form_error(ERR_ADDR_LEN)
+        );
+        assert_eq!(
+            parse(b"\x00\x01\x18\x00\xc0\x00\x02\x00").unwrap_err(),
+            ParseError::form_error(ERR_ADDR_LEN)
+        );
+        // Prefix lengths too long for IPv4.
+        assert_eq!(
+            parse(b"\x00\x01\x21\x00\xc0\x00\x02\x00\x00").unwrap_err(),
+            ParseError::form_error(ERR_SOURCE_LEN)
+        );
+        assert_eq!(
+            parse(b"\x00\x01\x18\x21\xc0\x00\x02").unwrap_err(),
+            ParseError::form_error(ERR_SCOPE_LEN)
+        );
+        // Bits set beyond the source prefix.
+        assert_eq!(
+            parse(b"\x00\x01\x17\x00\xc0\x00\x03").unwrap_err(),
+            ParseError::form_error(ERR_HOST_BITS)
+        );
+    }
+
     check!(prefix_at_boundary_v4, "192.0.2.0", 24, "192.0.2.0", true);
     check!(prefix_at_boundary_v6, "2001:db8::", 32, "2001:db8::", true);
     check!(prefix_no_truncation, "192.0.2.0", 23, "192.0.2.0", true);
@@ -1,5 +1,8 @@
 //! EDNS Options form RFC 7873
 
+use core::fmt;
+use rand::random;
+use super::super::cmp::constant_time_eq;
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
 use super::super::octets::{
@@ -10,12 +13,73 @@ use super::CodeOptData;
 
 //------------ Cookie --------------------------------------------------------
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct Cookie([u8; 8]);
+/// The DNS cookie option.
+///
+/// A DNS cookie consists of an eight octet client cookie optionally
+/// followed by a server cookie of between eight and 32 octets. A query sent
+/// to a server for the first time only contains the client cookie while
+/// later queries and all responses contain both.
+///
+/// Since the cookie is of limited size, the type keeps it in an array and is
+/// `Copy`.
+///
+/// Note that the `PartialEq` implementation compares cookies in the usual
+/// way and may therefore reveal through timing how many octets of two
+/// cookies are equal. When checking a cookie received from a peer, use
+/// [`verify`] instead which compares in constant time.
+///
+/// [`verify`]: #method.verify
+//
+//  The octets of `server` beyond `server_len` are always zero so that the
+//  derived impls only need to look at the used part.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Cookie {
+    /// The client cookie.
+    client: [u8; 8],
+
+    /// The server cookie in its first `server_len` octets.
+    server: [u8; 32],
+
+    /// The length of the server cookie, zero if there is none.
+    server_len: u8,
+}
 
 impl Cookie {
-    pub fn new(cookie: [u8; 8]) -> Self {
-        Cookie(cookie)
+    /// Creates a new cookie containing only a client cookie.
+    pub fn new(client: [u8; 8]) -> Self {
+        Cookie { client, server: [0; 32], server_len: 0 }
+    }
+
+    /// Creates a new cookie with both a client and a server cookie.
+    ///
+    /// The server cookie must be between 8 and 32 octets long. Otherwise
+    /// an error is returned.
+    pub fn with_server_cookie(
+        client: [u8; 8],
+        server: &[u8]
+    ) -> Result<Self, ServerCookieLenError> {
+        if !(8..=32).contains(&server.len()) {
+            return Err(ServerCookieLenError)
+        }
+        let mut res = Self::new(client);
+        res.server[..server.len()].copy_from_slice(server);
+        res.server_len = server.len() as u8;
+        Ok(res)
+    }
+
+    /// Creates a new cookie with a random client cookie.
+    ///
+    /// The client cookie is taken from the thread-local random number
+    /// generator of the `rand` crate which is cryptographically secure. A
+    /// stub resolver can add the cookie to an outgoing query and later
+    /// check that the response contains the same client cookie.
+    ///
+    /// RFC 7873 suggests deriving the client cookie from the client and
+    /// server addresses and a client secret so that it stays the same for
+    /// a given server. Use [`new`][Self::new] if you want to do that. Server
+    /// cookies are calculated by the server and are out of scope here.
+    pub fn generate_client() -> Self {
+        Self::new(random())
     }
 
     pub fn push<Target: OctetsBuilder>(
@@ -25,8 +89,38 @@ impl Cookie {
         builder.push(&Self::new(cookie))
     }
 
+    /// Returns the client cookie.
+    pub fn client_cookie(&self) -> [u8; 8] {
+        self.client
+    }
+
+    /// Returns the client cookie.
+    #[deprecated(since = "0.6.2", note = "use client_cookie instead")]
     pub fn cookie(self) -> [u8; 8] {
-        self.0
+        self.client_cookie()
+    }
+
+    /// Returns the server cookie if there is one.
+    pub fn server_cookie(&self) -> Option<&[u8]> {
+        if self.server_len == 0 {
+            None
+        }
+        else {
+            Some(&self.server[..usize::from(self.server_len)])
+        }
+    }
+
+    /// Returns whether two cookies are equal comparing in constant time.
+    ///
+    /// Both the client and server cookies are compared.
+    pub fn verify(&self, other: &Self) -> bool {
+        // Evaluate both to not reveal which part differs.
+        let client = constant_time_eq(&self.client, &other.client);
+        let server = constant_time_eq(
+            self.server_cookie().unwrap_or(b""),
+            other.server_cookie().unwrap_or(b""),
+        );
+        client & server
     }
 }
 
@@ -35,13 +129,21 @@ impl Cookie {
 
 impl<Ref: AsRef<[u8]>> Parse<Ref> for Cookie {
     fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
-        let mut res = [0u8; 8];
-        parser.parse_buf(&mut res[..])?;
-        Ok(Self::new(res))
+        let len = parser.remaining();
+        if len != 8 && !(16..=40).contains(&len) {
+            return Err(ParseError::form_error("invalid cookie length"))
+        }
+        let mut client = [0u8; 8];
+        parser.parse_buf(&mut client[..])?;
+        let mut res = Self::new(client);
+        parser.parse_buf(&mut res.server[..len - 8])?;
+        res.server_len = (len - 8) as u8;
+        Ok(res)
     }
 
     fn skip(parser: &mut Parser<Ref>) -> Result<(), ParseError> {
-        parser.advance(8)
+        parser.advance_to_end();
+        Ok(())
     }
 }
 
@@ -51,7 +153,10 @@ impl Compose for Cookie {
         &self,
         target: &mut T
     ) -> Result<(), ShortBuf> {
-        target.append_slice(&self.0[..])
+        target.append_all(|target| {
+            target.append_slice(&self.client[..])?;
+            target.append_slice(self.server_cookie().unwrap_or(b""))
+        })
     }
 }
 
@@ -62,3 +167,117 @@ impl CodeOptData for Cookie {
     const CODE: OptionCode = OptionCode::Cookie;
 }
 
+
+//--- Debug
+
+impl fmt::Debug for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cookie")
+            .field("client", &self.client)
+            .field("server", &self.server_cookie())
+            .finish()
+    }
+}
+
+
+//============ Error Types ===================================================
+
+//------------ ServerCookieLenError ------------------------------------------
+
+/// A server cookie was shorter than 8 or longer than 32 octets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ServerCookieLenError;
+
+impl fmt::Display for ServerCookieLenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid server cookie length")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ServerCookieLenError {}
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::super::octets::Octets64;
+
+    #[test]
+    fn verify() {
+        let cookie = Cookie::new(*b"abcdefgh");
+        let same = Cookie::new(*b"abcdefgh");
+        let other = Cookie::new(*b"abcdefgi");
+        assert_eq!(cookie.verify(&same), cookie == same);
+        assert!(cookie.verify(&same));
+        assert_eq!(cookie.verify(&other), cookie == other);
+        assert!(!cookie.verify(&other));
+    }
+
+    #[test]
+    fn parse_and_compose() {
+        fn parse(octets: &[u8]) -> Result<Cookie, ParseError> {
+            super::super::parse_all(octets)
+        }
+
+        fn compose(cookie: Cookie) -> Octets64 {
+            let mut buf = Octets64::new();
+            cookie.compose(&mut buf).unwrap();
+            buf
+        }
+
+        // Client cookie only.
+        let cookie = parse(b"abcdefgh").unwrap();
+        assert_eq!(cookie, Cookie::new(*b"abcdefgh"));
+        assert_eq!(cookie.client_cookie(), *b"abcdefgh");
+        assert_eq!(cookie.server_cookie(), None);
+        #[allow(deprecated)]
+        {
+            assert_eq!(cookie.cookie(), *b"abcdefgh");
+        }
+        assert_eq!(compose(cookie).as_slice(), b"abcdefgh");
+
+        // Client cookie and a 16 octet server cookie.
+        let wire = b"abcdefgh0123456789abcdef";
+        let cookie = parse(wire).unwrap();
+        assert_eq!(
+            cookie,
+            Cookie::with_server_cookie(*b"abcdefgh", b"0123456789abcdef")
+                .unwrap()
+        );
+        assert_eq!(cookie.client_cookie(), *b"abcdefgh");
+        assert_eq!(cookie.server_cookie(), Some(&b"0123456789abcdef"[..]));
+        assert_eq!(compose(cookie).as_slice(), wire);
+
+        // Invalid lengths.
+        assert!(parse(b"abcdefgh01").is_err());
+        assert!(parse(b"abcdefg").is_err());
+        assert!(parse(&[0; 41]).is_err());
+        assert!(Cookie::with_server_cookie(*b"abcdefgh", b"0123").is_err());
+        assert!(
+            Cookie::with_server_cookie(*b"abcdefgh", &[0; 33]).is_err()
+        );
+    }
+
+    #[test]
+    fn generate_client() {
+        let cookie = Cookie::generate_client();
+        assert_eq!(cookie.server_cookie(), None);
+        assert_ne!(cookie, Cookie::generate_client());
+    }
+
+    #[test]
+    fn verify_server_cookie() {
+        let cookie = Cookie::with_server_cookie(
+            *b"abcdefgh", b"01234567"
+        ).unwrap();
+        let other = Cookie::with_server_cookie(
+            *b"abcdefgh", b"01234568"
+        ).unwrap();
+        assert!(cookie.verify(&cookie));
+        assert!(!cookie.verify(&other));
+        assert!(!cookie.verify(&Cookie::new(*b"abcdefgh")));
+    }
+}
@@ -1,5 +1,6 @@
 //! EDNS Options from RFC 7901
 
+use core::fmt;
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
 use super::super::name::{Dname, ToDname};
@@ -11,6 +12,13 @@ use super::CodeOptData;
 
 //------------ Chain --------------------------------------------------------
 
+/// The CHAIN option.
+///
+/// The option contains the closest trust point, i.e., the name of the
+/// closest DNSKEY RRset the client already has validated, as an absolute,
+/// uncompressed domain name. When parsing, the option data must consist of
+/// exactly this name.
+//
 // TODO Impl more traits. We can’t derive them because that would force
 //      trait boundaries on Octets.
 #[derive(Clone)]
@@ -37,6 +45,7 @@ impl<Octets> Chain<Octets> {
         })
     }
 
+    /// Returns the closest trust point.
     pub fn start(&self) -> &Dname<Octets> {
         &self.start
     }
@@ -65,9 +74,60 @@ impl<Octets: AsRef<[u8]>> Compose for Chain<Octets> {
 }
 
 
+//--- Debug
+
+impl<Octets: AsRef<[u8]>> fmt::Debug for Chain<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Chain").field("start", &self.start).finish()
+    }
+}
+
+
 //--- CodeOptData
 
 impl<Octets> CodeOptData for Chain<Octets> {
     const CODE: OptionCode = OptionCode::Chain;
 }
 
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use core::str::FromStr;
+    use std::format;
+    use std::vec::Vec;
+
+    #[test]
+    fn debug() {
+        let chain = Chain::new(
+            Dname::<Vec<u8>>::from_str("example.com").unwrap()
+        );
+        assert_eq!(
+            format!("{:?}", chain),
+            "Chain { start: Dname(example.com.) }"
+        );
+    }
+
+    #[test]
+    fn push_and_parse() {
+        use crate::base::opt::{opt_with, Opt};
+
+        let name = Dname::<Vec<u8>>::from_str("example.com").unwrap();
+        let msg = opt_with(|opt| Chain::<Vec<u8>>::push(opt, &name));
+        let opt = msg.opt().unwrap();
+        assert_eq!(
+            *opt.first::<Chain<_>>().unwrap().unwrap().start(), name
+        );
+
+        // The option must contain exactly one name.
+        let opt = Opt::from_octets(
+            &b"\x00\x0d\x00\x0f\x07example\x03com\x00xx"[..]
+        ).unwrap();
+        assert!(opt.first::<Chain<_>>().unwrap().is_err());
+        let opt = Opt::from_octets(&b"\x00\x0d\x00\x04\x03com"[..]).unwrap();
+        assert!(opt.first::<Chain<_>>().unwrap().is_err());
+    }
+}
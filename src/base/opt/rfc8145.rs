@@ -1,5 +1,6 @@
 //! EDNS Options from RFC 8145.
 
+use core::fmt;
 use core::convert::TryInto;
 use super::super::iana::OptionCode;
 use super::super::message_builder::OptBuilder;
@@ -12,7 +13,12 @@ use super::CodeOptData;
 
 //------------ KeyTag -------------------------------------------------------
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+/// The edns-key-tag option.
+///
+/// The option contains a sequence of 16 bit key tags of the DNSKEY records
+/// a validating resolver is using as trust anchors. When parsing, the
+/// option data must have an even length.
+#[derive(Clone, Eq, Hash, PartialEq)]
 pub struct KeyTag<Octets> {
     octets: Octets,
 }
@@ -38,6 +44,7 @@ impl<Octets> KeyTag<Octets> {
         })
     }
 
+    /// Returns an iterator over the key tags.
     pub fn iter(&self) -> KeyTagIter
     where Octets: AsRef<[u8]> {
         KeyTagIter(self.octets.as_ref())
@@ -86,6 +93,15 @@ impl<Octets> CodeOptData for KeyTag<Octets> {
 }
 
 
+//--- Debug
+
+impl<Octets: AsRef<[u8]>> fmt::Debug for KeyTag<Octets> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("KeyTag").field(&self.iter()).finish()
+    }
+}
+
+
 //--- IntoIterator
 
 impl<'a, Octets: AsRef<[u8]>> IntoIterator for &'a KeyTag<Octets> {
@@ -100,7 +116,8 @@ impl<'a, Octets: AsRef<[u8]>> IntoIterator for &'a KeyTag<Octets> {
 
 //------------ KeyTagIter ----------------------------------------------------
 
-#[derive(Clone, Copy, Debug)]
+/// An iterator over the key tags of a [`KeyTag`] option.
+#[derive(Clone, Copy)]
 pub struct KeyTagIter<'a>(&'a [u8]);
 
 impl<'a> Iterator for KeyTagIter<'a> {
@@ -118,3 +135,43 @@ impl<'a> Iterator for KeyTagIter<'a> {
     }
 }
 
+impl<'a> fmt::Debug for KeyTagIter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use std::format;
+    use std::vec::Vec;
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", KeyTag::new(b"\x4f\x66\x00\x14".as_ref())),
+            "KeyTag([20326, 20])"
+        );
+    }
+
+    #[test]
+    fn push_and_parse() {
+        use crate::base::opt::{opt_with, Opt};
+
+        let msg = opt_with(|opt| KeyTag::<()>::push(opt, &[12345, 65535]));
+        let opt = msg.opt().unwrap();
+        let tags = opt.first::<KeyTag<_>>().unwrap().unwrap();
+        assert_eq!(tags.iter().collect::<Vec<_>>(), [12345, 65535]);
+        assert_eq!((&tags).into_iter().count(), 2);
+
+        // Odd lengths are rejected.
+        let opt = Opt::from_octets(&b"\x00\x0e\x00\x03\x30\x39\xff"[..])
+            .unwrap();
+        assert!(opt.first::<KeyTag<_>>().unwrap().is_err());
+    }
+}
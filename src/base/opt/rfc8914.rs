@@ -5,6 +5,7 @@ use super::super::{
         exterr::{ExtendedErrorCode, EDE_PRIVATE_RANGE_BEGIN},
         OptionCode,
     },
+    message_builder::OptBuilder,
     octets::{OctetsBuilder, OctetsRef, Parse, ParseError},
     opt::CodeOptData,
     Compose, Parser, ShortBuf,
@@ -47,6 +48,29 @@ impl<Octets: AsRef<[u8]>> ExtendedError<Octets> {
     }
 }
 
+impl ExtendedError<()> {
+    /// Appends an extended error option to an OPT record.
+    ///
+    /// The option will contain the given `code` and, if present, `text`
+    /// as its EXTRA-TEXT field.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `text` is longer than 65,533 octets.
+    pub fn push<Target: OctetsBuilder>(
+        builder: &mut OptBuilder<Target>,
+        code: ExtendedErrorCode,
+        text: Option<&str>,
+    ) -> Result<(), ShortBuf> {
+        let text = text.unwrap_or("").as_bytes();
+        assert!(text.len() <= usize::from(u16::MAX) - 2);
+        builder.push_raw_option(OptionCode::ExtendedError, |target| {
+            code.to_int().compose(target)?;
+            target.append_slice(text)
+        })
+    }
+}
+
 impl<Octets> From<ExtendedErrorCode> for ExtendedError<Octets> {
     fn from(code: ExtendedErrorCode) -> Self {
         Self { code, text: None }
@@ -134,6 +158,26 @@ mod tests {
         assert_eq!(ede.text, parsed.text);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn push() {
+        let msg = super::super::opt_with(|opt| {
+            ExtendedError::push(
+                opt, ExtendedErrorCode::Blocked, Some("by policy")
+            )?;
+            ExtendedError::push(opt, ExtendedErrorCode::StaleAnswer, None)
+        });
+        let opt = msg.opt().unwrap();
+        let mut iter = opt.iter::<ExtendedError<_>>();
+        let ede = iter.next().unwrap().unwrap();
+        assert_eq!(ede.code(), ExtendedErrorCode::Blocked);
+        assert_eq!(ede.text().map(AsRef::as_ref), Some(b"by policy".as_ref()));
+        let ede = iter.next().unwrap().unwrap();
+        assert_eq!(ede.code(), ExtendedErrorCode::StaleAnswer);
+        assert!(ede.text().is_none());
+        assert!(iter.next().is_none());
+    }
+
     #[test]
     fn private() {
         let ede: ExtendedError<&[u8]> = ExtendedErrorCode::DnssecBogus.into();
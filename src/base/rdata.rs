@@ -83,6 +83,25 @@ pub trait ParseRecordData<Ref>: RecordData {
         rtype: Rtype,
         parser: &mut Parser<Ref>,
     ) -> Result<Option<Self>, ParseError>;
+
+    /// Parses the empty record data of a record with class ANY or NONE.
+    ///
+    /// In UPDATE messages, records of these classes have special meaning
+    /// and may have empty record data even if their type doesn’t allow
+    /// that. For instance, a record with class ANY and empty data asks to
+    /// delete a complete RRset. If parsing such data via
+    /// [`parse_data`][Self::parse_data] fails, this method is called
+    /// instead.
+    ///
+    /// The default implementation returns `Ok(None)`, i.e., the record is
+    /// skipped.
+    fn parse_empty_data(
+        rtype: Rtype,
+        parser: &mut Parser<Ref>,
+    ) -> Result<Option<Self>, ParseError> {
+        let _ = (rtype, parser);
+        Ok(None)
+    }
 }
 
 //------------ RtypeRecordData -----------------------------------------------
@@ -201,7 +220,7 @@ impl UnknownRecordData<Bytes> {
                         return Err(SyntaxError::LongGenericData);
                     }
                     let ch = symbol.into_digit(16)? as u8;
-                    if let Some(ch1) = *first {
+                    if let Some(ch1) = first.take() {
                         res.put_u8(ch1 << 4 | ch);
                         *len -= 1;
                     } else {
@@ -320,6 +339,13 @@ where
             .parse_octets(rdlen)
             .map(|data| Some(Self::from_octets(rtype, data)))
     }
+
+    fn parse_empty_data(
+        rtype: Rtype,
+        parser: &mut Parser<Ref>,
+    ) -> Result<Option<Self>, ParseError> {
+        Self::parse_data(rtype, parser)
+    }
 }
 
 //--- Display
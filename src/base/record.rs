@@ -24,7 +24,7 @@ use super::octets::{
 };
 use super::rdata::{ParseRecordData, RecordData};
 use core::cmp::Ordering;
-use core::{fmt, hash};
+use core::{cmp, fmt, hash};
 
 //------------ Record --------------------------------------------------------
 
@@ -609,6 +609,12 @@ impl<Ref: OctetsRef> RecordHeader<ParsedDname<Ref>> {
     /// feels capable of parsing a record with a header of `self`, the
     /// method will parse the data and return a full `Record<D>`. Otherwise,
     /// it skips over the record data.
+    ///
+    /// Records with class ANY or NONE and empty record data are used in
+    /// UPDATE messages. If the record data type fails to parse such empty
+    /// data, [`ParseRecordData::parse_empty_data`] is used instead.
+    ///
+    /// [`ParseRecordData::parse_empty_data`]: ../rdata/trait.ParseRecordData.html#method.parse_empty_data
     pub fn parse_into_record<Data>(
         self,
         parser: &mut Parser<Ref>,
@@ -616,8 +622,14 @@ impl<Ref: OctetsRef> RecordHeader<ParsedDname<Ref>> {
     where
         Data: ParseRecordData<Ref>,
     {
+        let meta = self.rdlen == 0
+            && (self.class == Class::Any || self.class == Class::None);
         parser.parse_block(self.rdlen as usize, |parser| {
-            match Data::parse_data(self.rtype, parser)? {
+            let data = match Data::parse_data(self.rtype, parser) {
+                Err(_) if meta => Data::parse_empty_data(self.rtype, parser),
+                res => res,
+            };
+            match data? {
                 Some(data) => Ok(Some(Record::new(
                     self.owner, self.class, self.ttl, data,
                 ))),
@@ -874,6 +886,18 @@ impl<Ref> ParsedRecord<Ref> {
 }
 
 impl<Ref: OctetsRef> ParsedRecord<Ref> {
+    /// Returns the raw record data.
+    ///
+    /// The returned slice is borrowed from the underlying octets sequence
+    /// without parsing the record data at all. This is useful for quickly
+    /// inspecting records. Note, however, that the data may contain
+    /// compressed domain names which can only be resolved with the help of
+    /// the complete message.
+    pub fn data_bytes(&self) -> &[u8] {
+        let data = self.data.peek_all();
+        &data[..cmp::min(data.len(), usize::from(self.rdlen()))]
+    }
+
     /// Creates a real resource record from the parsed record.
     ///
     /// The method is generic over a type that knows how to parse record
@@ -994,10 +1018,62 @@ impl<N, D> From<ShortBuf> for RecordParseError<N, D> {
     }
 }
 
+impl<N, D> From<RecordParseError<N, D>> for ParseError
+where
+    N: Into<ParseError>,
+    D: Into<ParseError>,
+{
+    fn from(err: RecordParseError<N, D>) -> Self {
+        match err {
+            RecordParseError::Name(err) => err.into(),
+            RecordParseError::Data(err) => err.into(),
+            RecordParseError::ShortBuf => ParseError::ShortInput,
+        }
+    }
+}
+
 //============ Testing ======================================================
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn parsed_record_data_bytes() {
+        use crate::base::message::Message;
+        use crate::base::message_builder::MessageBuilder;
+        use crate::base::name::Dname;
+        use crate::rdata::{Aaaa, A};
+        use std::vec::Vec;
+
+        let name = Dname::vec_from_str("example.com").unwrap();
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push((&name, 3600, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        msg.push((&name, 3600, Aaaa::new("2001:db8::1".parse().unwrap())))
+            .unwrap();
+        msg.push((&name, 3600, A::from_octets(192, 0, 2, 2)))
+            .unwrap();
+        msg.push((&name, 3600, Aaaa::new("2001:db8::2".parse().unwrap())))
+            .unwrap();
+        let msg = Message::from_octets(msg.finish()).unwrap();
+
+        let aaaa: Vec<_> = msg
+            .answer()
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|record| record.rtype() == Rtype::Aaaa)
+            .map(|record| record.data_bytes().to_vec())
+            .collect();
+        assert_eq!(
+            aaaa,
+            [
+                b"\x20\x01\x0d\xb8\0\0\0\0\0\0\0\0\0\0\0\x01",
+                b"\x20\x01\x0d\xb8\0\0\0\0\0\0\0\0\0\0\0\x02"
+            ]
+        );
+    }
 
     #[test]
     #[cfg(features = "bytes")]
@@ -1018,4 +1094,49 @@ mod test {
         assert_eq!(ds.owner(), ds_bytes.owner());
         asswer_eq!(ds.data().digest(), ds_bytes.data().digest());
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn parse_empty_meta_data() {
+        use crate::base::message::Message;
+        use crate::base::message_builder::MessageBuilder;
+        use crate::base::name::Dname;
+        use crate::base::rdata::UnknownRecordData;
+        use crate::rdata::{AllRecordData, A};
+        use std::vec::Vec;
+
+        let name = Dname::vec_from_str("www.example.com").unwrap();
+        let mut msg = MessageBuilder::new_vec().answer();
+        for class in &[Class::Any, Class::None, Class::In] {
+            msg.push(Record::new(
+                &name,
+                *class,
+                0,
+                UnknownRecordData::from_octets(Rtype::A, Vec::new()),
+            ))
+            .unwrap();
+        }
+        let msg = Message::from_octets(msg.finish()).unwrap();
+
+        // Delete RRset and delete RR with class ANY and NONE parse into
+        // unknown data. An empty A record with class IN is an error.
+        let mut answer =
+            msg.answer().unwrap().limit_to::<AllRecordData<_, _>>();
+        for class in &[Class::Any, Class::None] {
+            let record = answer.next().unwrap().unwrap();
+            assert_eq!(record.class(), *class);
+            match *record.data() {
+                AllRecordData::Other(ref data) => {
+                    assert_eq!(data.rtype(), Rtype::A);
+                    assert!(data.data().is_empty());
+                }
+                ref other => panic!("expected unknown data, got {:?}", other),
+            }
+        }
+        assert!(answer.next().unwrap().is_err());
+
+        // Types that can’t represent empty data skip the records.
+        let mut answer = msg.answer().unwrap().limit_to::<A>();
+        assert!(answer.next().unwrap().is_err());
+    }
 }
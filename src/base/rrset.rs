@@ -0,0 +1,509 @@
+//! Resource record sets.
+//!
+//! A resource record set, or RRset for short, is the set of all records
+//! sharing the same owner name, class, and record type. Many operations in
+//! the DNS are defined on RRsets rather than individual records. This
+//! module provides the [`Rrset`] type for such a set and the [`Rrsets`]
+//! iterator adapter that collects a sequence of records into RRsets.
+//! Finally, [`ZoneDiff`] determines the RRsets that differ between two
+//! versions of a zone.
+//!
+//! [`Rrset`]: struct.Rrset.html
+//! [`Rrsets`]: struct.Rrsets.html
+//! [`ZoneDiff`]: struct.ZoneDiff.html
+
+use super::cmp::CanonicalOrd;
+use super::iana::{Class, Rtype};
+use super::name::ToDname;
+use super::rdata::RecordData;
+use super::record::Record;
+use core::cmp::Ordering;
+use core::iter::Peekable;
+use core::slice;
+use std::vec::{self, Vec};
+
+//------------ Rrset ---------------------------------------------------------
+
+/// A set of records with the same owner name, class, and record type.
+///
+/// An RRset always contains at least one record. Owner name, class, and
+/// record type are taken from this first record. Since the records of a
+/// set are supposed to have the same TTL, the TTL of the first record is
+/// used as the TTL of the set.
+#[derive(Clone, Debug)]
+pub struct Rrset<N, D> {
+    records: Vec<Record<N, D>>,
+}
+
+impl<N, D> Rrset<N, D> {
+    /// Creates a new RRset from its first record.
+    pub fn new(record: Record<N, D>) -> Self {
+        Rrset {
+            records: vec![record],
+        }
+    }
+
+    /// Returns a reference to the owner name of the set.
+    pub fn owner(&self) -> &N {
+        self.first().owner()
+    }
+
+    /// Returns the class of the set.
+    pub fn class(&self) -> Class {
+        self.first().class()
+    }
+
+    /// Returns the record type of the set.
+    pub fn rtype(&self) -> Rtype
+    where
+        D: RecordData,
+    {
+        self.first().rtype()
+    }
+
+    /// Returns the TTL of the set.
+    pub fn ttl(&self) -> u32 {
+        self.first().ttl()
+    }
+
+    /// Returns the smallest TTL of all the records in the set.
+    pub fn min_ttl(&self) -> u32 {
+        self.records.iter().map(Record::ttl).min().unwrap_or(0)
+    }
+
+    /// Sets the TTL of all records in the set to the smallest TTL.
+    ///
+    /// RFC 2181 requires all records of an RRset to have the same TTL and
+    /// recommends treating sets that don’t as if all records had the
+    /// smallest of the TTLs. This method does exactly that and should be
+    /// used before caching or signing a set.
+    pub fn normalize_ttl(&mut self) {
+        let ttl = self.min_ttl();
+        for record in &mut self.records {
+            record.set_ttl(ttl)
+        }
+    }
+
+    /// Returns the number of records in the set.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns whether the set is empty.
+    ///
+    /// Since a set always contains at least one record, this always
+    /// returns `false`.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns a reference to the first record of the set.
+    pub fn first(&self) -> &Record<N, D> {
+        &self.records[0]
+    }
+
+    /// Returns a slice of all the records of the set.
+    pub fn records(&self) -> &[Record<N, D>] {
+        &self.records
+    }
+
+    /// Returns an iterator over the record data of the set.
+    pub fn iter(&self) -> RrsetData<'_, N, D> {
+        RrsetData {
+            records: self.records.iter(),
+        }
+    }
+
+    /// Converts the set into a vec of its records.
+    pub fn into_records(self) -> Vec<Record<N, D>> {
+        self.records
+    }
+}
+
+impl<N: ToDname, D: RecordData> Rrset<N, D> {
+    /// Returns whether a record belongs to this set.
+    pub fn is_member<NN: ToDname, DD: RecordData>(
+        &self,
+        record: &Record<NN, DD>,
+    ) -> bool {
+        record.owner().name_eq(self.owner())
+            && record.class() == self.class()
+            && record.rtype() == self.rtype()
+    }
+
+    /// Adds a record to the set.
+    ///
+    /// If the record doesn’t belong to the set, it is returned as an error.
+    pub fn push(&mut self, record: Record<N, D>) -> Result<(), Record<N, D>> {
+        if self.is_member(&record) {
+            self.records.push(record);
+            Ok(())
+        } else {
+            Err(record)
+        }
+    }
+}
+
+//--- IntoIterator
+
+impl<'a, N, D> IntoIterator for &'a Rrset<N, D> {
+    type Item = &'a D;
+    type IntoIter = RrsetData<'a, N, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//------------ RrsetData -----------------------------------------------------
+
+/// An iterator over the record data of an RRset.
+#[derive(Clone, Debug)]
+pub struct RrsetData<'a, N, D> {
+    records: slice::Iter<'a, Record<N, D>>,
+}
+
+impl<'a, N, D> Iterator for RrsetData<'a, N, D> {
+    type Item = &'a D;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records.next().map(Record::data)
+    }
+}
+
+//------------ Rrsets --------------------------------------------------------
+
+/// An iterator adapter that groups records into RRsets.
+///
+/// The adapter expects the records of each RRset to be next to each other
+/// as is the case in a well-formed message. It starts a new RRset whenever
+/// a record doesn’t belong to the current one. If the records of an RRset
+/// are spread out, you will therefore receive more than one RRset for the
+/// same owner, class, and type. Use [`sorted`] if you can’t be sure that
+/// records are properly grouped.
+///
+/// [`sorted`]: #method.sorted
+pub struct Rrsets<Iter: Iterator> {
+    iter: Peekable<Iter>,
+}
+
+impl<Iter: Iterator> Rrsets<Iter> {
+    /// Creates a new adapter for an iterator over grouped records.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<IntoIter = Iter>,
+    {
+        Rrsets {
+            iter: iter.into_iter().peekable(),
+        }
+    }
+}
+
+impl<N, D> Rrsets<vec::IntoIter<Record<N, D>>>
+where
+    N: ToDname,
+    D: RecordData,
+{
+    /// Creates a new adapter for records in arbitrary order.
+    ///
+    /// The records are collected and sorted by owner name, class, and
+    /// record type first. Within each RRset, the original order of the
+    /// records is preserved.
+    pub fn sorted<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Record<N, D>>,
+    {
+        let mut records: Vec<_> = iter.into_iter().collect();
+        records.sort_by(|left, right| {
+            left.owner()
+                .name_cmp(right.owner())
+                .then(left.class().cmp(&right.class()))
+                .then(left.rtype().cmp(&right.rtype()))
+        });
+        Self::new(records)
+    }
+}
+
+impl<Iter, N, D> Iterator for Rrsets<Iter>
+where
+    Iter: Iterator<Item = Record<N, D>>,
+    N: ToDname,
+    D: RecordData,
+{
+    type Item = Rrset<N, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut res = Rrset::new(self.iter.next()?);
+        while let Some(record) = self.iter.peek() {
+            if !res.is_member(record) {
+                break;
+            }
+            if let Some(record) = self.iter.next() {
+                res.records.push(record)
+            }
+        }
+        Some(res)
+    }
+}
+
+//------------ ZoneDiff ------------------------------------------------------
+
+/// The differences between two versions of a zone.
+///
+/// The diff is determined RRset by RRset: An RRset is added if there was
+/// no RRset with the same owner, class, and type in the old version. It is
+/// removed if there is no such RRset in the new version anymore. It is
+/// changed if both versions contain the RRset but its records or its TTL
+/// differ. Records are compared using their canonical order, so the order
+/// of records within a set and duplicate records don’t matter.
+#[derive(Clone, Debug)]
+pub struct ZoneDiff<N, D> {
+    /// The RRsets only present in the new version.
+    added: Vec<Rrset<N, D>>,
+
+    /// The RRsets only present in the old version.
+    removed: Vec<Rrset<N, D>>,
+
+    /// The RRsets present in both versions but with different content.
+    changed: Vec<RrsetChange<N, D>>,
+}
+
+/// The old and new version of a changed RRset.
+pub type RrsetChange<N, D> = (Rrset<N, D>, Rrset<N, D>);
+
+impl<N, D> ZoneDiff<N, D>
+where
+    N: ToDname,
+    D: RecordData + CanonicalOrd,
+{
+    /// Determines the differences between two versions of a zone.
+    ///
+    /// The records can be given in any order.
+    pub fn new<Old, New>(old: Old, new: New) -> Self
+    where
+        Old: IntoIterator<Item = Record<N, D>>,
+        New: IntoIterator<Item = Record<N, D>>,
+    {
+        let mut res = ZoneDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        let mut old = Rrsets::new(Self::canonical_records(old)).peekable();
+        let mut new = Rrsets::new(Self::canonical_records(new)).peekable();
+        loop {
+            let order = match (old.peek(), new.peek()) {
+                (Some(old), Some(new)) => Self::rrset_cmp(old, new),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match order {
+                Ordering::Less => res.removed.extend(old.next()),
+                Ordering::Greater => res.added.extend(new.next()),
+                Ordering::Equal => {
+                    if let (Some(old), Some(new)) = (old.next(), new.next()) {
+                        if !Self::rrset_eq(&old, &new) {
+                            res.changed.push((old, new))
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Collects records into canonical order without duplicates.
+    fn canonical_records<I>(iter: I) -> Vec<Record<N, D>>
+    where
+        I: IntoIterator<Item = Record<N, D>>,
+    {
+        let mut res: Vec<_> = iter.into_iter().collect();
+        res.sort_by(CanonicalOrd::canonical_cmp);
+        res.dedup_by(|left, right| {
+            left.canonical_cmp(right) == Ordering::Equal
+        });
+        res
+    }
+
+    /// Compares owner, class, and type of two sets in canonical order.
+    fn rrset_cmp(left: &Rrset<N, D>, right: &Rrset<N, D>) -> Ordering {
+        left.class()
+            .cmp(&right.class())
+            .then(left.owner().name_cmp(right.owner()))
+            .then(left.rtype().cmp(&right.rtype()))
+    }
+
+    /// Compares TTL and the records of two sets in canonical order.
+    fn rrset_eq(left: &Rrset<N, D>, right: &Rrset<N, D>) -> bool {
+        left.ttl() == right.ttl()
+            && left.len() == right.len()
+            && left.iter().zip(right.iter()).all(|(left, right)| {
+                left.canonical_cmp(right) == Ordering::Equal
+            })
+    }
+}
+
+impl<N, D> ZoneDiff<N, D> {
+    /// Returns the RRsets only present in the new version.
+    pub fn added(&self) -> &[Rrset<N, D>] {
+        &self.added
+    }
+
+    /// Returns the RRsets only present in the old version.
+    pub fn removed(&self) -> &[Rrset<N, D>] {
+        &self.removed
+    }
+
+    /// Returns the RRsets whose content has changed.
+    ///
+    /// Each item is a pair of the old and new version of the RRset.
+    pub fn changed(&self) -> &[RrsetChange<N, D>] {
+        &self.changed
+    }
+
+    /// Returns whether the two versions of the zone are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::name::Dname;
+    use crate::rdata::{Aaaa, AllRecordData, A};
+    use core::str::FromStr;
+
+    type TestRecord =
+        Record<Dname<Vec<u8>>, AllRecordData<Vec<u8>, Dname<Vec<u8>>>>;
+
+    fn a(owner: &str, addr: &str) -> TestRecord {
+        Record::new(
+            Dname::from_str(owner).unwrap(),
+            Class::In,
+            3600,
+            A::from_str(addr).unwrap().into(),
+        )
+    }
+
+    fn aaaa(owner: &str, addr: &str) -> TestRecord {
+        Record::new(
+            Dname::from_str(owner).unwrap(),
+            Class::In,
+            3600,
+            Aaaa::from_str(addr).unwrap().into(),
+        )
+    }
+
+    #[test]
+    fn group_rrsets() {
+        let records = vec![
+            a("example.com", "192.0.2.1"),
+            a("EXAMPLE.com", "192.0.2.2"),
+            aaaa("example.com", "2001:db8::1"),
+        ];
+        let rrsets: Vec<_> = Rrsets::new(records).collect();
+        assert_eq!(rrsets.len(), 2);
+        assert_eq!(rrsets[0].rtype(), Rtype::A);
+        assert_eq!(rrsets[0].len(), 2);
+        assert_eq!(rrsets[0].iter().count(), 2);
+        assert_eq!(rrsets[1].rtype(), Rtype::Aaaa);
+        assert_eq!(rrsets[1].len(), 1);
+        assert_eq!(rrsets[1].class(), Class::In);
+        assert_eq!(rrsets[1].ttl(), 3600);
+        assert_eq!(
+            rrsets[1].owner(),
+            &Dname::<Vec<u8>>::from_str("example.com").unwrap()
+        );
+    }
+
+    #[test]
+    fn min_ttl() {
+        let mut rrset = Rrset::new(a("example.com", "192.0.2.1"));
+        rrset.push(a("example.com", "192.0.2.2")).unwrap();
+        rrset.push(a("example.com", "192.0.2.3")).unwrap();
+        for (record, ttl) in rrset.records.iter_mut().zip(&[300, 60, 3600]) {
+            record.set_ttl(*ttl)
+        }
+        assert_eq!(rrset.ttl(), 300);
+        assert_eq!(rrset.min_ttl(), 60);
+        rrset.normalize_ttl();
+        assert_eq!(rrset.ttl(), 60);
+        assert!(rrset.records().iter().all(|record| record.ttl() == 60));
+    }
+
+    #[test]
+    fn zone_diff() {
+        fn rec(owner: &str, addr: &str) -> Record<Dname<Vec<u8>>, A> {
+            Record::new(
+                Dname::from_str(owner).unwrap(),
+                Class::In,
+                3600,
+                A::from_str(addr).unwrap(),
+            )
+        }
+
+        let old = vec![
+            rec("www.example.com", "192.0.2.1"),
+            rec("www.example.com", "192.0.2.2"),
+            rec("mail.example.com", "192.0.2.3"),
+            rec("old.example.com", "192.0.2.5"),
+        ];
+        let new = vec![
+            rec("mail.example.com", "192.0.2.3"),
+            rec("www.example.com", "192.0.2.4"),
+            rec("www.example.com", "192.0.2.1"),
+            rec("new.example.com", "192.0.2.5"),
+        ];
+        assert!(ZoneDiff::new(old.clone(), old.clone()).is_empty());
+
+        let diff = ZoneDiff::new(old, new);
+        assert_eq!(diff.added().len(), 1);
+        assert_eq!(
+            diff.added()[0].owner(),
+            &Dname::<Vec<u8>>::from_str("new.example.com").unwrap()
+        );
+        assert_eq!(diff.removed().len(), 1);
+        assert_eq!(
+            diff.removed()[0].owner(),
+            &Dname::<Vec<u8>>::from_str("old.example.com").unwrap()
+        );
+        assert_eq!(diff.changed().len(), 1);
+        let (old, new) = &diff.changed()[0];
+        assert_eq!(
+            old.owner(),
+            &Dname::<Vec<u8>>::from_str("www.example.com").unwrap()
+        );
+        assert_eq!(old.len(), 2);
+        assert_eq!(new.len(), 2);
+        assert_eq!(
+            new.records()[1].data(),
+            &A::from_str("192.0.2.4").unwrap()
+        );
+    }
+
+    #[test]
+    fn group_sorted_rrsets() {
+        let records = vec![
+            a("example.com", "192.0.2.1"),
+            aaaa("example.com", "2001:db8::1"),
+            a("example.com", "192.0.2.2"),
+        ];
+        assert_eq!(Rrsets::new(records.clone()).count(), 3);
+
+        let rrsets: Vec<_> = Rrsets::sorted(records).collect();
+        assert_eq!(rrsets.len(), 2);
+        assert_eq!(rrsets[0].rtype(), Rtype::A);
+        let data: Vec<AllRecordData<Vec<u8>, Dname<Vec<u8>>>> = vec![
+            A::from_str("192.0.2.1").unwrap().into(),
+            A::from_str("192.0.2.2").unwrap().into(),
+        ];
+        assert_eq!(rrsets[0].iter().cloned().collect::<Vec<_>>(), data);
+        assert_eq!(rrsets[1].rtype(), Rtype::Aaaa);
+    }
+}
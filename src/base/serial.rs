@@ -3,9 +3,12 @@
 //! DNS uses 32 bit serial numbers in various places that are conceptionally
 //! viewed as the 32 bit modulus of a larger number space. Because of that,
 //! special rules apply when processing these values. This module provides
-//! the type [`Serial`] that implements these rules.
+//! the type [`Serial`] that implements these rules as well as the type
+//! [`Timestamp`] for the serial number based points in time used by RRSIG
+//! records.
 //!
 //! [`Serial`]: struct.Serial.html
+//! [`Timestamp`]: struct.Timestamp.html
 
 use super::cmp::CanonicalOrd;
 use super::octets::{
@@ -16,7 +19,7 @@ use crate::master::scan::{
     CharSource, Scan, ScanError, Scanner, SyntaxError,
 };
 #[cfg(feature = "chrono")]
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, TimeZone};
 use core::cmp::Ordering;
 use core::{cmp, fmt, str};
 #[cfg(feature = "std")]
@@ -89,77 +92,14 @@ impl Serial {
     /// serial values. Their master file format can either be the signature
     /// value or a specific date in `YYYYMMDDHHmmSS` format.
     ///
+    /// This is the same as scanning a [`Timestamp`] and converting it.
+    ///
     /// [RRSIG]: ../../rdata/rfc4034/struct.Rrsig.html
     #[cfg(feature = "master")]
     pub fn scan_rrsig<C: CharSource>(
         scanner: &mut Scanner<C>,
     ) -> Result<Self, ScanError> {
-        scanner.scan_phrase(
-            (0, [0u8; 14]),
-            |&mut (ref mut pos, ref mut buf), symbol| {
-                let ch = symbol.into_digit(10)? as u8;
-                if *pos == 14 {
-                    return Err(SyntaxError::IllegalInteger); // XXX Not quite
-                }
-                buf[*pos] = ch;
-                *pos += 1;
-                Ok(())
-            },
-            |(pos, buf)| {
-                if pos <= 10 {
-                    // We have an integer. We generate it into a u64 to deal
-                    // with possible overflows.
-                    let mut res = 0u64;
-                    for ch in &buf[..pos] {
-                        res = res * 10 + (u64::from(*ch));
-                    }
-                    if res > u64::from(::std::u32::MAX) {
-                        Err(SyntaxError::IllegalInteger)
-                    } else {
-                        Ok(Serial(res as u32))
-                    }
-                } else if pos == 14 {
-                    let year = u32_from_buf(&buf[0..4]) as i32;
-                    let month = u32_from_buf(&buf[4..6]);
-                    let day = u32_from_buf(&buf[6..8]);
-                    let hour = u32_from_buf(&buf[8..10]);
-                    let minute = u32_from_buf(&buf[10..12]);
-                    let second = u32_from_buf(&buf[12..14]);
-                    match month {
-                        1 | 3 | 5 | 7 | 8 | 10 | 12 => {
-                            if month > 31 {
-                                return Err(SyntaxError::IllegalInteger);
-                            }
-                        }
-                        4 | 6 | 9 | 11 => {
-                            if month > 30 {
-                                return Err(SyntaxError::IllegalInteger);
-                            }
-                        }
-                        2 => {
-                            if year % 4 == 0 && year % 100 != 0 {
-                                if month > 29 {
-                                    return Err(SyntaxError::IllegalInteger);
-                                }
-                            } else if month > 28 {
-                                return Err(SyntaxError::IllegalInteger);
-                            }
-                        }
-                        _ => return Err(SyntaxError::IllegalInteger),
-                    }
-                    if month < 1 || hour > 23 || minute > 59 || second > 59 {
-                        return Err(SyntaxError::IllegalInteger);
-                    }
-                    Ok(Serial(
-                        Utc.ymd(year, month, day)
-                            .and_hms(hour, minute, second)
-                            .timestamp() as u32,
-                    ))
-                } else {
-                    Err(SyntaxError::IllegalInteger) // XXX Still not quite.
-                }
-            },
-        )
+        Timestamp::scan(scanner).map(Into::into)
     }
 }
 
@@ -262,9 +202,229 @@ impl CanonicalOrd for Serial {
     }
 }
 
-//------------ Helper Functions ----------------------------------------------
+//------------ Timestamp -----------------------------------------------------
+
+/// A point in time as used in RRSIG records.
+///
+/// The signature expiration and inception times of [RRSIG] records are
+/// given as the number of seconds since 1 January 1970 00:00:00 UTC. Since
+/// these are only 32 bits long, they wrap around every 136 years and, as
+/// described in [RFC 4034, section 3.1.5], have to be interpreted using
+/// the serial number arithmetic of [RFC 1982]. Consequently, the type only
+/// implements a partial ordering, just like [`Serial`].
+///
+/// In the presentation format, the value can be given either as a decimal
+/// integer or as a date in the form `YYYYMMDDHHmmSS` in UTC. Both forms are
+/// accepted by the [`FromStr`][str::FromStr] and [`Scan`] implementations.
+/// Dates outside the range representable by 32 bits are wrapped around.
+/// The [`Display`][fmt::Display] implementation always produces the date
+/// form for a date between 1970 and 2106.
+///
+/// [RRSIG]: crate::rdata::rfc4034::Rrsig
+/// [RFC 1982]: https://tools.ietf.org/html/rfc1982
+/// [RFC 4034, section 3.1.5]: https://tools.ietf.org/html/rfc4034#section-3.1.5
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Timestamp(pub u32);
+
+impl Timestamp {
+    /// Returns a timestamp for the current Unix time.
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        Timestamp(Serial::now().into_int())
+    }
+
+    /// Returns the timestamp as a raw integer.
+    pub fn into_int(self) -> u32 {
+        self.0
+    }
+
+    /// Converts the timestamp into a serial number.
+    pub fn into_serial(self) -> Serial {
+        Serial(self.0)
+    }
+
+    /// Creates a timestamp from the digits of its presentation format.
+    ///
+    /// The digits must already have been converted into their numerical
+    /// values.
+    fn from_digits(digits: &[u8]) -> Result<Self, IllegalTimestamp> {
+        if digits.is_empty() {
+            Err(IllegalTimestamp)
+        } else if digits.len() <= 10 {
+            // We have an integer. We generate it into a u64 to deal
+            // with possible overflows.
+            let res = digits
+                .iter()
+                .fold(0u64, |res, ch| res * 10 + u64::from(*ch));
+            if res > u64::from(u32::MAX) {
+                Err(IllegalTimestamp)
+            } else {
+                Ok(Timestamp(res as u32))
+            }
+        } else if digits.len() == 14 {
+            let year = i64::from(u32_from_buf(&digits[0..4]));
+            let month = u32_from_buf(&digits[4..6]);
+            let day = u32_from_buf(&digits[6..8]);
+            let hour = i64::from(u32_from_buf(&digits[8..10]));
+            let minute = i64::from(u32_from_buf(&digits[10..12]));
+            let second = i64::from(u32_from_buf(&digits[12..14]));
+            if !(1..=12).contains(&month)
+                || day < 1
+                || day > days_in_month(year, month)
+                || hour > 23
+                || minute > 59
+                || second > 59
+            {
+                return Err(IllegalTimestamp);
+            }
+            let days = days_from_civil(year, month, day);
+            let secs = ((days * 24 + hour) * 60 + minute) * 60 + second;
+
+            // The value is only the lower 32 bits of the actual time.
+            Ok(Timestamp(secs.rem_euclid(0x1_0000_0000) as u32))
+        } else {
+            Err(IllegalTimestamp)
+        }
+    }
+}
+
+//--- From and FromStr
+
+impl From<u32> for Timestamp {
+    fn from(value: u32) -> Self {
+        Timestamp(value)
+    }
+}
+
+impl From<Timestamp> for u32 {
+    fn from(timestamp: Timestamp) -> u32 {
+        timestamp.0
+    }
+}
+
+impl From<Serial> for Timestamp {
+    fn from(serial: Serial) -> Self {
+        Timestamp(serial.0)
+    }
+}
+
+impl From<Timestamp> for Serial {
+    fn from(timestamp: Timestamp) -> Self {
+        Serial(timestamp.0)
+    }
+}
+
+impl str::FromStr for Timestamp {
+    type Err = IllegalTimestamp;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > 14 {
+            return Err(IllegalTimestamp);
+        }
+        let mut digits = [0u8; 14];
+        for (ch, digit) in s.chars().zip(digits.iter_mut()) {
+            *digit = ch.to_digit(10).ok_or(IllegalTimestamp)? as u8;
+        }
+        Self::from_digits(&digits[..s.len()])
+    }
+}
+
+//--- Parse and Compose
+
+impl<T: AsRef<[u8]>> Parse<T> for Timestamp {
+    fn parse(parser: &mut Parser<T>) -> Result<Self, ParseError> {
+        u32::parse(parser).map(Into::into)
+    }
+
+    fn skip(parser: &mut Parser<T>) -> Result<(), ParseError> {
+        u32::skip(parser)
+    }
+}
+
+impl Compose for Timestamp {
+    fn compose<T: OctetsBuilder>(
+        &self,
+        target: &mut T,
+    ) -> Result<(), ShortBuf> {
+        self.0.compose(target)
+    }
+}
+
+//--- Scan and Display
 
 #[cfg(feature = "master")]
+impl Scan for Timestamp {
+    fn scan<C: CharSource>(
+        scanner: &mut Scanner<C>,
+    ) -> Result<Self, ScanError> {
+        scanner.scan_phrase(
+            (0, [0u8; 14]),
+            |&mut (ref mut pos, ref mut buf), symbol| {
+                let ch = symbol.into_digit(10)? as u8;
+                if *pos == 14 {
+                    return Err(SyntaxError::IllegalInteger);
+                }
+                buf[*pos] = ch;
+                *pos += 1;
+                Ok(())
+            },
+            |(pos, buf)| {
+                Timestamp::from_digits(&buf[..pos])
+                    .map_err(|_| SyntaxError::IllegalInteger)
+            },
+        )
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = i64::from(self.0);
+        let (year, month, day) = civil_from_days(secs / 86400);
+        let secs = secs % 86400;
+        write!(
+            f,
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
+            year,
+            month,
+            day,
+            secs / 3600,
+            (secs / 60) % 60,
+            secs % 60
+        )
+    }
+}
+
+//--- PartialOrd and CanonicalOrd
+
+impl cmp::PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.into_serial().partial_cmp(&other.into_serial())
+    }
+}
+
+impl CanonicalOrd for Timestamp {
+    fn canonical_cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+//------------ IllegalTimestamp ----------------------------------------------
+
+/// A string could not be converted into a timestamp.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IllegalTimestamp;
+
+impl fmt::Display for IllegalTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("illegal timestamp")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IllegalTimestamp {}
+
+//------------ Helper Functions ----------------------------------------------
+
 fn u32_from_buf(buf: &[u8]) -> u32 {
     let mut res = 0;
     for ch in buf {
@@ -273,6 +433,53 @@ fn u32_from_buf(buf: &[u8]) -> u32 {
     res
 }
 
+/// Returns the number of days in the given month.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since 1970-01-01 for a date.
+///
+/// This is the algorithm from Howard Hinnant’s “chrono-Compatible Low-Level
+/// Date Algorithms.”
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+        + i64::from(day)
+        - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Returns year, month, and day for the number of days since 1970-01-01.
+///
+/// This is the inverse of `days_from_civil`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
 //============ Testing =======================================================
 
 #[cfg(test)]
@@ -339,4 +546,67 @@ mod test {
         assert_eq!(Serial(1).partial_cmp(&Serial(0x8000_0001)), None);
         assert_eq!(Serial(0x8000_0001).partial_cmp(&Serial(1)), None);
     }
+
+    #[test]
+    fn timestamp_from_str() {
+        use core::str::FromStr;
+
+        let int = Timestamp::from_str("1234567890").unwrap();
+        let date = Timestamp::from_str("20090213233130").unwrap();
+        assert_eq!(int, Timestamp(1_234_567_890));
+        assert_eq!(int, date);
+
+        assert_eq!(Timestamp::from_str("0"), Ok(Timestamp(0)));
+        assert_eq!(Timestamp::from_str("19700101000000"), Ok(Timestamp(0)));
+        assert_eq!(
+            Timestamp::from_str("20000229120000"),
+            Ok(Timestamp(951_825_600))
+        );
+        assert_eq!(
+            Timestamp::from_str("4294967295"),
+            Ok(Timestamp(u32::MAX))
+        );
+
+        // Dates past 2106 wrap around.
+        assert_eq!(Timestamp::from_str("21060207062816"), Ok(Timestamp(0)));
+
+        assert!(Timestamp::from_str("").is_err());
+        assert!(Timestamp::from_str("4294967296").is_err());
+        assert!(Timestamp::from_str("200902132331").is_err());
+        assert!(Timestamp::from_str("20090230000000").is_err());
+        assert!(Timestamp::from_str("21000229000000").is_err());
+        assert!(Timestamp::from_str("20091301000000").is_err());
+        assert!(Timestamp::from_str("20090213243130").is_err());
+        assert!(Timestamp::from_str("2009021323313a").is_err());
+    }
+
+    #[test]
+    fn timestamp_display() {
+        use std::string::ToString;
+
+        assert_eq!(Timestamp(1_234_567_890).to_string(), "20090213233130");
+        assert_eq!(Timestamp(0).to_string(), "19700101000000");
+        assert_eq!(Timestamp(u32::MAX).to_string(), "21060207062815");
+        assert_eq!(Timestamp(951_825_600).to_string(), "20000229120000");
+    }
+
+    #[test]
+    #[cfg(feature = "master")]
+    fn timestamp_scan() {
+        let mut scanner = Scanner::new("1234567890 20090213233130");
+        assert_eq!(
+            Timestamp::scan(&mut scanner).unwrap(),
+            Timestamp(1_234_567_890)
+        );
+        assert_eq!(
+            Timestamp::scan(&mut scanner).unwrap(),
+            Timestamp(1_234_567_890)
+        );
+    }
+
+    #[test]
+    fn timestamp_comparison() {
+        assert!(Timestamp(0xFFFF_FFF0) < Timestamp(0x10));
+        assert!(Timestamp(12) < Timestamp(13));
+    }
 }
@@ -65,6 +65,9 @@
 //!   sequences.
 //! * `std`: support for the Rust std library. This feature is enabled by
 //!   default.
+//! * `tls`: Enables DNS-over-TLS as a transport for the stub resolver via
+//!   the [rustls](https://github.com/rustls/rustls) crate. This
+//!   feature also enables the `resolv` feature.
 //! * `tsig`: support for signing and validating message exchanges via TSIG
 //!   signatures. This enables the [tsig] module and currently pulls in the
 //!   `bytes`, `ring`, and `smallvec` features.
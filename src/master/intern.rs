@@ -0,0 +1,107 @@
+//! Deduplication of domain names.
+//!
+//! When reading large zones, the same owner names appear over and over
+//! again. The [`NameInterner`] provided by this module allows sharing the
+//! octets of identical names.
+
+use crate::base::name::Dname;
+use bytes::Bytes;
+use std::collections::HashSet;
+
+//------------ NameInterner --------------------------------------------------
+
+/// A set of domain names for sharing their octets.
+///
+/// When a name is given to [`intern`][Self::intern], the interner checks
+/// whether it has seen a name with the identical wire format before. If
+/// so, it returns that name which shares the octets with all other copies.
+/// Otherwise it remembers the name and returns it unchanged.
+///
+/// Since a `Dname<Bytes>` is a single continuous octets sequence, names can
+/// only share octets with names they are a suffix of. The interner
+/// therefore also remembers all suffixes of each new name. If, say,
+/// `www.example.com` is interned first, a later `example.com` will be a
+/// slice of its octets.
+///
+/// Names are compared exactly, i.e., unlike the comparison of
+/// [`Dname`] itself, names that differ only in their case are kept
+/// separate.
+#[derive(Clone, Debug, Default)]
+pub struct NameInterner {
+    /// The known names and all their suffixes in wire format.
+    names: HashSet<Bytes>,
+}
+
+impl NameInterner {
+    /// Creates a new, empty interner.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns a name identical to `name` that shares octets if possible.
+    pub fn intern(&mut self, name: Dname<Bytes>) -> Dname<Bytes> {
+        if let Some(octets) = self.names.get(name.as_slice()) {
+            return unsafe { Dname::from_octets_unchecked(octets.clone()) };
+        }
+        let octets = name.into_octets();
+        let mut pos = 0;
+        while pos < octets.len() {
+            let suffix = octets.slice(pos..);
+            if !self.names.insert(suffix) {
+                // All further suffixes are known already.
+                break;
+            }
+            pos += usize::from(octets[pos]) + 1;
+        }
+        unsafe { Dname::from_octets_unchecked(octets) }
+    }
+
+    /// Returns the number of names known to the interner.
+    ///
+    /// This includes all suffixes of interned names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns whether the interner doesn’t know any names yet.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn name(s: &str) -> Dname<Bytes> {
+        Dname::bytes_from_str(s).unwrap()
+    }
+
+    #[test]
+    fn intern() {
+        let mut interner = NameInterner::new();
+        let www = interner.intern(name("www.example.com"));
+        assert_eq!(interner.len(), 4);
+
+        // An identical name shares the octets.
+        let again = interner.intern(name("www.example.com"));
+        assert_eq!(again.as_slice().as_ptr(), www.as_slice().as_ptr());
+
+        // A suffix is a slice of the original name.
+        let apex = interner.intern(name("example.com"));
+        assert_eq!(apex.as_slice(), b"\x07example\x03com\0");
+        assert_eq!(apex.as_slice().as_ptr(), www.as_slice()[4..].as_ptr());
+
+        // A new name only adds its new suffixes.
+        let mail = interner.intern(name("mail.example.com"));
+        assert_eq!(interner.len(), 5);
+        assert_ne!(mail.as_slice().as_ptr(), www.as_slice().as_ptr());
+
+        // Names differing in case are kept apart.
+        let upper = interner.intern(name("WWW.example.com"));
+        assert_eq!(upper.as_slice(), b"\x03WWW\x07example\x03com\0");
+        assert_eq!(interner.len(), 6);
+    }
+}
@@ -4,6 +4,8 @@
 #![cfg(feature = "master")]
 
 pub mod entry;
+pub mod intern;
 pub mod reader;
 pub mod scan;
 pub mod source;
+pub mod writer;
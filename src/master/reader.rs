@@ -1,8 +1,10 @@
 use super::entry::{Entry, MasterRecord};
+use super::intern::NameInterner;
 use super::scan::{CharSource, Pos, ScanError, Scanner};
 use super::source::Utf8File;
 use crate::base::iana::Class;
 use crate::base::name::Dname;
+use crate::base::record::Record;
 use bytes::Bytes;
 use std::fmt;
 use std::io;
@@ -13,6 +15,7 @@ pub struct Reader<C: CharSource> {
     scanner: Option<Scanner<C>>,
     ttl: Option<u32>,
     last: Option<(Dname<Bytes>, Class)>,
+    interner: Option<NameInterner>,
 }
 
 impl<C: CharSource> Reader<C> {
@@ -21,6 +24,21 @@ impl<C: CharSource> Reader<C> {
             scanner: Some(Scanner::new(source)),
             ttl: None,
             last: None,
+            interner: None,
+        }
+    }
+
+    /// Enables or disables sharing the octets of identical owner names.
+    ///
+    /// If enabled, owner names of records are passed through a
+    /// [`NameInterner`] so that records with the same owner share the
+    /// octets of the name. This reduces memory use when keeping the records
+    /// of large zones.
+    pub fn set_intern_names(&mut self, enable: bool) {
+        if !enable {
+            self.interner = None
+        } else if self.interner.is_none() {
+            self.interner = Some(NameInterner::new())
         }
     }
 }
@@ -44,6 +62,15 @@ impl<C: CharSource> Reader<C> {
                     return Ok(Some(ReaderItem::Control { name, start }))
                 }
                 Ok(Some(Entry::Record(record))) => {
+                    let record = match self.interner {
+                        Some(ref mut interner) => Record::new(
+                            interner.intern(record.owner().clone()),
+                            record.class(),
+                            record.ttl(),
+                            record.into_data(),
+                        ),
+                        None => record,
+                    };
                     self.last =
                         Some((record.owner().clone(), record.class()));
                     return Ok(Some(ReaderItem::Record(record)));
@@ -172,4 +199,57 @@ $INCLUDE <SUBSYS>ISI-MAILBOXES.TXT"[..],
             }
         }
     }
+
+    #[test]
+    fn intern_names() {
+        use std::fmt::Write;
+        use std::vec::Vec;
+
+        let mut zone = String::from(
+            "$ORIGIN example.com.\n$TTL 3600\n\
+             @ IN SOA ns hostmaster 1 7200 600 3600000 60\n",
+        );
+        for i in 0..1000 {
+            writeln!(zone, "host{} A 192.0.2.1", i).unwrap();
+            writeln!(zone, "host{} AAAA 2001:db8::1", i).unwrap();
+            writeln!(zone, "example.com. NS host{}", i).unwrap();
+        }
+        let owners = |intern| -> Vec<_> {
+            let mut reader = Reader::new(zone.as_str());
+            reader.set_intern_names(intern);
+            reader
+                .map(|item| match item.unwrap() {
+                    ReaderItem::Record(record) => record.owner().clone(),
+                    _ => panic!("unexpected item"),
+                })
+                .collect()
+        };
+
+        let interned = owners(true);
+        assert_eq!(interned.len(), 3001);
+        let apex = interned[0].as_slice().as_ptr();
+        for chunk in interned[1..].chunks(3) {
+            // Both records of a host share the owner.
+            assert_eq!(chunk[0].as_slice(), chunk[1].as_slice());
+            assert_eq!(
+                chunk[0].as_slice().as_ptr(),
+                chunk[1].as_slice().as_ptr()
+            );
+
+            // All apex owners share the octets of the first one.
+            assert_eq!(chunk[2].as_slice(), b"\x07example\x03com\0");
+            assert_eq!(chunk[2].as_slice().as_ptr(), apex);
+        }
+
+        // Without interning, they don’t.
+        let plain = owners(false);
+        assert_ne!(
+            plain[1].as_slice().as_ptr(),
+            plain[2].as_slice().as_ptr()
+        );
+        assert_ne!(
+            plain[0].as_slice().as_ptr(),
+            plain[3].as_slice().as_ptr()
+        );
+    }
 }
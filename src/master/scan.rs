@@ -1,7 +1,7 @@
 //! Scanning master file tokens.
 
 use crate::base::name;
-use crate::base::name::Dname;
+use crate::base::name::{Dname, UncertainDname};
 use crate::base::net::AddrParseError;
 use crate::base::str::{BadSymbol, Symbol};
 use crate::utils::{base32, base64};
@@ -112,6 +112,33 @@ impl<C: CharSource> Scanner<C> {
     pub fn set_origin(&mut self, origin: Option<Dname<Bytes>>) {
         self.origin = origin
     }
+
+    /// Scans an absolute domain name.
+    ///
+    /// The name is scanned in presentation format, i.e., with all the
+    /// escape sequences resolved. If the name is relative, the current
+    /// origin is appended to it. If there is no origin, a
+    /// [`SyntaxError::NoOrigin`] is returned instead.
+    ///
+    /// This is the method all record data types containing domain names
+    /// should use – usually via `Dname<Bytes>`’s `Scan` implementation – so
+    /// that names are treated the same everywhere.
+    ///
+    /// [`SyntaxError::NoOrigin`]: enum.SyntaxError.html#variant.NoOrigin
+    pub fn scan_dname(&mut self) -> Result<Dname<Bytes>, ScanError> {
+        let pos = self.pos();
+        let name = match UncertainDname::scan(self)? {
+            UncertainDname::Relative(name) => name,
+            UncertainDname::Absolute(name) => return Ok(name),
+        };
+        let origin = match self.origin {
+            Some(ref origin) => origin,
+            None => return Err((SyntaxError::NoOrigin, pos).into()),
+        };
+        name.into_builder()
+            .append_origin(origin)
+            .map_err(|err| (SyntaxError::from(err), pos).into())
+    }
 }
 
 /// # Fundamental Scanning
@@ -123,6 +150,18 @@ impl<C: CharSource> Scanner<C> {
         !matches!(self.peek(), Ok(Some(_)))
     }
 
+    /// Returns whether the scanner has reached the end of the current entry.
+    ///
+    /// This is the case if the next token is a newline, the start of a
+    /// comment, or the end of data. Errors from reading the underlying
+    /// source are returned.
+    pub fn end_of_entry_reached(&mut self) -> Result<bool, ScanError> {
+        match self.peek()? {
+            Some(ch) => Ok(ch.is_newline_ahead()),
+            None => Ok(true),
+        }
+    }
+
     /// Returns the current position of the scanner.
     pub fn pos(&self) -> Pos {
         self.cur_pos
@@ -446,6 +485,12 @@ impl<C: CharSource> Scanner<C> {
                         *left = &left[ch.len_utf8()..];
                         Ok(())
                     }
+                    Symbol::SimpleEscape(ch)
+                        if first == '\\' && left[1..].starts_with(ch) =>
+                    {
+                        *left = &left[1 + ch.len_utf8()..];
+                        Ok(())
+                    }
                     _ => Err(SyntaxError::Expected(literal.into())),
                 }
             },
@@ -1370,4 +1415,52 @@ mod test {
         let mut scanner = Scanner::new("one two three\nfour");
         assert_eq!(scanner.scan_string_word(Ok).unwrap(), "one");
     }
+
+    #[test]
+    fn skip_literal() {
+        let mut scanner = Scanner::new("\\# foo \\#");
+        scanner.skip_literal("\\#").unwrap();
+        assert!(scanner.skip_literal("fo").is_err());
+        scanner.skip_literal("foo").unwrap();
+        assert!(scanner.skip_literal("#").is_err());
+    }
+
+    #[test]
+    fn scan_dname() {
+        use core::str::FromStr;
+
+        let mut scanner = Scanner::new("www example.org. a\\046b foo");
+        scanner.set_origin(Some(Dname::from_str("example.org").unwrap()));
+        assert_eq!(
+            scanner.scan_dname().unwrap(),
+            Dname::<Bytes>::from_str("www.example.org").unwrap()
+        );
+        assert_eq!(
+            scanner.scan_dname().unwrap(),
+            Dname::<Bytes>::from_str("example.org").unwrap()
+        );
+        assert_eq!(
+            scanner.scan_dname().unwrap().as_slice(),
+            b"\x03a.b\x07example\x03org\x00"
+        );
+
+        scanner.set_origin(None);
+        assert!(scanner.scan_dname().is_err());
+    }
+
+    #[test]
+    fn scan_dname_empty_label() {
+        for s in
+            &["www..example.org.", ".example.org.", "example.org..", ".."]
+        {
+            let mut scanner = Scanner::new(*s);
+            match scanner.scan_dname() {
+                Err(ScanError::Syntax(
+                    SyntaxError::IllegalName(name::FromStrError::EmptyLabel),
+                    _,
+                )) => {}
+                res => panic!("{:?}: unexpected result {:?}", s, res),
+            }
+        }
+    }
 }
@@ -9,7 +9,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 use std::vec::Vec;
-use std::{char, error, fmt, io};
+use std::{char, cmp, error, fmt, io, str};
 
 //------------ str -----------------------------------------------------------
 
@@ -24,6 +24,90 @@ impl<'a> CharSource for &'a str {
     }
 }
 
+//------------ SliceCharSource -----------------------------------------------
+
+/// A character source reading UTF-8 encoded text from a byte slice.
+///
+/// This is useful for scanning master data that is already in memory, for
+/// instance when it was embedded in the program or received over the
+/// network. Unlike using a `&str` directly, the slice doesn’t need to have
+/// been checked for being valid UTF-8. Instead, an error is returned when an
+/// invalid sequence is encountered.
+///
+/// The source keeps track of the number of octets consumed so far which is
+/// available via [`offset`][Self::offset]. The human-friendly line and
+/// column position is tracked by the [`Scanner`] itself.
+///
+/// [`Scanner`]: super::scan::Scanner
+#[derive(Clone, Debug)]
+pub struct SliceCharSource<'a> {
+    /// The complete data.
+    data: &'a [u8],
+
+    /// The index in `data` of the next octet to be read.
+    offset: usize,
+}
+
+impl<'a> SliceCharSource<'a> {
+    /// Creates a new source atop a byte slice.
+    pub fn new(data: &'a [u8]) -> Self {
+        SliceCharSource { data, offset: 0 }
+    }
+
+    /// Returns the number of octets consumed so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the remaining, not yet consumed data.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.offset..]
+    }
+}
+
+impl<'a> From<&'a [u8]> for SliceCharSource<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<'a> From<&'a str> for SliceCharSource<'a> {
+    fn from(data: &'a str) -> Self {
+        Self::new(data.as_bytes())
+    }
+}
+
+impl<'a> CharSource for SliceCharSource<'a> {
+    fn next(&mut self) -> Result<Option<char>, io::Error> {
+        let remaining = self.remaining();
+        let head = &remaining[..cmp::min(remaining.len(), 4)];
+        let head = match str::from_utf8(head) {
+            Ok(head) => head,
+            Err(err) if err.valid_up_to() > 0 => {
+                // Safety: from_utf8 has just checked this part.
+                unsafe {
+                    str::from_utf8_unchecked(&head[..err.valid_up_to()])
+                }
+            }
+            Err(err) => {
+                if err.error_len().is_none() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "unexpected EOF",
+                    ));
+                }
+                return Err(Utf8Error.into());
+            }
+        };
+        let res = match head.chars().next() {
+            Some(ch) => ch,
+            None => return Ok(None),
+        };
+        self.offset += res.len_utf8();
+        Ok(Some(res))
+    }
+}
+
 //------------ AsciiFile -----------------------------------------------------
 
 /// A file that contains only ASCII characters.
@@ -283,3 +367,64 @@ impl fmt::Display for Utf8Error {
 }
 
 impl error::Error for Utf8Error {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::{Class, Rtype};
+    use crate::master::entry::Entry;
+    use crate::master::scan::Scanner;
+    use crate::rdata::MasterRecordData;
+    use std::net::Ipv4Addr;
+    use std::string::ToString;
+
+    #[test]
+    fn slice_chars() {
+        let mut source = SliceCharSource::new("añ€𝄞".as_bytes());
+        assert_eq!(source.next().unwrap(), Some('a'));
+        assert_eq!(source.offset(), 1);
+        assert_eq!(source.next().unwrap(), Some('ñ'));
+        assert_eq!(source.next().unwrap(), Some('€'));
+        assert_eq!(source.next().unwrap(), Some('𝄞'));
+        assert_eq!(source.offset(), 10);
+        assert_eq!(source.next().unwrap(), None);
+
+        let mut source = SliceCharSource::new(b"a\xff");
+        assert_eq!(source.next().unwrap(), Some('a'));
+        assert!(source.next().is_err());
+
+        let mut source = SliceCharSource::new(b"\xe2\x82");
+        assert_eq!(
+            source.next().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn scan_a_record() {
+        let mut scanner = Scanner::new(SliceCharSource::from(
+            "example.com. 3600 IN A 192.0.2.1\n",
+        ));
+        let record =
+            match Entry::scan(&mut scanner, None, None, None).unwrap() {
+                Some(Entry::Record(record)) => record,
+                _ => panic!("expected record"),
+            };
+        assert_eq!(record.owner().to_string(), "example.com");
+        assert_eq!(record.class(), Class::In);
+        assert_eq!(record.ttl(), 3600);
+        assert_eq!(record.rtype(), Rtype::A);
+        match record.data() {
+            MasterRecordData::A(a) => {
+                assert_eq!(a.addr(), Ipv4Addr::new(192, 0, 2, 1))
+            }
+            _ => panic!("expected A record"),
+        }
+        assert_eq!(scanner.pos(), (2, 1));
+        assert!(Entry::scan(&mut scanner, None, None, None)
+            .unwrap()
+            .is_none());
+    }
+}
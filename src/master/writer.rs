@@ -0,0 +1,162 @@
+//! Writing master files.
+
+use crate::base::iana::Class;
+use crate::base::name::{Dname, ToDname};
+use crate::base::rdata::RecordData;
+use crate::base::record::Record;
+use std::fmt;
+use std::io;
+use std::vec::Vec;
+
+//------------ ZoneWriter ----------------------------------------------------
+
+/// A writer producing master file output.
+///
+/// The writer outputs one line per record. It keeps track of the owner,
+/// class, and TTL of the previous record and leaves out whatever hasn’t
+/// changed, as is customary in hand-written zone files. If the owner is
+/// the same as in the previous record, the line starts with white space
+/// instead. If the TTL changes, a `$TTL` directive is written before the
+/// record so that records themselves never need to carry a TTL.
+pub struct ZoneWriter<W> {
+    /// The target to write to.
+    target: W,
+
+    /// The owner of the previous record.
+    owner: Option<Dname<Vec<u8>>>,
+
+    /// The class of the previous record.
+    class: Option<Class>,
+
+    /// The current default TTL.
+    ttl: Option<u32>,
+}
+
+impl<W: io::Write> ZoneWriter<W> {
+    /// Creates a new writer atop the given target.
+    pub fn new(target: W) -> Self {
+        ZoneWriter {
+            target,
+            owner: None,
+            class: None,
+            ttl: None,
+        }
+    }
+
+    /// Writes a record.
+    pub fn write_record<N, D>(
+        &mut self,
+        record: &Record<N, D>,
+    ) -> Result<(), io::Error>
+    where
+        N: ToDname + fmt::Display,
+        D: RecordData + fmt::Display,
+    {
+        if self.ttl != Some(record.ttl()) {
+            writeln!(self.target, "$TTL {}", record.ttl())?;
+            self.ttl = Some(record.ttl());
+        }
+        match self.owner {
+            Some(ref owner) if owner.name_eq(record.owner()) => {}
+            _ => {
+                write!(self.target, "{}.", record.owner())?;
+                self.owner = Some(record.owner().to_vec());
+            }
+        }
+        write!(self.target, "\t")?;
+        if self.class != Some(record.class()) {
+            write!(self.target, "{} ", record.class())?;
+            self.class = Some(record.class());
+        }
+        writeln!(self.target, "{} {}", record.rtype(), record.data())
+    }
+
+    /// Writes all records produced by an iterator.
+    pub fn write_records<'a, I, N, D>(
+        &mut self,
+        records: I,
+    ) -> Result<(), io::Error>
+    where
+        I: IntoIterator<Item = &'a Record<N, D>>,
+        N: ToDname + fmt::Display + 'a,
+        D: RecordData + fmt::Display + 'a,
+    {
+        for record in records {
+            self.write_record(record)?;
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the underlying target.
+    pub fn as_target(&self) -> &W {
+        &self.target
+    }
+
+    /// Converts the writer into the underlying target.
+    pub fn into_target(self) -> W {
+        self.target
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::master::reader::{Reader, ReaderItem};
+    use crate::rdata::{Aaaa, Mx, A};
+    use core::str::FromStr;
+    use std::string::String;
+
+    #[test]
+    fn omit_owner() {
+        let www = Dname::<Vec<u8>>::from_str("www.example.com").unwrap();
+        let mut writer = ZoneWriter::new(Vec::new());
+        writer
+            .write_record(&Record::new(
+                www.clone(),
+                Class::In,
+                3600,
+                A::from_octets(192, 0, 2, 1),
+            ))
+            .unwrap();
+        writer
+            .write_record(&Record::new(
+                www.clone(),
+                Class::In,
+                3600,
+                Aaaa::from_str("2001:db8::1").unwrap(),
+            ))
+            .unwrap();
+        writer
+            .write_record(&Record::new(
+                www.clone(),
+                Class::In,
+                3600,
+                Mx::new(10, www.clone()),
+            ))
+            .unwrap();
+        let text = String::from_utf8(writer.into_target()).unwrap();
+        assert_eq!(
+            text,
+            "$TTL 3600\n\
+             www.example.com.\tIN A 192.0.2.1\n\
+             \tAAAA 2001:db8::1\n\
+             \tMX 10 www.example.com.\n"
+        );
+
+        // We should be able to read this back, too.
+        let records = Reader::new(text.as_str())
+            .map(|item| match item.unwrap() {
+                ReaderItem::Record(record) => record,
+                _ => panic!("unexpected item"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 3);
+        for record in records {
+            assert_eq!(record.owner(), &www);
+            assert_eq!(record.class(), Class::In);
+            assert_eq!(record.ttl(), 3600);
+        }
+    }
+}
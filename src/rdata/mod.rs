@@ -27,6 +27,13 @@
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "bytes")]
+use crate::base::name::{Dname as BaseDname, ToDname};
+#[cfg(feature = "bytes")]
+use crate::base::record::Record;
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+
 pub mod rfc1035;
 pub mod rfc2782;
 pub mod rfc2845;
@@ -35,6 +42,7 @@ pub mod rfc4034;
 pub mod rfc5155;
 pub mod rfc6672;
 pub mod rfc7344;
+pub mod rfc9460;
 
 // The rdata_types! macro (defined in self::macros) reexports the record data
 // types here and creates the MasterRecordData and AllRecordData enums
@@ -113,4 +121,307 @@ rdata_types! {
             Cds<O>,
         }
     }
+    rfc9460::{
+        pseudo {
+            Svcb<O, N>,
+            Https<O, N>,
+        }
+    }
+}
+
+//------------ follow_cname --------------------------------------------------
+
+/// Follows a chain of CNAME and DNAME records starting at a query name.
+///
+/// Starting with `qname`, the function looks for a CNAME record owned by
+/// the current name in `answer`. If it finds one, it continues with the
+/// CNAME’s target. Otherwise, it looks for a DNAME record whose owner is
+/// a parent of the current name and continues with the name resulting
+/// from the substitution performed by [`Dname::apply_dname`]. Once
+/// neither is found for the current name, this name is returned. If there
+/// is nothing to follow for `qname` at all, `qname` itself is returned.
+///
+/// Since records of other types are ignored, `answer` can simply be the
+/// answer section of a response. If the chain loops, `None` is returned.
+///
+/// This is similar to [`Message::canonical_name`] but works on records that
+/// have already been collected.
+///
+/// [`Dname::apply_dname`]: ../base/name/struct.Dname.html#method.apply_dname
+/// [`Message::canonical_name`]: ../base/message/struct.Message.html#method.canonical_name
+#[cfg(feature = "bytes")]
+pub fn follow_cname<Name, Octets, RName, QName>(
+    answer: &[Record<Name, AllRecordData<Octets, RName>>],
+    qname: &QName,
+) -> Option<BaseDname<Bytes>>
+where
+    Name: ToDname,
+    RName: ToDname,
+    QName: ToDname + ?Sized,
+{
+    let step = |name: &BaseDname<Bytes>| {
+        answer
+            .iter()
+            .find_map(|record| match *record.data() {
+                AllRecordData::Cname(ref cname)
+                    if record.owner().name_eq(name) =>
+                {
+                    Some(cname.cname().to_bytes())
+                }
+                _ => None,
+            })
+            .or_else(|| {
+                answer.iter().find_map(|record| match *record.data() {
+                    AllRecordData::Dname(ref dname) => {
+                        name.apply_dname(record.owner(), dname.dname())
+                    }
+                    _ => None,
+                })
+            })
+    };
+
+    // A DNAME record can be applied more than once, so the number of steps
+    // isn’t limited by the number of records. Since each step only depends
+    // on the current name, though, the chain loops if and only if a name
+    // is repeated. We detect this with Brent’s algorithm: `mark` is a name
+    // seen earlier that we move forward to the current name whenever the
+    // number of steps since it was last moved reaches a power of two.
+    let mut name = qname.to_bytes();
+    let mut mark = name.clone();
+    let mut power = 1usize;
+    let mut steps = 0usize;
+    loop {
+        name = match step(&name) {
+            Some(next) => next,
+            None => return Some(name),
+        };
+        if name == mark {
+            return None;
+        }
+        steps += 1;
+        if steps == power {
+            mark = name.clone();
+            power *= 2;
+            steps = 0;
+        }
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use crate::base::iana::Rtype;
+    use crate::base::name::Dname;
+    use crate::base::rdata::UnknownRecordData;
+    use crate::base::{Message, MessageBuilder};
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    #[test]
+    fn parse_all_record_data() {
+        let name = Dname::<Vec<u8>>::from_str("www.example.com").unwrap();
+        let alias = Dname::<Vec<u8>>::from_str("alias.example.com").unwrap();
+        let mut msg = MessageBuilder::new_vec().answer();
+        msg.push((&alias, 3600, Cname::new(name.clone()))).unwrap();
+        msg.push((&name, 3600, A::from_octets(192, 0, 2, 1)))
+            .unwrap();
+        msg.push((&name, 3600, Aaaa::from_str("2001:db8::1").unwrap()))
+            .unwrap();
+        msg.push((
+            &name,
+            3600,
+            UnknownRecordData::from_octets(Rtype::Int(65280), vec![1, 2]),
+        ))
+        .unwrap();
+        let msg = Message::from_octets(msg.finish()).unwrap();
+
+        let mut answer =
+            msg.answer().unwrap().limit_to::<AllRecordData<_, _>>();
+        match *answer.next().unwrap().unwrap().data() {
+            AllRecordData::Cname(ref cname) => {
+                assert_eq!(cname.cname(), &name)
+            }
+            ref other => panic!("expected CNAME, got {:?}", other),
+        }
+        match *answer.next().unwrap().unwrap().data() {
+            AllRecordData::A(ref a) => {
+                assert_eq!(a, &A::from_octets(192, 0, 2, 1))
+            }
+            ref other => panic!("expected A, got {:?}", other),
+        }
+        match *answer.next().unwrap().unwrap().data() {
+            AllRecordData::Aaaa(ref aaaa) => {
+                assert_eq!(aaaa, &Aaaa::from_str("2001:db8::1").unwrap())
+            }
+            ref other => panic!("expected AAAA, got {:?}", other),
+        }
+        match *answer.next().unwrap().unwrap().data() {
+            AllRecordData::Other(ref data) => {
+                assert_eq!(data.rtype(), Rtype::Int(65280));
+                assert_eq!(data.data(), &[1, 2]);
+            }
+            ref other => panic!("expected unknown data, got {:?}", other),
+        }
+        assert!(answer.next().is_none());
+    }
+
+    #[test]
+    fn legacy_types_as_unknown() {
+        use crate::base::octets::{Compose, Parse, Parser};
+        use crate::base::record::Record;
+        use std::string::ToString;
+
+        assert_eq!(Rtype::from_str("WKS").unwrap(), Rtype::Wks);
+        assert_eq!(Rtype::from_str("a6").unwrap(), Rtype::A6);
+        assert_eq!(Rtype::Wks.to_string(), "WKS");
+        assert_eq!(Rtype::A6.to_string(), "A6");
+
+        // example.com. 3600 IN WKS 192.0.2.1 TCP SMTP
+        let wire = b"\x07example\x03com\x00\x00\x0b\x00\x01\
+                     \x00\x00\x0e\x10\x00\x09\
+                     \xc0\x00\x02\x01\x06\x00\x00\x00\x40";
+        let text =
+            "example.com. 3600 IN WKS \\# 9 c0 00 02 01 06 00 00 00 40";
+
+        let mut parser = Parser::from_ref(wire.as_ref());
+        let record: Record<_, AllRecordData<_, _>> =
+            Option::parse(&mut parser).unwrap().unwrap();
+        assert_eq!(parser.remaining(), 0);
+        match *record.data() {
+            AllRecordData::Other(ref data) => {
+                assert_eq!(data.rtype(), Rtype::Wks);
+                assert_eq!(*data.data(), &wire[23..]);
+            }
+            ref other => panic!("expected unknown data, got {:?}", other),
+        }
+        assert_eq!(record.to_string(), text);
+        let mut composed = Vec::new();
+        record.compose(&mut composed).unwrap();
+        assert_eq!(composed.as_slice(), wire.as_ref());
+
+        #[cfg(feature = "master")]
+        {
+            use crate::master::entry::Entry;
+            use crate::master::scan::Scanner;
+
+            let mut scanner = Scanner::new(text);
+            let record = match Entry::scan(&mut scanner, None, None, None) {
+                Ok(Some(Entry::Record(record))) => record,
+                other => panic!("expected record, got {:?}", other),
+            };
+            match *record.data() {
+                MasterRecordData::Other(ref data) => {
+                    assert_eq!(data.rtype(), Rtype::Wks);
+                    assert_eq!(data.data().as_ref(), &wire[23..]);
+                }
+                ref other => panic!("expected unknown data, got {:?}", other),
+            }
+            assert_eq!(record.to_string(), text);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn follow_cname() {
+        use crate::base::iana::Class;
+        use crate::base::record::Record;
+
+        fn name(s: &str) -> Dname<Vec<u8>> {
+            Dname::from_str(s).unwrap()
+        }
+
+        fn cname(
+            owner: &str,
+            target: &str,
+        ) -> Record<Dname<Vec<u8>>, AllRecordData<Vec<u8>, Dname<Vec<u8>>>>
+        {
+            Record::new(
+                name(owner),
+                Class::In,
+                3600,
+                Cname::new(name(target)).into(),
+            )
+        }
+
+        let www = name("www.example.com");
+        let answer = vec![
+            Record::new(
+                name("target.example.net"),
+                Class::In,
+                3600,
+                A::from_octets(192, 0, 2, 1).into(),
+            ),
+            cname("alias.example.org", "target.example.net"),
+            cname("www.example.com", "alias.example.org"),
+        ];
+        assert_eq!(
+            super::follow_cname(&answer, &www).unwrap(),
+            name("target.example.net")
+        );
+        assert_eq!(
+            super::follow_cname(&answer, &name("alias.example.org")).unwrap(),
+            name("target.example.net")
+        );
+        assert_eq!(
+            super::follow_cname(&answer, &name("example.com")).unwrap(),
+            name("example.com")
+        );
+
+        let answer = vec![
+            Record::new(
+                name("example.com"),
+                Class::In,
+                3600,
+                rfc6672::Dname::new(name("example.net")).into(),
+            ),
+            cname("www.example.net", "www.example.org"),
+        ];
+        assert_eq!(
+            super::follow_cname(&answer, &www).unwrap(),
+            name("www.example.org")
+        );
+        assert_eq!(
+            super::follow_cname(&answer, &name("example.com")).unwrap(),
+            name("example.com")
+        );
+
+        // The DNAME is applied twice, which takes more steps than there are
+        // records.
+        let answer = vec![
+            Record::new(
+                name("example.com"),
+                Class::In,
+                3600,
+                rfc6672::Dname::new(name("example.net")).into(),
+            ),
+            cname("www.example.net", "foo.example.com"),
+        ];
+        assert_eq!(
+            super::follow_cname(&answer, &www).unwrap(),
+            name("foo.example.net")
+        );
+
+        let answer = vec![
+            Record::new(
+                name("example.com"),
+                Class::In,
+                3600,
+                rfc6672::Dname::new(name("example.net")).into(),
+            ),
+            cname("www.example.net", "www.example.com"),
+        ];
+        assert_eq!(super::follow_cname(&answer, &www), None);
+
+        let answer = vec![cname("www.example.com", "www.example.com")];
+        assert_eq!(super::follow_cname(&answer, &www), None);
+
+        let answer = vec![
+            cname("www.example.com", "alias.example.org"),
+            cname("alias.example.org", "www.example.com"),
+        ];
+        assert_eq!(super::follow_cname(&answer, &www), None);
+    }
 }
@@ -299,7 +299,10 @@ impl<Octets: AsRef<[u8]>> hash::Hash for Hinfo<Octets> {
 
 impl<Ref: OctetsRef> Parse<Ref> for Hinfo<Ref::Range> {
     fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
-        Ok(Self::new(CharStr::parse(parser)?, CharStr::parse(parser)?))
+        Ok(Self::new(
+            parser.parse_char_str()?,
+            parser.parse_char_str()?,
+        ))
     }
 
     fn skip(parser: &mut Parser<Ref>) -> Result<(), ParseError> {
@@ -1456,26 +1459,40 @@ impl<Octets: AsRef<[u8]>> Compose for Txt<Octets> {
 
 #[cfg(feature = "master")]
 impl Scan for Txt<Bytes> {
+    /// Scans the record data of a Txt record.
+    ///
+    /// The data consists of one or more phrases, each of which becomes
+    /// its own character string. Phrases longer than 255 octets are split
+    /// into multiple character strings.
     fn scan<C: CharSource>(
         scanner: &mut Scanner<C>,
     ) -> Result<Self, ScanError> {
-        scanner.scan_byte_phrase(|res| {
-            let mut builder = TxtBuilder::new_bytes();
-            if builder.append_slice(res.as_ref()).is_err() {
-                Err(SyntaxError::LongCharStr)
-            } else {
-                Ok(builder.finish())
+        let mut builder = TxtBuilder::new_bytes();
+        loop {
+            scanner.scan_byte_phrase(|res| {
+                builder
+                    .append_char_str(res.as_ref())
+                    .map_err(|_| SyntaxError::LongGenericData)
+            })?;
+            if scanner.end_of_entry_reached()? {
+                break;
             }
-        })
+        }
+        Ok(builder.finish())
     }
 }
 
 impl<Octets: AsRef<[u8]>> fmt::Display for Txt<Octets> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for slice in self.iter() {
+        for (i, slice) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str("\"")?;
             for ch in slice.iter() {
                 fmt::Display::fmt(&Symbol::from_octet(*ch), f)?
             }
+            f.write_str("\"")?;
         }
         Ok(())
     }
@@ -1510,7 +1527,7 @@ impl<'a> Iterator for TxtIter<'a> {
         if self.0.remaining() == 0 {
             None
         } else {
-            Some(CharStr::parse(&mut self.0).unwrap().into_octets())
+            Some(self.0.parse_char_str().unwrap().into_octets())
         }
     }
 }
@@ -1572,11 +1589,37 @@ impl<Builder: OctetsBuilder> TxtBuilder<Builder> {
         Ok(())
     }
 
-    pub fn finish(mut self) -> Txt<Builder::Octets> {
-        if let Some(start) = self.start {
+    /// Appends a complete character string.
+    ///
+    /// Other than [`append_slice`][Self::append_slice], the content of
+    /// `slice` is not combined with any previously appended data but
+    /// starts a new character string. If `slice` is longer than 255
+    /// octets, it is split into multiple character strings. If it is
+    /// empty, an empty character string is appended.
+    pub fn append_char_str(&mut self, slice: &[u8]) -> Result<(), ShortBuf> {
+        self.close_char_str();
+        if slice.is_empty() {
+            if self.builder.len() + 1 >= 0xFFFF {
+                return Err(ShortBuf);
+            }
+            self.builder.append_slice(&[0])?;
+        } else {
+            self.append_slice(slice)?;
+        }
+        self.close_char_str();
+        Ok(())
+    }
+
+    /// Finishes the current character string if there is one.
+    fn close_char_str(&mut self) {
+        if let Some(start) = self.start.take() {
             let last_slice_len = self.builder.len() - (start + 1);
             self.builder.as_mut()[start] = last_slice_len as u8;
         }
+    }
+
+    pub fn finish(mut self) -> Txt<Builder::Octets> {
+        self.close_char_str();
         Txt(self.builder.freeze())
     }
 }
@@ -1595,6 +1638,41 @@ mod test {
     use super::*;
     use std::vec::Vec;
 
+    type TestName<'a> = ParsedDname<&'a [u8]>;
+
+    #[test]
+    fn rdata_roundtrip() {
+        test_rdata_roundtrip!(A, b"\xc0\x00\x02\x01", "192.0.2.1");
+        test_rdata_roundtrip!(
+            Cname<TestName>;
+            Cname<crate::base::Dname<Bytes>>,
+            b"\x03www\x07example\x03com\x00",
+            "www.example.com."
+        );
+        test_rdata_roundtrip!(
+            Mx<TestName>;
+            Mx<crate::base::Dname<Bytes>>,
+            b"\x00\x0a\x04mail\x07example\x03com\x00",
+            "10 mail.example.com."
+        );
+        test_rdata_roundtrip!(
+            Soa<TestName>;
+            Soa<crate::base::Dname<Bytes>>,
+            b"\x03ns1\x07example\x03com\x00\
+              \x0ahostmaster\x07example\x03com\x00\
+              \x78\x76\x2a\xb5\x00\x00\x1c\x20\x00\x00\x0e\x10\
+              \x00\x12\x75\x00\x00\x00\x01\x2c",
+            "ns1.example.com. hostmaster.example.com. \
+             2021010101 7200 3600 1209600 300"
+        );
+        test_rdata_roundtrip!(
+            Txt<&[u8]>;
+            Txt<Bytes>,
+            b"\x05hello\x0bhello world",
+            "\"hello\" \"hello\\ world\""
+        );
+    }
+
     #[test]
     #[cfg(features = "bytes")]
     fn hinfo_octets_into() {
@@ -1705,4 +1783,62 @@ mod test {
             assert_eq!(a, b);
         }
     }
+
+    #[test]
+    #[cfg(feature = "master")]
+    fn txt_scan() {
+        use crate::master::scan::Scanner;
+        use std::string::{String, ToString};
+
+        let long: String = core::iter::repeat('a').take(300).collect();
+        let zone = format!("\"{}\" \"b\\\"c\" \"\"\n", long);
+        let mut scanner = Scanner::new(zone.as_str());
+        let txt = Txt::scan(&mut scanner).unwrap();
+        let chunks: Vec<_> = txt.iter().collect();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0], &long.as_bytes()[..255]);
+        assert_eq!(chunks[1], &long.as_bytes()[255..]);
+        assert_eq!(chunks[2], b"b\"c");
+        assert_eq!(chunks[3], b"");
+
+        let mut composed = Vec::new();
+        txt.compose(&mut composed).unwrap();
+        assert_eq!(composed[0], 255);
+        assert_eq!(composed[256], 45);
+        assert_eq!(composed.len(), 300 + 4 + 3);
+
+        // Display quotes each chunk and can be scanned again.
+        let displayed = txt.to_string();
+        assert_eq!(
+            displayed,
+            format!(
+                "\"{}\" \"{}\" \"b\\\"c\" \"\"",
+                &long[..255],
+                &long[255..]
+            )
+        );
+        let mut scanner = Scanner::new(displayed.as_str());
+        assert_eq!(Txt::scan(&mut scanner).unwrap(), txt);
+
+        // The scan ends at a comment.
+        let mut scanner = Scanner::new("\"a\" b ; comment\n");
+        let txt = Txt::scan(&mut scanner).unwrap();
+        assert_eq!(txt.iter().collect::<Vec<_>>(), [b"a", b"b"]);
+    }
+
+    #[test]
+    #[cfg(feature = "master")]
+    fn txt_scan_overflow() {
+        use crate::master::scan::{ScanError, Scanner, SyntaxError};
+        use std::string::String;
+
+        // The last phrase exceeds the maximum record data length.
+        let long: String = core::iter::repeat('a').take(65000).collect();
+        let zone = format!("\"a\" \"{}\" \"{}\"\n", long, &long[..1000]);
+        let mut scanner = Scanner::new(zone.as_str());
+        match Txt::scan(&mut scanner) {
+            Err(ScanError::Syntax(SyntaxError::LongGenericData, _)) => {}
+            res => panic!("expected long data error, got {:?}", res),
+        }
+    }
 }
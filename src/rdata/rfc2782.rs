@@ -225,8 +225,26 @@ impl<N: fmt::Display> fmt::Display for Srv<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{} {} {} {}",
+            "{} {} {} {}.",
             self.priority, self.weight, self.port, self.target
         )
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn srv_roundtrip() {
+        test_rdata_roundtrip!(
+            Srv<ParsedDname<&[u8]>>;
+            Srv<crate::base::Dname<bytes::Bytes>>,
+            b"\x00\x0a\x00\x05\x13\xc4\x03sip\x07example\x03com\x00",
+            "10 5 5060 sip.example.com."
+        );
+    }
+}
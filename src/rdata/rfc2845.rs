@@ -4,7 +4,7 @@
 //!
 //! [RFC 2845]: https://tools.ietf.org/html/rfc2845
 
-use crate::base::cmp::CanonicalOrd;
+use crate::base::cmp::{constant_time_eq, CanonicalOrd};
 use crate::base::iana::{Rtype, TsigRcode};
 use crate::base::name::{ParsedDname, ToDname};
 use crate::base::octets::{
@@ -120,6 +120,20 @@ impl<O, N> Tsig<O, N> {
         self.mac.as_ref()
     }
 
+    /// Returns whether the MAC equals the given MAC.
+    ///
+    /// The comparison is done in constant time. This method should be used
+    /// instead of comparing [`mac_slice`] directly whenever a MAC received
+    /// from a peer is checked.
+    ///
+    /// [`mac_slice`]: #method.mac_slice
+    pub fn verify_mac(&self, mac: &[u8]) -> bool
+    where
+        O: AsRef<[u8]>,
+    {
+        constant_time_eq(self.mac.as_ref(), mac)
+    }
+
     /// Converts the record data into the MAC.
     pub fn into_mac(self) -> O {
         self.mac
@@ -524,9 +538,7 @@ impl From<Time48> for u64 {
 
 impl<Ref: AsRef<[u8]>> Parse<Ref> for Time48 {
     fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
-        let mut buf = [0u8; 6];
-        parser.parse_buf(&mut buf)?;
-        Ok(Time48::from_slice(&buf))
+        parser.parse_u48().map(Time48)
     }
 
     fn skip(parser: &mut Parser<Ref>) -> Result<(), ParseError> {
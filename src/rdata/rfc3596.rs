@@ -75,7 +75,12 @@ impl CanonicalOrd for Aaaa {
     }
 }
 
-//--- Parse, ParseAll, and Compose
+//--- Parse and Compose
+//
+//    AAAA record data always is exactly 16 octets long. The record data
+//    length is enforced by `RecordHeader::parse_into_record` which fails
+//    if the data isn’t consumed completely or is too short, so there is
+//    no need to check it here.
 
 impl<Ref: AsRef<[u8]>> Parse<Ref> for Aaaa {
     fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
@@ -150,3 +155,78 @@ impl AsMut<Ipv6Addr> for Aaaa {
         &mut self.addr
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use crate::base::iana::Class;
+    use crate::base::name::{Dname, ParsedDname};
+    use crate::base::record::Record;
+    use core::str::FromStr;
+    use std::vec::Vec;
+
+    /// Returns the wire format of a root AAAA record with the given rdata.
+    fn wire_record(rdata: &[u8]) -> Vec<u8> {
+        let mut res = vec![0, 0, 28, 0, 1, 0, 0, 0x0e, 0x10];
+        res.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        res.extend_from_slice(rdata);
+        res
+    }
+
+    type AaaaRecord<'a> = Record<ParsedDname<&'a [u8]>, Aaaa>;
+
+    fn parse_record(
+        wire: &[u8],
+    ) -> Result<Option<AaaaRecord<'_>>, ParseError> {
+        let mut parser = Parser::from_ref(wire);
+        let res = Parse::parse(&mut parser);
+        assert_eq!(parser.remaining(), 0);
+        res
+    }
+
+    #[test]
+    fn compose_parse_aaaa() {
+        let aaaa = Aaaa::from_str("2001:db8::1").unwrap();
+        let mut rdata = Vec::new();
+        aaaa.compose(&mut rdata).unwrap();
+        assert_eq!(rdata.len(), 16);
+
+        let mut parser = Parser::from_ref(rdata.as_slice());
+        assert_eq!(Aaaa::parse(&mut parser).unwrap(), aaaa);
+        assert_eq!(parser.remaining(), 0);
+        let mut parser = Parser::from_ref(rdata.as_slice());
+        Aaaa::skip(&mut parser).unwrap();
+        assert_eq!(parser.remaining(), 0);
+
+        let wire = wire_record(&rdata);
+        let record = parse_record(&wire).unwrap().unwrap();
+        assert_eq!(record.owner(), &Dname::root_slice());
+        assert_eq!(record.class(), Class::In);
+        assert_eq!(record.ttl(), 3600);
+        assert_eq!(record.data(), &aaaa);
+
+        let mut composed = Vec::new();
+        record.compose(&mut composed).unwrap();
+        assert_eq!(composed, wire);
+    }
+
+    #[test]
+    fn aaaa_roundtrip() {
+        test_rdata_roundtrip!(
+            Aaaa,
+            b"\x20\x01\x0d\xb8\0\0\0\0\0\0\0\0\0\0\0\x01",
+            "2001:db8::1"
+        );
+        test_rdata_roundtrip!(Aaaa, &[0; 16], "::");
+    }
+
+    #[test]
+    fn parse_aaaa_bad_len() {
+        assert!(parse_record(&wire_record(&[0; 15])).is_err());
+        assert!(parse_record(&wire_record(&[0; 17])).is_err());
+        assert!(parse_record(&wire_record(&[])).is_err());
+    }
+}
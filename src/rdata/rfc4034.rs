@@ -14,14 +14,16 @@ use crate::base::octets::{
     OctetsRef, Parse, ParseError, Parser, ShortBuf,
 };
 use crate::base::rdata::RtypeRecordData;
-use crate::base::serial::Serial;
+use crate::base::serial::Timestamp;
 #[cfg(feature = "master")]
 use crate::master::scan::{CharSource, Scan, ScanError, Scanner};
 use crate::utils::base64;
+use crate::utils::hex::HexStr;
 #[cfg(feature = "master")]
 use bytes::{Bytes, BytesMut};
 use core::cmp::Ordering;
 use core::convert::TryInto;
+use core::iter::FromIterator;
 use core::{fmt, hash, ptr};
 #[cfg(feature = "std")]
 use std::vec::Vec;
@@ -119,39 +121,12 @@ impl<Octets> Dnskey<Octets> {
         Octets: AsRef<[u8]>,
     {
         if self.algorithm == SecAlg::RsaMd5 {
-            // The key tag is third-to-last and second-to-last octets of the
-            // key as a big-endian u16. If we don’t have enough octets in the
-            // key, we return 0.
-            let len = self.public_key.as_ref().len();
-            if len > 2 {
-                u16::from_be_bytes(
-                    self.public_key.as_ref()[len - 3..len - 1]
-                        .try_into()
-                        .unwrap(),
-                )
-            } else {
-                0
-            }
+            rsa_md5_key_tag(self.public_key.as_ref())
         } else {
-            // Treat record data as a octet sequence. Add octets at odd
-            // indexes as they are, add octets at even indexes shifted left
-            // by 8 bits.
             let mut res = u32::from(self.flags);
             res += u32::from(self.protocol) << 8;
             res += u32::from(self.algorithm.to_int());
-            let mut iter = self.public_key().as_ref().iter();
-            loop {
-                match iter.next() {
-                    Some(&x) => res += u32::from(x) << 8,
-                    None => break,
-                }
-                match iter.next() {
-                    Some(&x) => res += u32::from(x),
-                    None => break,
-                }
-            }
-            res += (res >> 16) & 0xFFFF;
-            (res & 0xFFFF) as u16
+            finish_key_tag(add_key_tag_octets(res, self.public_key.as_ref()))
         }
     }
 }
@@ -321,6 +296,63 @@ impl<Octets> RtypeRecordData for Dnskey<Octets> {
     const RTYPE: Rtype = Rtype::Dnskey;
 }
 
+//------------ dnskey_key_tag -----------------------------------------------
+
+/// Returns the key tag for the given DNSKEY record data.
+///
+/// The function calculates the key tag directly from the wire format of the
+/// record data as described in [RFC 4034, Appendix B], including the
+/// special case for algorithm 1 (RSA/MD5). This is useful if you only have
+/// the raw record data and don’t want to parse it into a [`Dnskey`] first.
+///
+/// [RFC 4034, Appendix B]: https://tools.ietf.org/html/rfc4034#appendix-B
+pub fn dnskey_key_tag(rdata: &[u8]) -> u16 {
+    if rdata.len() >= 4 && rdata[3] == SecAlg::RsaMd5.to_int() {
+        rsa_md5_key_tag(&rdata[4..])
+    } else {
+        finish_key_tag(add_key_tag_octets(0, rdata))
+    }
+}
+
+/// Returns the key tag for an RSA/MD5 public key.
+///
+/// The key tag is third-to-last and second-to-last octets of the key as a
+/// big-endian u16. If we don’t have enough octets in the key, we return 0.
+fn rsa_md5_key_tag(key: &[u8]) -> u16 {
+    let len = key.len();
+    if len > 2 {
+        u16::from_be_bytes(key[len - 3..len - 1].try_into().unwrap())
+    } else {
+        0
+    }
+}
+
+/// Adds octets to the key tag accumulator.
+///
+/// Treats the data as an octet sequence starting at an even index. Adds
+/// octets at odd indexes as they are, adds octets at even indexes shifted
+/// left by 8 bits.
+fn add_key_tag_octets(mut res: u32, data: &[u8]) -> u32 {
+    let mut iter = data.iter();
+    loop {
+        match iter.next() {
+            Some(&x) => res += u32::from(x) << 8,
+            None => break,
+        }
+        match iter.next() {
+            Some(&x) => res += u32::from(x),
+            None => break,
+        }
+    }
+    res
+}
+
+/// Folds the key tag accumulator into the final key tag.
+fn finish_key_tag(res: u32) -> u16 {
+    let res = res + ((res >> 16) & 0xFFFF);
+    (res & 0xFFFF) as u16
+}
+
 //------------ ProtoRrsig ----------------------------------------------------
 
 /// The RRSIG RDATA to be included when creating the signature.
@@ -330,8 +362,8 @@ pub struct ProtoRrsig<Name> {
     algorithm: SecAlg,
     labels: u8,
     original_ttl: u32,
-    expiration: Serial,
-    inception: Serial,
+    expiration: Timestamp,
+    inception: Timestamp,
     key_tag: u16,
     signer_name: Name,
 }
@@ -343,8 +375,8 @@ impl<Name> ProtoRrsig<Name> {
         algorithm: SecAlg,
         labels: u8,
         original_ttl: u32,
-        expiration: Serial,
-        inception: Serial,
+        expiration: Timestamp,
+        inception: Timestamp,
         key_tag: u16,
         signer_name: Name,
     ) -> Self {
@@ -442,8 +474,8 @@ pub struct Rrsig<Octets, Name> {
     algorithm: SecAlg,
     labels: u8,
     original_ttl: u32,
-    expiration: Serial,
-    inception: Serial,
+    expiration: Timestamp,
+    inception: Timestamp,
     key_tag: u16,
     signer_name: Name,
     signature: Octets,
@@ -456,8 +488,8 @@ impl<Octets, Name> Rrsig<Octets, Name> {
         algorithm: SecAlg,
         labels: u8,
         original_ttl: u32,
-        expiration: Serial,
-        inception: Serial,
+        expiration: Timestamp,
+        inception: Timestamp,
         key_tag: u16,
         signer_name: Name,
         signature: Octets,
@@ -491,11 +523,11 @@ impl<Octets, Name> Rrsig<Octets, Name> {
         self.original_ttl
     }
 
-    pub fn expiration(&self) -> Serial {
+    pub fn expiration(&self) -> Timestamp {
         self.expiration
     }
 
-    pub fn inception(&self) -> Serial {
+    pub fn inception(&self) -> Timestamp {
         self.inception
     }
 
@@ -692,8 +724,8 @@ impl<Ref: OctetsRef> Parse<Ref> for Rrsig<Ref::Range, ParsedDname<Ref>> {
         let algorithm = SecAlg::parse(parser)?;
         let labels = u8::parse(parser)?;
         let original_ttl = u32::parse(parser)?;
-        let expiration = Serial::parse(parser)?;
-        let inception = Serial::parse(parser)?;
+        let expiration = Timestamp::parse(parser)?;
+        let inception = Timestamp::parse(parser)?;
         let key_tag = u16::parse(parser)?;
         let signer_name = ParsedDname::parse(parser)?;
         let len = parser.remaining();
@@ -716,8 +748,8 @@ impl<Ref: OctetsRef> Parse<Ref> for Rrsig<Ref::Range, ParsedDname<Ref>> {
         SecAlg::skip(parser)?;
         u8::skip(parser)?;
         u32::skip(parser)?;
-        Serial::skip(parser)?;
-        Serial::skip(parser)?;
+        Timestamp::skip(parser)?;
+        Timestamp::skip(parser)?;
         u16::skip(parser)?;
         ParsedDname::skip(parser)?;
         parser.advance_to_end();
@@ -773,8 +805,8 @@ impl Scan for Rrsig<Bytes, Dname<Bytes>> {
             SecAlg::scan(scanner)?,
             u8::scan(scanner)?,
             u32::scan(scanner)?,
-            Serial::scan_rrsig(scanner)?,
-            Serial::scan_rrsig(scanner)?,
+            Timestamp::scan(scanner)?,
+            Timestamp::scan(scanner)?,
             u16::scan(scanner)?,
             Dname::scan(scanner)?,
             scanner.scan_base64_phrases(Ok)?,
@@ -856,6 +888,29 @@ impl<Octets, Name> Nsec<Octets, Name> {
     pub fn types(&self) -> &RtypeBitmap<Octets> {
         &self.types
     }
+
+    /// Returns whether the NSEC record covers the given name.
+    ///
+    /// A name is covered if it sorts strictly between `owner`, the owner
+    /// name of the NSEC record, and its next name in canonical order. Such
+    /// a name does not exist in the zone. If the next name doesn’t sort
+    /// after the owner, the record is the last one of the zone and its next
+    /// name is the zone apex. In this case, all names in the zone sorting
+    /// after the owner are covered.
+    pub fn covers<O, N>(&self, owner: &O, name: &N) -> bool
+    where
+        Name: ToDname,
+        O: ToDname,
+        N: ToDname,
+    {
+        if owner.name_cmp(&self.next_name) == Ordering::Less {
+            owner.name_cmp(name) == Ordering::Less
+                && name.name_cmp(&self.next_name) == Ordering::Less
+        } else {
+            owner.name_cmp(name) == Ordering::Less
+                && name.ends_with(&self.next_name)
+        }
+    }
 }
 
 //--- OctetsFrom
@@ -1225,13 +1280,12 @@ impl<Octets: AsRef<[u8]>> fmt::Display for Ds<Octets> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{} {} {} ",
-            self.key_tag, self.algorithm, self.digest_type
-        )?;
-        for ch in self.digest.as_ref() {
-            write!(f, "{:02x}", ch)?
-        }
-        Ok(())
+            "{} {} {} {}",
+            self.key_tag,
+            self.algorithm,
+            self.digest_type,
+            HexStr::new(self.digest.as_ref())
+        )
     }
 }
 
@@ -1298,6 +1352,23 @@ impl<Octets> RtypeBitmap<Octets> {
         RtypeBitmapBuilder::new()
     }
 
+    /// Creates a bitmap from the record types produced by an iterator.
+    ///
+    /// The record types can be given in any order and may contain
+    /// duplicates.
+    pub fn from_rtypes<I>(iter: I) -> Result<Self, ShortBuf>
+    where
+        Octets: FromBuilder,
+        <Octets as FromBuilder>::Builder: EmptyBuilder,
+        I: IntoIterator<Item = Rtype>,
+    {
+        let mut builder = Self::builder();
+        for rtype in iter {
+            builder.add(rtype)?;
+        }
+        Ok(builder.finalize())
+    }
+
     pub fn as_octets(&self) -> &Octets {
         &self.0
     }
@@ -1333,6 +1404,26 @@ impl<Octets: AsRef<[u8]>> RtypeBitmap<Octets> {
     }
 }
 
+//--- FromIterator
+
+impl<Octets> FromIterator<Rtype> for RtypeBitmap<Octets>
+where
+    Octets: FromBuilder,
+    <Octets as FromBuilder>::Builder: EmptyBuilder,
+{
+    /// Creates a bitmap from the record types produced by an iterator.
+    ///
+    /// # Panics
+    ///
+    /// The function panics if the underlying octets builder runs out of
+    /// space. Use [`RtypeBitmap::from_rtypes`] if this can happen.
+    ///
+    /// [`RtypeBitmap::from_rtypes`]: #method.from_rtypes
+    fn from_iter<I: IntoIterator<Item = Rtype>>(iter: I) -> Self {
+        Self::from_rtypes(iter).expect("bitmap exceeds octets builder")
+    }
+}
+
 //--- AsRef
 
 impl<T, Octets: AsRef<T>> AsRef<T> for RtypeBitmap<Octets> {
@@ -1743,6 +1834,34 @@ mod test {
     use super::*;
     use crate::base::iana::Rtype;
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn ds_roundtrip() {
+        test_rdata_roundtrip!(
+            Ds<&[u8]>;
+            Ds<bytes::Bytes>,
+            b"\xec\x45\x05\x01\x2b\xb1\x83\xaf\x5f\x22\x58\x81\x79\xa5\
+              \x3b\x0a\x98\x63\x1f\xad\x1a\x29\x21\x18",
+            "60485 RSASHA1 1 2BB183AF5F22588179A53B0A98631FAD1A292118"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "master")]
+    fn ds_scan_wrapped_digest() {
+        use crate::master::scan::{Scan, Scanner};
+
+        let mut scanner = Scanner::new(
+            "60485 5 1 ( 2BB183AF5F22588179A53B0A\n\t98631FAD1A292118 )",
+        );
+        let ds = Ds::<bytes::Bytes>::scan(&mut scanner).unwrap();
+        assert_eq!(
+            ds.digest().as_ref(),
+            b"\x2b\xb1\x83\xaf\x5f\x22\x58\x81\x79\xa5\x3b\x0a\
+              \x98\x63\x1f\xad\x1a\x29\x21\x18"
+        );
+    }
+
     #[test]
     fn rtype_split() {
         assert_eq!(split_rtype(Rtype::A), (0, 0, 0b01000000));
@@ -1766,6 +1885,52 @@ mod test {
         assert!(read_window(data).is_none());
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn nsec_covers() {
+        use crate::base::name::Dname;
+        use core::str::FromStr;
+
+        let name = |s| Dname::<Vec<u8>>::from_str(s).unwrap();
+        let types = RtypeBitmap::<Vec<u8>>::from_rtypes(Some(Rtype::A));
+
+        let alpha = name("alpha.example.com");
+        let nsec = Nsec::new(name("gamma.example.com"), types.unwrap());
+        assert!(nsec.covers(&alpha, &name("beta.example.com")));
+        assert!(nsec.covers(&alpha, &name("foo.beta.example.com")));
+        // Canonical order is by octets, so delta sorts before gamma.
+        assert!(nsec.covers(&alpha, &name("delta.example.com")));
+        assert!(!nsec.covers(&alpha, &name("zeta.example.com")));
+        assert!(!nsec.covers(&alpha, &name("alpha.example.com")));
+        assert!(!nsec.covers(&alpha, &name("gamma.example.com")));
+        assert!(!nsec.covers(&alpha, &name("example.com")));
+
+        // The last NSEC of the zone wraps around to the apex.
+        let zeta = name("zeta.example.com");
+        let nsec = Nsec::new(name("example.com"), nsec.types().clone());
+        assert!(nsec.covers(&zeta, &name("zz.example.com")));
+        assert!(nsec.covers(&zeta, &name("a.zeta.example.com")));
+        assert!(!nsec.covers(&zeta, &name("beta.example.com")));
+        assert!(!nsec.covers(&zeta, &name("zz.example.org")));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rtype_bitmap_from_iter() {
+        let bitmap: RtypeBitmap<Vec<u8>> =
+            vec![Rtype::Caa, Rtype::Nsec, Rtype::A, Rtype::Rrsig, Rtype::A]
+                .into_iter()
+                .collect();
+        assert_eq!(
+            bitmap.as_slice(),
+            b"\x00\x06\x40\x00\x00\x00\x00\x03\x01\x01\x40"
+        );
+        assert_eq!(
+            bitmap.iter().collect::<Vec<_>>(),
+            [Rtype::A, Rtype::Rrsig, Rtype::Nsec, Rtype::Caa]
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn rtype_bitmap_builder() {
@@ -1880,6 +2045,49 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn dnskey_key_tag_raw() {
+        fn rdata(flags: u16, alg: SecAlg, key: &str) -> Vec<u8> {
+            let mut res = flags.to_be_bytes().to_vec();
+            res.push(3);
+            res.push(alg.to_int());
+            res.extend_from_slice(&base64::decode(key).unwrap());
+            res
+        }
+
+        assert_eq!(
+            super::dnskey_key_tag(&rdata(
+                257,
+                SecAlg::RsaSha256,
+                "AwEAAaz/tAm8yTn4Mfeh5eyI96WSVexTBAvkMgJzkKTO\
+                iW1vkIbzxeF3+/4RgWOq7HrxRixHlFlExOLAJr5emLvN\
+                7SWXgnLh4+B5xQlNVz8Og8kvArMtNROxVQuCaSnIDdD5\
+                LKyWbRd2n9WGe2R8PzgCmr3EgVLrjyBxWezF0jLHwVN8\
+                efS3rCj/EWgvIWgb9tarpVUDK/b58Da+sqqls3eNbuv7\
+                pr+eoZG+SrDK6nWeL3c6H5Apxz7LjVc1uTIdsIXxuOLY\
+                A4/ilBmSVIzuDWfdRUfhHdY6+cn8HFRm+2hM8AnXGXws\
+                9555KrUB5qihylGa8subX2Nn6UwNR1AkUTV74bU="
+            )),
+            20326
+        );
+        assert_eq!(
+            super::dnskey_key_tag(&rdata(
+                257,
+                SecAlg::RsaMd5,
+                "AwEAAcVaA4jSBIGRrSzpecoJELvKE9+OMuFnL8mmUBsY\
+                lB6epN1CqX7NzwjDpi6VySiEXr0C4uTYkU/L1uMv2mHE\
+                AljThFDJ1GuozJ6gA7jf3lnaGppRg2IoVQ9IVmLORmjw\
+                C+7Eoi12SqybMTicD3Ezwa9XbG1iPjmjhbMrLh7MSQpX"
+            )),
+            18698
+        );
+
+        // Short data doesn’t panic.
+        assert_eq!(super::dnskey_key_tag(b""), 0);
+        assert_eq!(super::dnskey_key_tag(b"\x01\x01\x03\x01\xAB"), 0);
+    }
+
     #[test]
     #[cfg(feature = "bytes")]
     fn dnskey_flags() {
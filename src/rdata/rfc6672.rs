@@ -38,4 +38,14 @@ mod test {
         let rdata = rfc6672::Dname::new(name.clone());
         assert_eq!(rdata.dname(), &name);
     }
+
+    #[test]
+    fn dname_roundtrip() {
+        test_rdata_roundtrip!(
+            rfc6672::Dname<crate::base::name::ParsedDname<&[u8]>>;
+            rfc6672::Dname<Dname<bytes::Bytes>>,
+            b"\x03bar\x07example\x03com\x00",
+            "bar.example.com."
+        );
+    }
 }
@@ -11,6 +11,7 @@ use crate::base::rdata::RtypeRecordData;
 #[cfg(feature = "master")]
 use crate::master::scan::{CharSource, Scan, ScanError, Scanner};
 use crate::utils::base64;
+use crate::utils::hex::HexStr;
 #[cfg(feature = "master")]
 use bytes::Bytes;
 use core::cmp::Ordering;
@@ -424,13 +425,12 @@ impl<Octets: AsRef<[u8]>> fmt::Display for Cds<Octets> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{} {} {} ",
-            self.key_tag, self.algorithm, self.digest_type
-        )?;
-        for ch in self.digest.as_ref() {
-            write!(f, "{:02x}", ch)?
-        }
-        Ok(())
+            "{} {} {} {}",
+            self.key_tag,
+            self.algorithm,
+            self.digest_type,
+            HexStr::new(self.digest.as_ref())
+        )
     }
 }
 
@@ -0,0 +1,1145 @@
+//! Record data from [RFC 9460]: SVCB and HTTPS records.
+//!
+//! This RFC defines two record types for service binding: the
+//! general-purpose SVCB record and the HTTPS record specific to HTTP.
+//! Both share the same record data format consisting of a priority, a
+//! target name, and a list of service parameters. The latter is provided
+//! via the [`SvcParams`] type.
+//!
+//! [RFC 9460]: https://tools.ietf.org/html/rfc9460
+
+use crate::base::cmp::CanonicalOrd;
+use crate::base::iana::{Rtype, SvcParamKey};
+use crate::base::name::{ParsedDname, ToDname};
+use crate::base::net::{Ipv4Addr, Ipv6Addr};
+#[cfg(feature = "std")]
+use crate::base::octets::EmptyBuilder;
+use crate::base::octets::{
+    Compose, OctetsBuilder, OctetsFrom, OctetsRef, Parse, ParseError, Parser,
+    ShortBuf,
+};
+use crate::base::rdata::RtypeRecordData;
+use crate::utils::base64;
+use core::cmp::Ordering;
+use core::{fmt, hash};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+//------------ Svcb and Https ------------------------------------------------
+
+macro_rules! svcb_rdata {
+    ( $(#[$attr:meta])* ( $target:ident, $rtype:ident ) ) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $target<O, N> {
+            priority: u16,
+            target: N,
+            params: SvcParams<O>,
+        }
+
+        impl<O, N> $target<O, N> {
+            /// Creates new record data from its components.
+            pub fn new(priority: u16, target: N, params: SvcParams<O>) -> Self {
+                $target { priority, target, params }
+            }
+
+            /// Returns the priority of the record.
+            ///
+            /// A priority of zero marks the record as being in alias mode.
+            /// Otherwise, the record is in service mode and records with a
+            /// lower priority are preferred.
+            pub fn priority(&self) -> u16 {
+                self.priority
+            }
+
+            /// Returns whether the record is in alias mode.
+            pub fn is_alias(&self) -> bool {
+                self.priority == 0
+            }
+
+            /// Returns the target name of the record.
+            pub fn target(&self) -> &N {
+                &self.target
+            }
+
+            /// Returns the service parameters of the record.
+            pub fn params(&self) -> &SvcParams<O> {
+                &self.params
+            }
+        }
+
+        impl<O: AsRef<[u8]>, N> $target<O, N> {
+            /// Checks that the service parameters are valid.
+            ///
+            /// See [`SvcParams::validate`] for the rules that are checked.
+            pub fn validate(&self) -> Result<(), SvcbError> {
+                self.params.validate()
+            }
+        }
+
+        //--- OctetsFrom
+
+        impl<Octets, SrcOctets, Name, SrcName>
+            OctetsFrom<$target<SrcOctets, SrcName>> for $target<Octets, Name>
+        where
+            Octets: OctetsFrom<SrcOctets>,
+            Name: OctetsFrom<SrcName>,
+        {
+            fn octets_from(
+                source: $target<SrcOctets, SrcName>
+            ) -> Result<Self, ShortBuf> {
+                Ok($target::new(
+                    source.priority,
+                    Name::octets_from(source.target)?,
+                    SvcParams::octets_from(source.params)?,
+                ))
+            }
+        }
+
+        //--- PartialEq and Eq
+
+        impl<O, OO, N, NN> PartialEq<$target<OO, NN>> for $target<O, N>
+        where
+            O: AsRef<[u8]>,
+            OO: AsRef<[u8]>,
+            N: ToDname,
+            NN: ToDname,
+        {
+            fn eq(&self, other: &$target<OO, NN>) -> bool {
+                self.priority == other.priority
+                    && self.target.name_eq(&other.target)
+                    && self.params == other.params
+            }
+        }
+
+        impl<O: AsRef<[u8]>, N: ToDname> Eq for $target<O, N> {}
+
+        //--- PartialOrd, Ord, and CanonicalOrd
+
+        impl<O, OO, N, NN> PartialOrd<$target<OO, NN>> for $target<O, N>
+        where
+            O: AsRef<[u8]>,
+            OO: AsRef<[u8]>,
+            N: ToDname,
+            NN: ToDname,
+        {
+            fn partial_cmp(
+                &self,
+                other: &$target<OO, NN>
+            ) -> Option<Ordering> {
+                Some(self.canonical_cmp(other))
+            }
+        }
+
+        impl<O: AsRef<[u8]>, N: ToDname> Ord for $target<O, N> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.canonical_cmp(other)
+            }
+        }
+
+        impl<O, OO, N, NN> CanonicalOrd<$target<OO, NN>> for $target<O, N>
+        where
+            O: AsRef<[u8]>,
+            OO: AsRef<[u8]>,
+            N: ToDname,
+            NN: ToDname,
+        {
+            fn canonical_cmp(&self, other: &$target<OO, NN>) -> Ordering {
+                match self.priority.cmp(&other.priority) {
+                    Ordering::Equal => {}
+                    other => return other,
+                }
+                // The target name is not converted to lowercase in the
+                // canonical form, so compare it as composed.
+                match self.target.composed_cmp(&other.target) {
+                    Ordering::Equal => {}
+                    other => return other,
+                }
+                self.params.as_slice().cmp(other.params.as_slice())
+            }
+        }
+
+        //--- Hash
+
+        impl<O: AsRef<[u8]>, N: hash::Hash> hash::Hash for $target<O, N> {
+            fn hash<H: hash::Hasher>(&self, state: &mut H) {
+                self.priority.hash(state);
+                self.target.hash(state);
+                self.params.hash(state);
+            }
+        }
+
+        //--- Parse and Compose
+
+        impl<Ref: OctetsRef> Parse<Ref>
+            for $target<Ref::Range, ParsedDname<Ref>>
+        {
+            fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
+                let priority = u16::parse(parser)?;
+                let target = ParsedDname::parse(parser)?;
+                let params = SvcParams::parse(parser)?;
+                Ok($target::new(priority, target, params))
+            }
+
+            fn skip(parser: &mut Parser<Ref>) -> Result<(), ParseError> {
+                u16::skip(parser)?;
+                ParsedDname::skip(parser)?;
+                SvcParams::skip(parser)
+            }
+        }
+
+        impl<O: AsRef<[u8]>, N: Compose> Compose for $target<O, N> {
+            fn compose<T: OctetsBuilder>(
+                &self,
+                target: &mut T,
+            ) -> Result<(), ShortBuf> {
+                target.append_all(|buf| {
+                    self.priority.compose(buf)?;
+                    self.target.compose(buf)?;
+                    self.params.compose(buf)
+                })
+            }
+        }
+
+        //--- Display and Debug
+
+        impl<O: AsRef<[u8]>, N: fmt::Display> fmt::Display for $target<O, N> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{} {}.", self.priority, self.target)?;
+                if !self.params.as_slice().is_empty() {
+                    write!(f, " {}", self.params)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl<O: AsRef<[u8]>, N: fmt::Debug> fmt::Debug for $target<O, N> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_struct(stringify!($target))
+                    .field("priority", &self.priority)
+                    .field("target", &self.target)
+                    .field("params", &self.params)
+                    .finish()
+            }
+        }
+
+        //--- RtypeRecordData
+
+        impl<O, N> RtypeRecordData for $target<O, N> {
+            const RTYPE: Rtype = Rtype::$rtype;
+        }
+    }
+}
+
+svcb_rdata! {
+    /// SVCB record data.
+    ///
+    /// The general-purpose service binding record provides the endpoints
+    /// and parameters for accessing a service. Which service that is,
+    /// is determined by the owner name of the record.
+    (Svcb, Svcb)
+}
+
+svcb_rdata! {
+    /// HTTPS record data.
+    ///
+    /// The HTTPS record is the variant of the [SVCB record][Svcb] used for
+    /// HTTP origins. Its record data is identical.
+    (Https, Https)
+}
+
+//------------ SvcParams -----------------------------------------------------
+
+/// The service parameters of an SVCB or HTTPS record.
+///
+/// In wire format, the parameters are a sequence of key-value pairs. Each
+/// pair consists of a 16 bit key, a 16 bit length, and that many octets of
+/// value. The type wraps an octets sequence containing such a sequence and
+/// guarantees that it is correctly formatted and that the keys are in
+/// strictly ascending order as required by the RFC. It does not, however,
+/// guarantee that the values themselves are correct. Because of this,
+/// composing the parameters always produces keys in the correct order.
+///
+/// You can iterate over all parameters via [`iter`][Self::iter] or get the
+/// raw value of a certain parameter via [`get`][Self::get]. For the
+/// parameters defined in the RFC, there are methods returning their
+/// values in a more useful form.
+///
+/// New parameters can be created via [`SvcParamsBuilder`] which makes sure
+/// that the keys are in the correct order.
+///
+/// The `Display` implementation formats the parameters in presentation
+/// format separated by a single space. Values that are malformed for their
+/// key are given in the generic quoted form.
+#[derive(Clone, Default)]
+pub struct SvcParams<O> {
+    octets: O,
+}
+
+impl<O: AsRef<[u8]>> SvcParams<O> {
+    /// Creates service parameters from an octets sequence.
+    ///
+    /// The function checks that the octets contain a correctly formatted
+    /// sequence of parameters and that their keys are strictly ascending,
+    /// i.e., that they are sorted and no key appears more than once.
+    pub fn from_octets(octets: O) -> Result<Self, ParseError> {
+        let mut parser = Parser::from_ref(octets.as_ref());
+        let mut last = None;
+        while parser.remaining() > 0 {
+            let key = SvcParamKey::parse(&mut parser)?;
+            if last.map(|last| key <= last).unwrap_or(false) {
+                return Err(ParseError::form_error(
+                    "service parameter keys not strictly ascending",
+                ));
+            }
+            last = Some(key);
+            let len = parser.parse_u16()?;
+            parser.advance(len.into())?;
+        }
+        Ok(SvcParams { octets })
+    }
+
+    /// Returns a reference to the underlying octets sequence.
+    pub fn as_octets(&self) -> &O {
+        &self.octets
+    }
+
+    /// Returns a slice of the underlying octets.
+    pub fn as_slice(&self) -> &[u8] {
+        self.octets.as_ref()
+    }
+
+    /// Returns whether there are no parameters at all.
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Returns an iterator over the keys and raw values of all parameters.
+    pub fn iter(&self) -> SvcParamIter<'_> {
+        SvcParamIter {
+            octets: self.as_slice(),
+        }
+    }
+
+    /// Returns the raw value of the parameter with the given key.
+    ///
+    /// Returns `None` if there is no such parameter.
+    pub fn get(&self, key: SvcParamKey) -> Option<&[u8]> {
+        self.iter()
+            .find(|&(item_key, _)| item_key == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns whether a parameter with the given key is present.
+    pub fn contains(&self, key: SvcParamKey) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Checks that the parameters follow the rules of the RFC.
+    ///
+    /// The order of the keys is already guaranteed by the type itself. In
+    /// addition, if there is a `mandatory` parameter, its value must be a
+    /// correctly formatted, strictly ascending list of keys that doesn’t
+    /// include `mandatory` itself, and each of these keys must be present
+    /// in the parameters.
+    ///
+    /// The values of the other parameters are not checked.
+    pub fn validate(&self) -> Result<(), SvcbError> {
+        let value = match self.get(SvcParamKey::Mandatory) {
+            Some(value) => value,
+            None => return Ok(()),
+        };
+        let keys = mandatory_keys(value).ok_or(SvcbError::BadMandatory)?;
+        let mut last = None;
+        for key in keys {
+            if key == SvcParamKey::Mandatory
+                || last.map(|last| key <= last).unwrap_or(false)
+            {
+                return Err(SvcbError::BadMandatory);
+            }
+            if !self.contains(key) {
+                return Err(SvcbError::MissingMandatoryKey(key));
+            }
+            last = Some(key);
+        }
+        Ok(())
+    }
+
+    /// Returns the keys listed in the `mandatory` parameter.
+    ///
+    /// Returns `None` if there is no such parameter or if its value is
+    /// malformed.
+    #[cfg(feature = "std")]
+    pub fn mandatory(&self) -> Option<Vec<SvcParamKey>> {
+        self.get(SvcParamKey::Mandatory)
+            .and_then(mandatory_keys)
+            .map(Iterator::collect)
+    }
+
+    /// Returns the protocol identifiers listed in the `alpn` parameter.
+    ///
+    /// Returns `None` if there is no such parameter or if its value is
+    /// malformed.
+    #[cfg(feature = "std")]
+    pub fn alpn(&self) -> Option<Vec<&[u8]>> {
+        self.get(SvcParamKey::Alpn)
+            .and_then(alpn_ids)
+            .map(Iterator::collect)
+    }
+
+    /// Returns whether the `no-default-alpn` parameter is present.
+    pub fn no_default_alpn(&self) -> bool {
+        self.contains(SvcParamKey::NoDefaultAlpn)
+    }
+
+    /// Returns the value of the `port` parameter.
+    ///
+    /// Returns `None` if there is no such parameter or if its value is
+    /// malformed.
+    pub fn port(&self) -> Option<u16> {
+        self.get(SvcParamKey::Port).and_then(port)
+    }
+
+    /// Returns the addresses listed in the `ipv4hint` parameter.
+    ///
+    /// Returns `None` if there is no such parameter or if its value is
+    /// malformed.
+    #[cfg(feature = "std")]
+    pub fn ipv4hint(&self) -> Option<Vec<Ipv4Addr>> {
+        self.get(SvcParamKey::Ipv4Hint)
+            .and_then(ipv4_hints)
+            .map(Iterator::collect)
+    }
+
+    /// Returns the addresses listed in the `ipv6hint` parameter.
+    ///
+    /// Returns `None` if there is no such parameter or if its value is
+    /// malformed.
+    #[cfg(feature = "std")]
+    pub fn ipv6hint(&self) -> Option<Vec<Ipv6Addr>> {
+        self.get(SvcParamKey::Ipv6Hint)
+            .and_then(ipv6_hints)
+            .map(Iterator::collect)
+    }
+}
+
+//--- Parse and Compose
+//
+//    Since the parameters have no length of their own, they always take up
+//    the rest of the record data, so parsing consumes all remaining data.
+
+impl<Ref: OctetsRef> Parse<Ref> for SvcParams<Ref::Range> {
+    fn parse(parser: &mut Parser<Ref>) -> Result<Self, ParseError> {
+        let len = parser.remaining();
+        SvcParams::from_octets(parser.parse_octets(len)?)
+    }
+
+    fn skip(parser: &mut Parser<Ref>) -> Result<(), ParseError> {
+        Self::parse(parser).map(|_| ())
+    }
+}
+
+impl<O: AsRef<[u8]>> Compose for SvcParams<O> {
+    fn compose<T: OctetsBuilder>(
+        &self,
+        target: &mut T,
+    ) -> Result<(), ShortBuf> {
+        target.append_slice(self.as_slice())
+    }
+}
+
+//--- OctetsFrom
+
+impl<Octets, SrcOctets> OctetsFrom<SvcParams<SrcOctets>> for SvcParams<Octets>
+where
+    Octets: OctetsFrom<SrcOctets>,
+{
+    fn octets_from(source: SvcParams<SrcOctets>) -> Result<Self, ShortBuf> {
+        Ok(SvcParams {
+            octets: Octets::octets_from(source.octets)?,
+        })
+    }
+}
+
+//--- PartialEq and Eq
+
+impl<O, OO> PartialEq<SvcParams<OO>> for SvcParams<O>
+where
+    O: AsRef<[u8]>,
+    OO: AsRef<[u8]>,
+{
+    fn eq(&self, other: &SvcParams<OO>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<O: AsRef<[u8]>> Eq for SvcParams<O> {}
+
+//--- Hash
+
+impl<O: AsRef<[u8]>> hash::Hash for SvcParams<O> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+//--- Display and Debug
+
+impl<O: AsRef<[u8]>> fmt::Display for SvcParams<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for (key, value) in self.iter() {
+            if first {
+                first = false;
+            } else {
+                f.write_str(" ")?;
+            }
+            fmt_param(key, value, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<O: AsRef<[u8]>> fmt::Debug for SvcParams<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SvcParams({})", self)
+    }
+}
+
+//------------ SvcParamIter --------------------------------------------------
+
+/// An iterator over the service parameters.
+///
+/// The iterator returns pairs of the key and the raw value of each
+/// parameter in the order they appear in the record data.
+#[derive(Clone, Debug)]
+pub struct SvcParamIter<'a> {
+    octets: &'a [u8],
+}
+
+impl<'a> Iterator for SvcParamIter<'a> {
+    type Item = (SvcParamKey, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.octets.len() < 4 {
+            return None;
+        }
+        let key = u16::from_be_bytes([self.octets[0], self.octets[1]]);
+        let len =
+            usize::from(u16::from_be_bytes([self.octets[2], self.octets[3]]));
+        let value = self.octets.get(4..4 + len)?;
+        self.octets = &self.octets[4 + len..];
+        Some((SvcParamKey::from_int(key), value))
+    }
+}
+
+//------------ SvcParamsBuilder ----------------------------------------------
+
+/// A builder for service parameters.
+///
+/// Parameters can be added in any order. When building the final
+/// [`SvcParams`] via [`finish`][Self::finish] or composing them via
+/// [`compose`][Self::compose], the parameters are placed in strictly
+/// ascending order of their keys as required by the RFC. Adding a parameter
+/// with a key that is already present replaces the earlier value.
+///
+/// In addition to the methods for the parameters defined in the RFC, any
+/// parameter can be added as a raw value via [`push_raw`][Self::push_raw].
+/// This can be used to preserve unknown parameters.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct SvcParamsBuilder {
+    params: BTreeMap<SvcParamKey, Vec<u8>>,
+}
+
+#[cfg(feature = "std")]
+impl SvcParamsBuilder {
+    /// Creates a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder containing the given parameters.
+    pub fn from_params<O: AsRef<[u8]>>(params: &SvcParams<O>) -> Self {
+        let mut res = Self::new();
+        for (key, value) in params.iter() {
+            res.params.insert(key, value.into());
+        }
+        res
+    }
+
+    /// Adds a parameter with a raw value.
+    ///
+    /// Returns an error if the value is longer than 65,535 octets.
+    pub fn push_raw(
+        &mut self,
+        key: SvcParamKey,
+        value: &[u8],
+    ) -> Result<(), SvcParamError> {
+        if value.len() > usize::from(u16::MAX) {
+            return Err(SvcParamError::LongValue);
+        }
+        self.params.insert(key, value.into());
+        Ok(())
+    }
+
+    /// Removes the parameter with the given key.
+    ///
+    /// Returns whether there was such a parameter.
+    pub fn remove(&mut self, key: SvcParamKey) -> bool {
+        self.params.remove(&key).is_some()
+    }
+
+    /// Sets the keys of the `mandatory` parameter.
+    ///
+    /// The keys are stored in ascending order as required by the RFC.
+    pub fn mandatory(
+        &mut self,
+        keys: &[SvcParamKey],
+    ) -> Result<(), SvcParamError> {
+        let mut keys = keys.to_vec();
+        keys.sort();
+        let mut value = Vec::with_capacity(keys.len() * 2);
+        for key in keys {
+            value.extend_from_slice(&key.to_int().to_be_bytes());
+        }
+        self.push_raw(SvcParamKey::Mandatory, &value)
+    }
+
+    /// Sets the protocol identifiers of the `alpn` parameter.
+    ///
+    /// Returns an error if an identifier is empty or longer than 255
+    /// octets.
+    pub fn alpn(&mut self, ids: &[&[u8]]) -> Result<(), SvcParamError> {
+        let mut value = Vec::new();
+        for id in ids {
+            if id.is_empty() || id.len() > 255 {
+                return Err(SvcParamError::BadAlpnId);
+            }
+            value.push(id.len() as u8);
+            value.extend_from_slice(id);
+        }
+        self.push_raw(SvcParamKey::Alpn, &value)
+    }
+
+    /// Adds the `no-default-alpn` parameter.
+    pub fn no_default_alpn(&mut self) {
+        self.params.insert(SvcParamKey::NoDefaultAlpn, Vec::new());
+    }
+
+    /// Sets the `port` parameter.
+    pub fn port(&mut self, port: u16) {
+        self.params
+            .insert(SvcParamKey::Port, port.to_be_bytes().into());
+    }
+
+    /// Sets the addresses of the `ipv4hint` parameter.
+    pub fn ipv4hint(
+        &mut self,
+        addrs: &[Ipv4Addr],
+    ) -> Result<(), SvcParamError> {
+        let mut value = Vec::with_capacity(addrs.len() * 4);
+        for addr in addrs {
+            value.extend_from_slice(&addr.octets());
+        }
+        self.push_raw(SvcParamKey::Ipv4Hint, &value)
+    }
+
+    /// Sets the addresses of the `ipv6hint` parameter.
+    pub fn ipv6hint(
+        &mut self,
+        addrs: &[Ipv6Addr],
+    ) -> Result<(), SvcParamError> {
+        let mut value = Vec::with_capacity(addrs.len() * 16);
+        for addr in addrs {
+            value.extend_from_slice(&addr.octets());
+        }
+        self.push_raw(SvcParamKey::Ipv6Hint, &value)
+    }
+
+    /// Appends the wire format of the parameters to a target.
+    ///
+    /// The parameters are appended in ascending order of their keys.
+    pub fn compose<T: OctetsBuilder>(
+        &self,
+        target: &mut T,
+    ) -> Result<(), ShortBuf> {
+        target.append_all(|target| {
+            for (key, value) in &self.params {
+                key.compose(target)?;
+                (value.len() as u16).compose(target)?;
+                target.append_slice(value)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Finishes building and returns the service parameters.
+    pub fn finish<Target>(
+        &self,
+    ) -> Result<SvcParams<Target::Octets>, ShortBuf>
+    where
+        Target: EmptyBuilder + OctetsBuilder,
+    {
+        let mut target = Target::empty();
+        self.compose(&mut target)?;
+        Ok(SvcParams {
+            octets: target.freeze(),
+        })
+    }
+}
+
+//------------ Helper Functions ----------------------------------------------
+
+/// Returns an iterator over the keys of a `mandatory` value.
+///
+/// Returns `None` if the value is malformed.
+fn mandatory_keys(
+    value: &[u8],
+) -> Option<impl Iterator<Item = SvcParamKey> + '_> {
+    let chunks = value.chunks_exact(2);
+    if value.is_empty() || !chunks.remainder().is_empty() {
+        return None;
+    }
+    Some(chunks.map(|chunk| {
+        SvcParamKey::from_int(u16::from_be_bytes([chunk[0], chunk[1]]))
+    }))
+}
+
+/// Returns an iterator over the protocol identifiers of an `alpn` value.
+///
+/// Returns `None` if the value is malformed.
+fn alpn_ids(value: &[u8]) -> Option<impl Iterator<Item = &[u8]>> {
+    let mut tail = value;
+    while let Some((&len, rest)) = tail.split_first() {
+        if len == 0 || rest.len() < usize::from(len) {
+            return None;
+        }
+        tail = &rest[usize::from(len)..];
+    }
+    if value.is_empty() {
+        return None;
+    }
+    let mut tail = value;
+    Some(core::iter::from_fn(move || {
+        let (&len, rest) = tail.split_first()?;
+        let (id, rest) = rest.split_at(usize::from(len));
+        tail = rest;
+        Some(id)
+    }))
+}
+
+/// Returns the port of a `port` value or `None` if it is malformed.
+fn port(value: &[u8]) -> Option<u16> {
+    if value.len() == 2 {
+        Some(u16::from_be_bytes([value[0], value[1]]))
+    } else {
+        None
+    }
+}
+
+/// Returns an iterator over the addresses of an `ipv4hint` value.
+///
+/// Returns `None` if the value is malformed.
+fn ipv4_hints(value: &[u8]) -> Option<impl Iterator<Item = Ipv4Addr> + '_> {
+    let chunks = value.chunks_exact(4);
+    if value.is_empty() || !chunks.remainder().is_empty() {
+        return None;
+    }
+    Some(chunks.map(|chunk| {
+        Ipv4Addr::from([chunk[0], chunk[1], chunk[2], chunk[3]])
+    }))
+}
+
+/// Returns an iterator over the addresses of an `ipv6hint` value.
+///
+/// Returns `None` if the value is malformed.
+fn ipv6_hints(value: &[u8]) -> Option<impl Iterator<Item = Ipv6Addr> + '_> {
+    let chunks = value.chunks_exact(16);
+    if value.is_empty() || !chunks.remainder().is_empty() {
+        return None;
+    }
+    Some(chunks.map(|chunk| {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(chunk);
+        Ipv6Addr::from(octets)
+    }))
+}
+
+/// Formats a single parameter in presentation format.
+fn fmt_param(
+    key: SvcParamKey,
+    value: &[u8],
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    /// Formats a list of items separated by commas.
+    fn list<T: fmt::Display>(
+        f: &mut fmt::Formatter,
+        items: impl Iterator<Item = T>,
+    ) -> fmt::Result {
+        for (i, item) in items.enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            item.fmt(f)?;
+        }
+        Ok(())
+    }
+
+    match key {
+        SvcParamKey::Mandatory => {
+            if let Some(keys) = mandatory_keys(value) {
+                f.write_str("mandatory=")?;
+                return list(f, keys);
+            }
+        }
+        SvcParamKey::Alpn => {
+            if let Some(ids) = alpn_ids(value) {
+                f.write_str("alpn=\"")?;
+                for (i, id) in ids.enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    // Commas within an identifier need double escaping.
+                    for &ch in id {
+                        if ch == b',' || ch == b'\\' {
+                            f.write_str("\\\\")?;
+                        }
+                        fmt_value_octet(ch, f)?;
+                    }
+                }
+                return f.write_str("\"");
+            }
+        }
+        SvcParamKey::NoDefaultAlpn if value.is_empty() => {
+            return f.write_str("no-default-alpn");
+        }
+        SvcParamKey::Port => {
+            if let Some(port) = port(value) {
+                return write!(f, "port={}", port);
+            }
+        }
+        SvcParamKey::Ipv4Hint => {
+            if let Some(addrs) = ipv4_hints(value) {
+                f.write_str("ipv4hint=")?;
+                return list(f, addrs);
+            }
+        }
+        SvcParamKey::Ech => {
+            f.write_str("ech=")?;
+            return base64::display(value, f);
+        }
+        SvcParamKey::Ipv6Hint => {
+            if let Some(addrs) = ipv6_hints(value) {
+                f.write_str("ipv6hint=")?;
+                return list(f, addrs);
+            }
+        }
+        _ => {}
+    }
+
+    // Generic format for unknown keys and malformed values.
+    write!(f, "key{}", key.to_int())?;
+    if !value.is_empty() {
+        f.write_str("=\"")?;
+        for &ch in value {
+            fmt_value_octet(ch, f)?;
+        }
+        f.write_str("\"")?;
+    }
+    Ok(())
+}
+
+/// Formats a single octet of a value within quotes.
+fn fmt_value_octet(ch: u8, f: &mut fmt::Formatter) -> fmt::Result {
+    if ch == b'"' || ch == b'\\' {
+        write!(f, "\\{}", ch as char)
+    } else if (0x20..0x7F).contains(&ch) {
+        write!(f, "{}", ch as char)
+    } else {
+        write!(f, "\\{:03}", ch)
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ SvcParamError -------------------------------------------------
+
+/// A service parameter value was invalid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SvcParamError {
+    /// The value was longer than 65,535 octets.
+    LongValue,
+
+    /// An ALPN protocol identifier was empty or longer than 255 octets.
+    BadAlpnId,
+}
+
+impl fmt::Display for SvcParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SvcParamError::LongValue => {
+                f.write_str("service parameter value too long")
+            }
+            SvcParamError::BadAlpnId => {
+                f.write_str("invalid ALPN protocol identifier")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SvcParamError {}
+
+//------------ SvcbError -----------------------------------------------------
+
+/// The service parameters of an SVCB or HTTPS record were invalid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SvcbError {
+    /// The value of the `mandatory` parameter was malformed.
+    BadMandatory,
+
+    /// A key listed as mandatory was missing.
+    MissingMandatoryKey(SvcParamKey),
+}
+
+impl fmt::Display for SvcbError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SvcbError::BadMandatory => {
+                f.write_str("invalid mandatory service parameter")
+            }
+            SvcbError::MissingMandatoryKey(key) => {
+                write!(f, "mandatory service parameter {} missing", key)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SvcbError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use crate::base::name::Dname;
+    use core::str::FromStr;
+    use std::string::ToString;
+
+    fn build(builder: &SvcParamsBuilder) -> SvcParams<Vec<u8>> {
+        builder.finish::<Vec<u8>>().unwrap()
+    }
+
+    #[test]
+    fn typed_params() {
+        let mut builder = SvcParamsBuilder::new();
+        builder.alpn(&[b"h2", b"h3"]).unwrap();
+        builder.port(8443);
+        builder.no_default_alpn();
+        builder
+            .ipv4hint(&[
+                Ipv4Addr::new(192, 0, 2, 1),
+                Ipv4Addr::new(192, 0, 2, 2),
+            ])
+            .unwrap();
+        builder
+            .ipv6hint(&[Ipv6Addr::from_str("2001:db8::1").unwrap()])
+            .unwrap();
+        builder
+            .mandatory(&[SvcParamKey::Port, SvcParamKey::Alpn])
+            .unwrap();
+        let params = build(&builder);
+
+        assert_eq!(
+            params.mandatory().unwrap(),
+            [SvcParamKey::Alpn, SvcParamKey::Port]
+        );
+        assert_eq!(params.alpn().unwrap(), [&b"h2"[..], &b"h3"[..]]);
+        assert!(params.no_default_alpn());
+        assert_eq!(params.port(), Some(8443));
+        assert_eq!(
+            params.ipv4hint().unwrap(),
+            [Ipv4Addr::new(192, 0, 2, 1), Ipv4Addr::new(192, 0, 2, 2)]
+        );
+        assert_eq!(
+            params.ipv6hint().unwrap(),
+            [Ipv6Addr::from_str("2001:db8::1").unwrap()]
+        );
+        assert_eq!(params.get(SvcParamKey::Ech), None);
+        assert_eq!(
+            params.to_string(),
+            "mandatory=alpn,port alpn=\"h2,h3\" no-default-alpn port=8443 \
+             ipv4hint=192.0.2.1,192.0.2.2 ipv6hint=2001:db8::1"
+        );
+
+        assert!(builder.alpn(&[b""]).is_err());
+        assert!(builder.push_raw(SvcParamKey::Ech, &[0; 65536]).is_err());
+    }
+
+    #[test]
+    fn compose_in_key_order() {
+        // Add the parameters in reverse order including an unknown one.
+        let mut builder = SvcParamsBuilder::new();
+        builder
+            .push_raw(SvcParamKey::Int(65000), b"\x01\"x")
+            .unwrap();
+        builder.port(443);
+        builder.alpn(&[b"h2"]).unwrap();
+        builder.mandatory(&[SvcParamKey::Port]).unwrap();
+
+        let mut buf = Vec::new();
+        builder.compose(&mut buf).unwrap();
+        assert_eq!(
+            buf,
+            b"\x00\x00\x00\x02\x00\x03\
+              \x00\x01\x00\x03\x02h2\
+              \x00\x03\x00\x02\x01\xbb\
+              \xfd\xe8\x00\x03\x01\"x"
+        );
+
+        let params = SvcParams::from_octets(buf).unwrap();
+        let keys: Vec<_> = params.iter().map(|(key, _)| key).collect();
+        assert_eq!(
+            keys,
+            [
+                SvcParamKey::Mandatory,
+                SvcParamKey::Alpn,
+                SvcParamKey::Port,
+                SvcParamKey::Int(65000)
+            ]
+        );
+        assert_eq!(
+            params.get(SvcParamKey::Int(65000)),
+            Some(&b"\x01\"x"[..])
+        );
+        assert_eq!(
+            params.to_string(),
+            "mandatory=port alpn=\"h2\" port=443 key65000=\"\\001\\\"x\""
+        );
+
+        // Rebuilding parameters keeps unknown keys.
+        let rebuilt = build(&SvcParamsBuilder::from_params(&params));
+        assert_eq!(rebuilt.as_slice(), params.as_slice());
+    }
+
+    #[test]
+    fn unsorted_keys() {
+        // key65000 port=53
+        assert!(SvcParams::from_octets(
+            b"\xfd\xe8\x00\x00\x00\x03\x00\x02\x00\x35".as_ref()
+        )
+        .is_err());
+
+        // port=443 alpn=h2
+        assert!(SvcParams::from_octets(
+            b"\x00\x03\x00\x02\x01\xbb\x00\x01\x00\x03\x02h2".as_ref()
+        )
+        .is_err());
+
+        // port=443 port=443
+        assert!(SvcParams::from_octets(
+            b"\x00\x03\x00\x02\x01\xbb\x00\x03\x00\x02\x01\xbb".as_ref()
+        )
+        .is_err());
+
+        // Record data with unsorted keys doesn’t parse either.
+        let mut rdata = b"\x00\x01\x00".to_vec();
+        rdata.extend_from_slice(
+            b"\x00\x03\x00\x02\x01\xbb\x00\x01\x00\x03\x02h2",
+        );
+        assert!(Svcb::<&[u8], ParsedDname<&[u8]>>::parse(
+            &mut Parser::from_ref(rdata.as_slice())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn malformed_params() {
+        assert!(
+            SvcParams::from_octets(b"\x00\x03\x00\x02\x01".as_ref()).is_err()
+        );
+        assert!(SvcParams::from_octets(b"\x00\x03\x00".as_ref()).is_err());
+
+        let params =
+            SvcParams::from_octets(b"\x00\x03\x00\x01\x01".as_ref()).unwrap();
+        assert_eq!(params.port(), None);
+        assert_eq!(params.to_string(), "key3=\"\\001\"");
+    }
+
+    #[test]
+    fn svcb_compose_parse() {
+        let mut builder = SvcParamsBuilder::new();
+        builder.alpn(&[b"h3"]).unwrap();
+        builder.port(8443);
+        let target = Dname::<Vec<u8>>::from_str("svc.example.com").unwrap();
+        let rdata = Https::new(1, target.clone(), build(&builder));
+        assert!(!rdata.is_alias());
+
+        let mut buf = Vec::new();
+        rdata.compose(&mut buf).unwrap();
+        let parsed =
+            Https::parse(&mut Parser::from_ref(buf.as_slice())).unwrap();
+        assert_eq!(parsed, rdata);
+        assert_eq!(parsed.priority(), 1);
+        assert_eq!(*parsed.target(), target);
+        assert_eq!(parsed.params().port(), Some(8443));
+        assert_eq!(
+            rdata.to_string(),
+            "1 svc.example.com. alpn=\"h3\" port=8443"
+        );
+
+        let alias = Svcb::new(
+            0,
+            Dname::<Vec<u8>>::from_str("pool.example.net").unwrap(),
+            SvcParams::from_octets(Vec::new()).unwrap(),
+        );
+        assert!(alias.is_alias());
+        assert_eq!(alias.to_string(), "0 pool.example.net.");
+    }
+
+    #[test]
+    fn validate() {
+        fn svcb(params: &[u8]) -> Svcb<&[u8], Dname<Vec<u8>>> {
+            Svcb::new(
+                1,
+                Dname::root_vec(),
+                SvcParams::from_octets(params).unwrap(),
+            )
+        }
+
+        // mandatory=alpn,port alpn=h2 port=443
+        let valid = svcb(
+            b"\x00\x00\x00\x04\x00\x01\x00\x03\
+              \x00\x01\x00\x03\x02h2\
+              \x00\x03\x00\x02\x01\xbb",
+        );
+        assert_eq!(valid.validate(), Ok(()));
+        assert_eq!(svcb(b"").validate(), Ok(()));
+
+        // mandatory=alpn,port alpn=h2
+        assert_eq!(
+            svcb(
+                b"\x00\x00\x00\x04\x00\x01\x00\x03\
+                  \x00\x01\x00\x03\x02h2"
+            )
+            .validate(),
+            Err(SvcbError::MissingMandatoryKey(SvcParamKey::Port))
+        );
+
+        // mandatory=port,alpn alpn=h2 port=443
+        assert_eq!(
+            svcb(
+                b"\x00\x00\x00\x04\x00\x03\x00\x01\
+                  \x00\x01\x00\x03\x02h2\
+                  \x00\x03\x00\x02\x01\xbb"
+            )
+            .validate(),
+            Err(SvcbError::BadMandatory)
+        );
+    }
+}
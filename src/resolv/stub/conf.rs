@@ -9,6 +9,7 @@
 //! Both parts are modeled along the lines of glibc’s resolver.
 
 use crate::base::name::{self, Dname};
+use crate::base::opt::DEFAULT_UDP_PAYLOAD_SIZE;
 use smallvec::SmallVec;
 use std::cmp::Ordering;
 use std::default::Default;
@@ -16,6 +17,7 @@ use std::io::Read;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::Path;
 use std::str::{self, FromStr, SplitWhitespace};
+use std::string::String;
 use std::time::Duration;
 use std::vec::Vec;
 use std::{convert, error, fmt, fs, io, ops};
@@ -40,9 +42,13 @@ pub struct ResolvOptions {
     pub ndots: usize,
 
     /// Timeout to wait for a response.
+    ///
+    /// This is ignored if `retry` is set.
     pub timeout: Duration,
 
     /// Number of retries before giving up.
+    ///
+    /// This is ignored if `retry` is set.
     pub attempts: usize,
 
     /// Accept authoritative answers only.
@@ -164,6 +170,15 @@ pub struct ResolvOptions {
     /// `search` and `ndots` fields govern resolution of relative names of
     /// all kinds.
     pub no_tld_query: bool,
+
+    /// An explicit retry policy.
+    ///
+    /// If this is `None`, each server’s own `request_timeout` is used for
+    /// every attempt and the server list is tried `attempts` times.
+    /// Otherwise the policy determines both the number of attempts and the
+    /// timeout for each of them and takes precedence over the `attempts`
+    /// and `timeout` fields as well as the servers’ `request_timeout`.
+    pub retry: Option<RetryPolicy>,
 }
 
 impl Default for ResolvOptions {
@@ -198,6 +213,102 @@ impl Default for ResolvOptions {
             single_request: false,
             single_request_reopen: false,
             no_tld_query: false,
+            retry: None,
+        }
+    }
+}
+
+//------------ RetryPolicy ---------------------------------------------------
+
+/// How failed queries are retried.
+///
+/// A query is sent to one server at a time. If an attempt fails, either
+/// because the server didn’t answer in time or because of some other
+/// error, the next attempt goes to the next server in the list, wrapping
+/// around at its end. At most `attempts` attempts are made in total.
+///
+/// The timeout for the first attempt is `timeout`. For each further
+/// attempt, it is multiplied by `multiplier`. Finally, a random duration
+/// of up to `jitter` is added to each timeout.
+///
+/// When used via [`ResolvOptions::retry`], the policy replaces the
+/// resolver’s `attempts` and `timeout` options.
+///
+/// [`ResolvOptions::retry`]: struct.ResolvOptions.html#structfield.retry
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts across all servers.
+    pub attempts: usize,
+
+    /// The timeout for the first attempt.
+    pub timeout: Duration,
+
+    /// The factor by which the timeout grows with each attempt.
+    pub multiplier: u32,
+
+    /// The maximum random duration added to each timeout.
+    pub jitter: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a new policy without backoff and jitter.
+    pub fn new(attempts: usize, timeout: Duration) -> Self {
+        RetryPolicy {
+            attempts,
+            timeout,
+            multiplier: 1,
+            jitter: Duration::from_secs(0),
+        }
+    }
+
+    /// Returns the timeout for the given attempt without jitter.
+    ///
+    /// Attempts are counted starting from zero.
+    pub fn base_timeout(&self, attempt: usize) -> Duration {
+        let mut res = self.timeout;
+        for _ in 0..attempt {
+            res = match res.checked_mul(self.multiplier) {
+                Some(res) => res,
+                None => return Duration::from_secs(u64::MAX),
+            }
+        }
+        res
+    }
+
+    /// Returns the timeout for the given attempt with random jitter added.
+    pub fn timeout(&self, attempt: usize) -> Duration {
+        let base = self.base_timeout(attempt);
+        if self.jitter == Duration::from_secs(0) {
+            return base;
+        }
+        // The jitter may well exceed what fits into a u64 of nanoseconds, so
+        // we stay with u128 until we have seconds.
+        let jitter = rand::random::<u128>() % self.jitter.as_nanos();
+        let jitter = Duration::new(
+            (jitter / 1_000_000_000) as u64,
+            (jitter % 1_000_000_000) as u32,
+        );
+        base.checked_add(jitter).unwrap_or(base)
+    }
+
+    /// Returns the maximum time spent on all attempts.
+    pub fn budget(&self) -> Duration {
+        let max = Duration::from_secs(u64::MAX);
+        (0..self.attempts).fold(Duration::from_secs(0), |res, attempt| {
+            res.checked_add(self.base_timeout(attempt))
+                .and_then(|res| res.checked_add(self.jitter))
+                .unwrap_or(max)
+        })
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            attempts: 4,
+            timeout: Duration::from_secs(1),
+            multiplier: 2,
+            jitter: Duration::from_millis(100),
         }
     }
 }
@@ -205,13 +316,23 @@ impl Default for ResolvOptions {
 //------------ Transport -----------------------------------------------------
 
 /// The transport protocol to be used for a server.
+///
+/// The enum is non-exhaustive so that transports can be added without
+/// breaking code matching on it.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
 pub enum Transport {
     /// Unencrypted UDP transport.
     Udp,
 
     /// Unencrypted TCP transport.
     Tcp,
+
+    /// TCP transport encrypted with TLS, i.e., DNS-over-TLS.
+    ///
+    /// This transport is only available if the `tls` feature is enabled.
+    /// Otherwise, queries to a server using it fail.
+    Tls,
 }
 
 impl Transport {
@@ -224,6 +345,7 @@ impl Transport {
         match self {
             Transport::Udp => true,
             Transport::Tcp => false,
+            Transport::Tls => false,
         }
     }
 
@@ -232,6 +354,7 @@ impl Transport {
         match self {
             Transport::Udp => false,
             Transport::Tcp => true,
+            Transport::Tls => true,
         }
     }
 }
@@ -275,8 +398,30 @@ pub struct ServerConf {
     ///
     /// This values will be announced in request if EDNS is supported by the
     /// server. It will be included both for datagram and streaming transport
-    /// but really only matters for UDP.
+    /// but really only matters for UDP. It defaults to
+    /// [`DEFAULT_UDP_PAYLOAD_SIZE`].
     pub udp_payload_size: u16,
+
+    /// The name of the server used with the TLS transport.
+    ///
+    /// The name is sent in the TLS handshake and the server’s certificate
+    /// is verified against it. If it is `None`, the server’s IP address is
+    /// used instead.
+    pub tls_server_name: Option<String>,
+
+    /// Whether to verify the server’s certificate with the TLS transport.
+    ///
+    /// This defaults to `true`. Only ever disable verification for
+    /// testing.
+    pub tls_verify: bool,
+
+    /// The block length queries are padded to with the TLS transport.
+    ///
+    /// If EDNS is used, queries are padded via the EDNS padding option so
+    /// their length is a multiple of this value. This defaults to 128 as
+    /// recommended by RFC 8467. If the value is `None`, no padding is
+    /// added.
+    pub tls_padding: Option<u16>,
 }
 
 impl ServerConf {
@@ -290,9 +435,23 @@ impl ServerConf {
             transport,
             request_timeout: Duration::from_secs(2),
             recv_size: 1232,
-            udp_payload_size: 1232,
+            udp_payload_size: DEFAULT_UDP_PAYLOAD_SIZE,
+            tls_server_name: None,
+            tls_verify: true,
+            tls_padding: Some(128),
         }
     }
+
+    /// Returns a new server config for DNS-over-TLS.
+    ///
+    /// The server’s certificate will be verified against `server_name`.
+    /// Queries to the server will fail unless the `tls` feature is
+    /// enabled.
+    pub fn tls(addr: SocketAddr, server_name: impl Into<String>) -> Self {
+        let mut res = Self::new(addr, Transport::Tls);
+        res.tls_server_name = Some(server_name.into());
+        res
+    }
 }
 
 //------------ ResolvConf ---------------------------------------------------
@@ -362,6 +521,24 @@ impl ResolvConf {
         }
     }
 
+    /// Creates a configuration for the given server addresses.
+    ///
+    /// Each server is used for both UDP and TCP. Queries are retried as
+    /// determined by `retry`. The configuration is already finalized.
+    pub fn with_servers<I>(servers: I, retry: RetryPolicy) -> Self
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        let mut res = ResolvConf::new();
+        for addr in servers {
+            res.servers.push(ServerConf::new(addr, Transport::Udp));
+            res.servers.push(ServerConf::new(addr, Transport::Tcp));
+        }
+        res.options.retry = Some(retry);
+        res.finalize();
+        res
+    }
+
     /// Creates a default configuration for this system.
     ///
     /// XXX This currently only works for Unix-y systems.
@@ -773,4 +950,27 @@ mod test {
         assert!(conf.options.use_vc);
         assert_eq!(conf.options.ndots, 122);
     }
+
+    #[test]
+    fn retry_policy() {
+        let policy = RetryPolicy {
+            attempts: 3,
+            timeout: Duration::from_millis(100),
+            multiplier: 2,
+            jitter: Duration::from_millis(10),
+        };
+        assert_eq!(policy.base_timeout(0), Duration::from_millis(100));
+        assert_eq!(policy.base_timeout(2), Duration::from_millis(400));
+        let timeout = policy.timeout(1);
+        assert!(timeout >= Duration::from_millis(200));
+        assert!(timeout < Duration::from_millis(210));
+        assert_eq!(policy.budget(), Duration::from_millis(730));
+
+        // Jitter that doesn’t fit into 64 bits of nanoseconds.
+        let policy = RetryPolicy {
+            jitter: Duration::from_nanos(u64::MAX) + Duration::from_nanos(1),
+            ..policy
+        };
+        assert!(policy.timeout(0) >= Duration::from_millis(100));
+    }
 }
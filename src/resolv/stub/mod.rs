@@ -1,5 +1,6 @@
 use self::conf::{
-    ResolvConf, ResolvOptions, SearchSuffix, ServerConf, Transport,
+    ResolvConf, ResolvOptions, RetryPolicy, SearchSuffix, ServerConf,
+    Transport,
 };
 use crate::base::iana::Rcode;
 use crate::base::message::Message;
@@ -8,6 +9,8 @@ use crate::base::message_builder::{
 };
 use crate::base::name::{ToDname, ToRelativeDname};
 use crate::base::octets::Octets512;
+use crate::base::opt::rfc7830::{Padding, PaddingMode};
+use crate::base::opt::TcpKeepalive;
 use crate::base::question::Question;
 use crate::resolv::lookup::addr::{lookup_addr, FoundAddrs};
 use crate::resolv::lookup::host::{lookup_host, search_host, FoundHosts};
@@ -15,12 +18,17 @@ use crate::resolv::lookup::srv::{lookup_srv, FoundSrvs, SrvError};
 use crate::resolv::resolver::{Resolver, SearchNames};
 use bytes::Bytes;
 use futures::future::FutureExt;
+use futures::task::noop_waker_ref;
 use std::boxed::Box;
+#[cfg(feature = "tls")]
+use std::convert::TryFrom;
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::task::Context;
+use std::time::{Duration, Instant};
 use std::vec::Vec;
 /// A stub resolver.
 ///
@@ -32,22 +40,33 @@ use std::vec::Vec;
 ///
 /// The main type is [`StubResolver`] that implements the [`Resolver`] trait
 /// and thus can be used with the various lookup functions.
-use std::{io, ops};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use std::{cmp, fmt, io, ops};
+use tokio::io::{
+    AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf,
+};
 use tokio::net::{TcpStream, UdpSocket};
 #[cfg(feature = "resolv-sync")]
 use tokio::runtime;
 use tokio::time::timeout;
+#[cfg(feature = "tls")]
+use tokio_rustls::rustls;
 
 //------------ Sub-modules ---------------------------------------------------
 
 pub mod conf;
+pub mod pipeline;
 
 //------------ Module Configuration ------------------------------------------
 
 /// How many times do we try a new random port if we get ‘address in use.’
 const RETRY_RANDOM_PORT: usize = 10;
 
+/// The maximum time we keep an idle stream connection open.
+///
+/// Servers can advertise a longer keep-alive duration, but we don’t want to
+/// hold on to connections forever.
+const MAX_KEEP_ALIVE: Duration = Duration::from_secs(300);
+
 //------------ StubResolver --------------------------------------------------
 
 /// A DNS stub resolver.
@@ -98,6 +117,17 @@ impl StubResolver {
         }
     }
 
+    /// Creates a new resolver using the given servers.
+    ///
+    /// Each server will be used for both UDP and TCP. Failed queries are
+    /// retried according to `retry`.
+    pub fn with_servers<I>(servers: I, retry: RetryPolicy) -> Self
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        Self::from_conf(ResolvConf::with_servers(servers, retry))
+    }
+
     pub fn options(&self) -> &ResolvOptions {
         &self.options
     }
@@ -239,6 +269,9 @@ pub struct Query<'a> {
     /// The number of attempts, starting with zero.
     attempt: usize,
 
+    /// The number of queries sent so far across all servers.
+    sent: usize,
+
     /// The index in the server list we currently trying.
     counter: ServerListCounter,
 
@@ -270,6 +303,7 @@ impl<'a> Query<'a> {
             resolver,
             preferred,
             attempt: 0,
+            sent: 0,
             counter,
             error: Err(io::Error::new(
                 io::ErrorKind::TimedOut,
@@ -334,9 +368,16 @@ impl<'a> Query<'a> {
         &mut self,
         message: &mut QueryMessage,
     ) -> Result<Answer, io::Error> {
+        let timeout = match self.resolver.options().retry {
+            Some(ref retry) => Some(retry.timeout(self.sent)),
+            None => None,
+        };
+        self.sent += 1;
         let server = self.current_server();
         server.prepare_message(message);
-        server.query(message).await
+        server
+            .query(message, timeout.unwrap_or(server.conf.request_timeout))
+            .await
     }
 
     fn current_server(&self) -> &ServerInfo {
@@ -374,6 +415,15 @@ impl<'a> Query<'a> {
     }
 
     fn next_server(&mut self) -> bool {
+        if let Some(ref retry) = self.resolver.options().retry {
+            if self.sent >= retry.attempts {
+                return false;
+            }
+            if !self.counter.next() {
+                self.counter = self.new_counter();
+            }
+            return true;
+        }
         if self.counter.next() {
             return true;
         }
@@ -381,14 +431,18 @@ impl<'a> Query<'a> {
         if self.attempt >= self.resolver.options().attempts {
             return false;
         }
-        self.counter = if self.preferred {
+        self.counter = self.new_counter();
+        true
+    }
+
+    fn new_counter(&self) -> ServerListCounter {
+        if self.preferred {
             self.resolver
                 .preferred
                 .counter(self.resolver.options().rotate)
         } else {
             self.resolver.stream.counter(self.resolver.options().rotate)
-        };
-        true
+        }
     }
 }
 
@@ -443,6 +497,9 @@ struct ServerInfo {
     ///
     /// We start out with assuming it does and unset it if we get a FORMERR.
     edns: Arc<AtomicBool>,
+
+    /// The state of stream connections to this server.
+    stream: Arc<Mutex<StreamState>>,
 }
 
 impl ServerInfo {
@@ -460,6 +517,19 @@ impl ServerInfo {
             query
                 .opt(|opt| {
                     opt.set_udp_payload_size(self.conf.udp_payload_size);
+                    if self.conf.transport.is_stream() {
+                        // An empty option signals support for keepalive.
+                        TcpKeepalive::push(opt, None)?;
+                    }
+                    if let (Transport::Tls, Some(block_len)) =
+                        (self.conf.transport, self.conf.tls_padding)
+                    {
+                        Padding::push_block(
+                            opt,
+                            block_len,
+                            PaddingMode::Zero,
+                        )?;
+                    }
                     Ok(())
                 })
                 .unwrap();
@@ -469,11 +539,12 @@ impl ServerInfo {
     pub async fn query(
         &self,
         query: &QueryMessage,
+        request_timeout: Duration,
     ) -> Result<Answer, io::Error> {
         let res = match self.conf.transport {
             Transport::Udp => {
                 timeout(
-                    self.conf.request_timeout,
+                    request_timeout,
                     Self::udp_query(
                         query,
                         self.conf.addr,
@@ -482,10 +553,10 @@ impl ServerInfo {
                 )
                 .await
             }
-            Transport::Tcp => {
+            _ => {
                 timeout(
-                    self.conf.request_timeout,
-                    Self::tcp_query(query, self.conf.addr),
+                    request_timeout,
+                    self.stream_query(query, request_timeout),
                 )
                 .await
             }
@@ -500,24 +571,82 @@ impl ServerInfo {
         }
     }
 
-    pub async fn tcp_query(
+    async fn stream_query(
+        &self,
         query: &QueryMessage,
-        addr: SocketAddr,
+        request_timeout: Duration,
     ) -> Result<Answer, io::Error> {
-        let mut sock = TcpStream::connect(&addr).await?;
+        // If we have an idle connection, try that first. Since the server
+        // may have dropped it in the meantime without us noticing, we
+        // quietly fall back to a new connection if that fails or takes
+        // longer than half the request timeout.
+        if let Some(sock) = self.take_idle_stream() {
+            if let Ok(Ok((answer, sock))) = timeout(
+                request_timeout / 2,
+                Self::stream_exchange(query, sock),
+            )
+            .await
+            {
+                self.keep_idle_stream(sock, &answer);
+                return Ok(answer);
+            }
+        }
+        let sock = self.connect_stream().await?;
+        let (answer, sock) = Self::stream_exchange(query, sock).await?;
+        self.keep_idle_stream(sock, &answer);
+        Ok(answer)
+    }
+
+    async fn connect_stream(
+        &self,
+    ) -> Result<Box<dyn StreamSocket>, io::Error> {
+        if self.conf.transport == Transport::Tls {
+            return self.connect_tls().await;
+        }
+        let sock = TcpStream::connect(&self.conf.addr).await?;
+        Ok(Box::new(sock))
+    }
+
+    #[cfg(feature = "tls")]
+    async fn connect_tls(&self) -> Result<Box<dyn StreamSocket>, io::Error> {
+        let server_name = match self.conf.tls_server_name {
+            Some(ref name) => {
+                rustls::ServerName::try_from(name.as_str()).map_err(
+                    |err| io::Error::new(io::ErrorKind::InvalidInput, err),
+                )?
+            }
+            None => rustls::ServerName::IpAddress(self.conf.addr.ip()),
+        };
+        let connector =
+            tokio_rustls::TlsConnector::from(tls::client_config(
+                self.conf.tls_verify,
+            ));
+        let sock = TcpStream::connect(&self.conf.addr).await?;
+        let sock = connector.connect(server_name, sock).await?;
+        Ok(Box::new(sock))
+    }
+
+    #[cfg(not(feature = "tls"))]
+    async fn connect_tls(&self) -> Result<Box<dyn StreamSocket>, io::Error> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "DNS-over-TLS requires the tls feature",
+        ))
+    }
+
+    async fn stream_exchange(
+        query: &QueryMessage,
+        mut sock: Box<dyn StreamSocket>,
+    ) -> Result<(Answer, Box<dyn StreamSocket>), io::Error> {
         sock.write_all(query.as_target().as_stream_slice()).await?;
 
         // This loop can be infinite because we have a timeout on this whole
         // thing, anyway.
         loop {
-            let mut buf = Vec::new();
-            let len = sock.read_u16().await? as u64;
-            AsyncReadExt::take(&mut sock, len)
-                .read_to_end(&mut buf)
-                .await?;
+            let buf = Self::read_stream_message(&mut sock).await?;
             if let Ok(answer) = Message::from_octets(buf.into()) {
                 if answer.is_answer(&query.as_message()) {
-                    return Ok(answer.into());
+                    return Ok((answer.into(), sock));
                 }
             // else try with the next message.
             } else {
@@ -529,6 +658,20 @@ impl ServerInfo {
         }
     }
 
+    /// Reads a single message from a stream socket.
+    ///
+    /// The message is preceded by a 16 bit length value. If the stream ends
+    /// before that many octets have been received, returns an error of kind
+    /// `UnexpectedEof` rather than a truncated message.
+    async fn read_stream_message<S: AsyncRead + Unpin + ?Sized>(
+        sock: &mut S,
+    ) -> Result<Vec<u8>, io::Error> {
+        let len = usize::from(sock.read_u16().await?);
+        let mut buf = vec![0; len];
+        sock.read_exact(&mut buf).await?;
+        Ok(buf)
+    }
+
     pub async fn udp_query(
         query: &QueryMessage,
         addr: SocketAddr,
@@ -560,6 +703,59 @@ impl ServerInfo {
         }
     }
 
+    /// Takes the idle stream connection if there is one.
+    ///
+    /// Connections that have expired or have been closed by the server are
+    /// dropped.
+    fn take_idle_stream(&self) -> Option<Box<dyn StreamSocket>> {
+        let mut state = self.stream.lock().unwrap();
+        match state.idle.take() {
+            Some((mut sock, expires)) if expires > Instant::now() => {
+                if Self::is_idle_stream_open(&mut sock) {
+                    Some(sock)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks whether an idle stream connection is still open.
+    ///
+    /// Since there is no outstanding query on an idle connection, there
+    /// should be nothing to read. If reading doesn’t block, the server has
+    /// either closed the connection, reset it, or sent us unexpected data.
+    /// In all these cases the connection can’t be used any more.
+    fn is_idle_stream_open(sock: &mut Box<dyn StreamSocket>) -> bool {
+        let mut buf = [0u8; 1];
+        let mut buf = ReadBuf::new(&mut buf);
+        let mut cx = Context::from_waker(noop_waker_ref());
+        Pin::new(sock).poll_read(&mut cx, &mut buf).is_pending()
+    }
+
+    /// Keeps a stream connection around for reuse.
+    ///
+    /// If the answer contains a TCP keepalive option, the keep-alive
+    /// duration advertised by the server is used from now on.
+    fn keep_idle_stream(&self, sock: Box<dyn StreamSocket>, answer: &Answer) {
+        let keep_alive = answer
+            .opt()
+            .and_then(|opt| opt.iter::<TcpKeepalive>().next())
+            .and_then(Result::ok)
+            .and_then(TcpKeepalive::timeout);
+        let mut state = self.stream.lock().unwrap();
+        if let Some(timeout) = keep_alive {
+            state.keep_alive = cmp::min(
+                Duration::from_millis(u64::from(timeout) * 100),
+                MAX_KEEP_ALIVE,
+            );
+        }
+        if state.keep_alive > Duration::from_secs(0) {
+            state.idle = Some((sock, Instant::now() + state.keep_alive));
+        }
+    }
+
     async fn udp_bind(v4: bool) -> Result<UdpSocket, io::Error> {
         let mut i = 0;
         loop {
@@ -587,6 +783,7 @@ impl From<ServerConf> for ServerInfo {
         ServerInfo {
             conf,
             edns: Arc::new(AtomicBool::new(true)),
+            stream: Default::default(),
         }
     }
 }
@@ -597,6 +794,99 @@ impl<'a> From<&'a ServerConf> for ServerInfo {
     }
 }
 
+//------------ StreamState ---------------------------------------------------
+
+/// The state of the stream connections to a server.
+#[derive(Default)]
+struct StreamState {
+    /// How long an idle connection may be kept open.
+    ///
+    /// This starts out as zero, i.e., connections are closed right away,
+    /// and is updated whenever the server advertises a TCP keepalive
+    /// timeout.
+    keep_alive: Duration,
+
+    /// An idle connection and the time it expires.
+    idle: Option<(Box<dyn StreamSocket>, Instant)>,
+}
+
+impl fmt::Debug for StreamState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StreamState")
+            .field("keep_alive", &self.keep_alive)
+            .field("idle", &self.idle.as_ref().map(|idle| idle.1))
+            .finish()
+    }
+}
+
+//------------ StreamSocket --------------------------------------------------
+
+/// A socket for a stream transport.
+trait StreamSocket: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> StreamSocket for T {}
+
+//------------ tls -----------------------------------------------------------
+
+/// Configuration for the TLS transport.
+#[cfg(feature = "tls")]
+mod tls {
+    use std::sync::Arc;
+    use std::time::SystemTime;
+    use tokio_rustls::rustls::client::{
+        ServerCertVerified, ServerCertVerifier,
+    };
+    use tokio_rustls::rustls::{
+        Certificate, ClientConfig, Error, OwnedTrustAnchor, RootCertStore,
+        ServerName,
+    };
+
+    /// Returns a client config for connecting to a server.
+    ///
+    /// If `verify` is `true`, the server’s certificate is verified against
+    /// the Mozilla root certificates provided by the webpki-roots crate.
+    /// Otherwise, any certificate is accepted.
+    pub fn client_config(verify: bool) -> Arc<ClientConfig> {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(
+            |anchor| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    anchor.subject,
+                    anchor.spki,
+                    anchor.name_constraints,
+                )
+            },
+        ));
+        let mut config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        if !verify {
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(NoVerification));
+        }
+        Arc::new(config)
+    }
+
+    /// A certificate verifier that accepts any certificate.
+    struct NoVerification;
+
+    impl ServerCertVerifier for NoVerification {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &Certificate,
+            _intermediates: &[Certificate],
+            _server_name: &ServerName,
+            _scts: &mut dyn Iterator<Item = &[u8]>,
+            _ocsp_response: &[u8],
+            _now: SystemTime,
+        ) -> Result<ServerCertVerified, Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+    }
+}
+
 //------------ ServerList ----------------------------------------------------
 
 #[derive(Clone, Debug)]
@@ -772,3 +1062,286 @@ impl<'a> Iterator for SearchIter<'a> {
         }
     }
 }
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::iana::Rtype;
+    use crate::base::name::Dname;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn retry_next_server() {
+        // The first two servers never answer, the third one does.
+        let silent1 = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let silent2 = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addrs = vec![
+            silent1.local_addr().unwrap(),
+            silent2.local_addr().unwrap(),
+            server.local_addr().unwrap(),
+        ];
+        tokio::spawn(async move {
+            let mut buf = vec![0; 512];
+            let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+            buf.truncate(len);
+            let request = Message::from_octets(buf).unwrap();
+            let answer = MessageBuilder::new_vec()
+                .start_answer(&request, Rcode::NoError)
+                .unwrap();
+            server.send_to(answer.as_slice(), peer).await.unwrap();
+        });
+
+        let retry = RetryPolicy {
+            attempts: 3,
+            timeout: Duration::from_millis(50),
+            multiplier: 2,
+            jitter: Duration::from_secs(0),
+        };
+        let resolver = StubResolver::with_servers(addrs, retry);
+        let start = Instant::now();
+        let answer = resolver
+            .query((Dname::root_slice(), Rtype::A))
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+        assert_eq!(answer.header().rcode(), Rcode::NoError);
+        assert!(elapsed >= Duration::from_millis(150));
+        assert!(elapsed < retry.budget());
+    }
+
+    #[tokio::test]
+    async fn tcp_keepalive() {
+        use tokio::net::TcpListener;
+
+        // The server only accepts a single connection, so the second query
+        // has to reuse it.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            loop {
+                answer_keepalive(&mut sock).await;
+            }
+        });
+
+        let mut conf = ResolvConf::new();
+        conf.servers.push(ServerConf::new(addr, Transport::Tcp));
+        let resolver = StubResolver::from_conf(conf);
+        let server = &resolver.stream[0];
+        assert_eq!(
+            server.stream.lock().unwrap().keep_alive,
+            Duration::from_secs(0)
+        );
+        for _ in 0..2 {
+            let answer = resolver
+                .query((Dname::root_slice(), Rtype::A))
+                .await
+                .unwrap();
+            assert_eq!(answer.header().rcode(), Rcode::NoError);
+            let state = server.stream.lock().unwrap();
+            assert_eq!(state.keep_alive, Duration::from_secs(5));
+            assert!(state.idle.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn idle_stream_closed() {
+        use tokio::net::TcpListener;
+
+        // The server closes the connection right after the first answer.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            answer_keepalive(&mut sock).await;
+        });
+
+        let mut conf = ResolvConf::new();
+        conf.servers.push(ServerConf::new(addr, Transport::Tcp));
+        let resolver = StubResolver::from_conf(conf);
+        let server = &resolver.stream[0];
+        resolver
+            .query((Dname::root_slice(), Rtype::A))
+            .await
+            .unwrap();
+        assert!(server.stream.lock().unwrap().idle.is_some());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(server.take_idle_stream().is_none());
+    }
+
+    #[tokio::test]
+    async fn idle_stream_unresponsive() {
+        use tokio::net::TcpListener;
+
+        // The server keeps the first connection open after the first
+        // answer but never answers on it again.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut first, _) = listener.accept().await.unwrap();
+            answer_keepalive(&mut first).await;
+            let (mut second, _) = listener.accept().await.unwrap();
+            answer_keepalive(&mut second).await;
+            drop(first);
+        });
+
+        let mut conf = ResolvConf::new();
+        let mut server = ServerConf::new(addr, Transport::Tcp);
+        server.request_timeout = Duration::from_secs(1);
+        conf.servers.push(server);
+        let resolver = StubResolver::from_conf(conf);
+        resolver
+            .query((Dname::root_slice(), Rtype::A))
+            .await
+            .unwrap();
+        let start = Instant::now();
+        resolver
+            .query((Dname::root_slice(), Rtype::A))
+            .await
+            .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    /// Answers a single query on `sock` with a TCP keepalive option.
+    async fn answer_keepalive(sock: &mut tokio::net::TcpStream) {
+        let len = sock.read_u16().await.unwrap() as usize;
+        let mut buf = vec![0; len];
+        sock.read_exact(&mut buf).await.unwrap();
+        let request = Message::from_octets(buf).unwrap();
+        // The query ends with an empty keepalive option.
+        assert!(request.as_slice().ends_with(&[0, 11, 0, 0]));
+        let mut answer = MessageBuilder::from_target(StreamTarget::new_vec())
+            .unwrap()
+            .start_answer(&request, Rcode::NoError)
+            .unwrap()
+            .additional();
+        answer.opt(|opt| TcpKeepalive::push(opt, Some(50))).unwrap();
+        let answer = answer.finish();
+        sock.write_all(answer.as_stream_slice()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn read_stream_message() {
+        let mut data: &[u8] = b"\x00\x03abc\x00\x00\x00\x05ab";
+        let mut sock = &mut data;
+        assert_eq!(
+            ServerInfo::read_stream_message(&mut sock).await.unwrap(),
+            b"abc"
+        );
+        assert_eq!(
+            ServerInfo::read_stream_message(&mut sock).await.unwrap(),
+            b""
+        );
+        assert_eq!(
+            ServerInfo::read_stream_message(&mut sock)
+                .await
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn tls_padding() {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 853));
+        let server = ServerInfo::from(ServerConf::tls(addr, "dns.example"));
+        let mut message =
+            Query::create_message((Dname::root_slice(), Rtype::A).into());
+        server.prepare_message(&mut message);
+        assert_eq!(message.as_target().as_dgram_slice().len(), 128);
+
+        // Preparing the message again mustn’t change anything.
+        server.prepare_message(&mut message);
+        assert_eq!(message.as_target().as_dgram_slice().len(), 128);
+    }
+
+    #[cfg(feature = "tls")]
+    async fn tls_server() -> SocketAddr {
+        use tokio::net::TcpListener;
+
+        let certs = rustls_pemfile::certs(
+            &mut include_bytes!("../../../test-data/tls/server.crt").as_ref(),
+        )
+        .unwrap();
+        let key = rustls_pemfile::pkcs8_private_keys(
+            &mut include_bytes!("../../../test-data/tls/server.key").as_ref(),
+        )
+        .unwrap()
+        .remove(0);
+        let config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                certs.into_iter().map(rustls::Certificate).collect(),
+                rustls::PrivateKey(key),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (sock, _) = listener.accept().await.unwrap();
+                let mut sock = match acceptor.accept(sock).await {
+                    Ok(sock) => sock,
+                    Err(_) => continue,
+                };
+                let len = sock.read_u16().await.unwrap() as usize;
+                let mut buf = vec![0; len];
+                sock.read_exact(&mut buf).await.unwrap();
+                let request = Message::from_octets(buf).unwrap();
+                let answer =
+                    MessageBuilder::from_target(StreamTarget::new_vec())
+                        .unwrap()
+                        .start_answer(&request, Rcode::NoError)
+                        .unwrap()
+                        .finish();
+                sock.write_all(answer.as_stream_slice()).await.unwrap();
+            }
+        });
+        addr
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn tls_query() {
+        let addr = tls_server().await;
+
+        // The server’s certificate is self-signed, so strict verification
+        // has to fail.
+        let mut conf = ResolvConf::new();
+        conf.servers.push(ServerConf::tls(addr, "dns.example"));
+        conf.options.use_vc = true;
+        let resolver = StubResolver::from_conf(conf.clone());
+        assert!(resolver
+            .query((Dname::root_slice(), Rtype::A))
+            .await
+            .is_err());
+
+        conf.servers[0].tls_verify = false;
+        let resolver = StubResolver::from_conf(conf);
+        let answer = resolver
+            .query((Dname::root_slice(), Rtype::A))
+            .await
+            .unwrap();
+        assert!(answer.header().qr());
+        assert_eq!(answer.header().rcode(), Rcode::NoError);
+    }
+
+    #[cfg(not(feature = "tls"))]
+    #[tokio::test]
+    async fn tls_without_feature() {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 853));
+        let mut conf = ResolvConf::new();
+        conf.servers.push(ServerConf::tls(addr, "dns.example"));
+        conf.options.use_vc = true;
+        let resolver = StubResolver::from_conf(conf);
+        assert!(resolver
+            .query((Dname::root_slice(), Rtype::A))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,484 @@
+//! Pipelined queries over a single stream connection.
+//!
+//! RFC 7766 allows a client to send several queries over the same stream
+//! connection without waiting for the responses and a server to answer
+//! them in any order. The [`Pipeline`] type provided by this module takes
+//! care of matching responses to their queries.
+
+use crate::base::message::Message;
+use crate::base::octets::OctetsRef;
+use bytes::Bytes;
+use futures::channel::mpsc;
+use futures::future::{select, Either};
+use futures::lock::Mutex as AsyncMutex;
+use futures::stream::StreamExt;
+use std::collections::HashMap;
+use std::io;
+use std::sync::Mutex;
+use std::vec::Vec;
+use tokio::io::{
+    split, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf,
+    WriteHalf,
+};
+
+//------------ Pipeline ------------------------------------------------------
+
+/// A stream connection with multiple queries in flight.
+///
+/// Any number of tasks can call [`query`][Self::query] concurrently on a
+/// shared reference to the pipeline. Each query is sent right away. The
+/// responses are matched to their queries via the message ID and are
+/// delivered to the right query regardless of the order they arrive in.
+///
+/// Because of this, only one query with any given ID can be in flight at
+/// a time. An attempt to start a second query with the same ID fails.
+///
+/// The pipeline doesn’t spawn any tasks. Instead, one of the waiting
+/// queries reads responses from the connection and hands them over to the
+/// others. A query whose response has been handed over finishes right
+/// away even if the query currently reading is still waiting for its own
+/// response.
+///
+/// Dropping the future returned by [`query`][Self::query] cancels the
+/// query. Its ID becomes available again right away and a late response
+/// to the query is discarded. This is true even if the query was in the
+/// middle of reading a response. If the query was dropped while it was
+/// still being sent, the rest of it is sent before the next query.
+pub struct Pipeline<Sock> {
+    /// The write half of the connection.
+    ///
+    /// Whoever holds the lock writes out any pending data.
+    write: AsyncMutex<WriteState<Sock>>,
+
+    /// The read half of the connection.
+    ///
+    /// Whoever holds the lock reads the next response.
+    read: AsyncMutex<ReadState<Sock>>,
+
+    /// The IDs of the queries in flight and where to send their responses.
+    pending: Mutex<PendingMap>,
+}
+
+impl<Sock: AsyncRead + AsyncWrite> Pipeline<Sock> {
+    /// Creates a new pipeline atop a connected socket.
+    pub fn new(sock: Sock) -> Self {
+        let (read, write) = split(sock);
+        Pipeline {
+            write: AsyncMutex::new(WriteState::new(write)),
+            read: AsyncMutex::new(ReadState::new(read)),
+            pending: Default::default(),
+        }
+    }
+
+    /// Sends a query and waits for its response.
+    ///
+    /// The query is sent with the length prefix required for streams.
+    /// Responses that carry the ID of the query but are not an answer to
+    /// it are ignored.
+    ///
+    /// If another query with the same ID is currently in flight, returns
+    /// an error of kind `AlreadyExists` without sending the query.
+    pub async fn query<Octets>(
+        &self,
+        query: &Message<Octets>,
+    ) -> Result<Message<Bytes>, io::Error>
+    where
+        Octets: AsRef<[u8]>,
+        for<'a> &'a Octets: OctetsRef,
+    {
+        let id = query.header().id();
+        let (_pending, mut responses) = PendingGuard::new(&self.pending, id)?;
+        self.send(query.as_slice()).await?;
+        loop {
+            // Wait for either someone else to hand us a response or for
+            // our turn to read from the connection.
+            let mut read =
+                match select(responses.next(), self.read.lock()).await {
+                    Either::Left((Some(answer), _)) => {
+                        if answer.is_answer(query) {
+                            return Ok(answer);
+                        }
+                        continue;
+                    }
+                    Either::Left((None, _)) => {
+                        // The guard keeps the sender alive.
+                        unreachable!()
+                    }
+                    Either::Right((read, _)) => read,
+                };
+
+            // Someone else may have received our response right before
+            // we got the lock.
+            while let Ok(answer) = responses.try_recv() {
+                if answer.is_answer(query) {
+                    return Ok(answer);
+                }
+            }
+
+            let response = read.next_message().await?;
+            drop(read);
+            if let Ok(response) = Message::from_octets(Bytes::from(response))
+            {
+                if response.header().id() == id {
+                    if response.is_answer(query) {
+                        return Ok(response);
+                    }
+                } else {
+                    self.deliver(response);
+                }
+            }
+        }
+    }
+
+    /// Sends a message with its length prefix.
+    async fn send(&self, msg: &[u8]) -> Result<(), io::Error> {
+        if msg.len() > usize::from(u16::MAX) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "message too long for stream transport",
+            ));
+        }
+        let mut write = self.write.lock().await;
+        write.buf.extend_from_slice(&(msg.len() as u16).to_be_bytes());
+        write.buf.extend_from_slice(msg);
+        write.flush().await
+    }
+
+    /// Hands a response to the query with its ID.
+    ///
+    /// Responses without a matching query are dropped.
+    fn deliver(&self, response: Message<Bytes>) {
+        let pending = self.pending.lock().unwrap();
+        if let Some(sender) = pending.get(&response.header().id()) {
+            // The receiver only goes away together with the entry.
+            let _ = sender.unbounded_send(response);
+        }
+    }
+}
+
+//------------ ReadState -----------------------------------------------------
+
+/// The read half of a connection and any partially received response.
+struct ReadState<Sock> {
+    /// The read half of the connection.
+    sock: ReadHalf<Sock>,
+
+    /// The data received but not yet processed.
+    buf: Vec<u8>,
+}
+
+impl<Sock: AsyncRead> ReadState<Sock> {
+    fn new(sock: ReadHalf<Sock>) -> Self {
+        ReadState {
+            sock,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Reads the next message from the connection.
+    ///
+    /// This is cancel safe: if the returned future is dropped, all data
+    /// received so far stays in the buffer.
+    async fn next_message(&mut self) -> Result<Vec<u8>, io::Error> {
+        loop {
+            if self.buf.len() >= 2 {
+                let len = usize::from(u16::from_be_bytes([
+                    self.buf[0],
+                    self.buf[1],
+                ]));
+                if self.buf.len() >= len + 2 {
+                    let res = self.buf[2..len + 2].to_vec();
+                    self.buf.drain(..len + 2);
+                    return Ok(res);
+                }
+            }
+            if self.sock.read_buf(&mut self.buf).await? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "connection closed",
+                ));
+            }
+        }
+    }
+}
+
+//------------ WriteState ----------------------------------------------------
+
+/// The write half of a connection and any data not yet sent.
+struct WriteState<Sock> {
+    /// The write half of the connection.
+    sock: WriteHalf<Sock>,
+
+    /// The data still to be sent.
+    ///
+    /// Messages are added here in full before writing, so that a partially
+    /// sent message is completed by the next writer.
+    buf: Vec<u8>,
+}
+
+impl<Sock: AsyncWrite> WriteState<Sock> {
+    fn new(sock: WriteHalf<Sock>) -> Self {
+        WriteState {
+            sock,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Writes out all data in the buffer.
+    ///
+    /// This is cancel safe: if the returned future is dropped, all data
+    /// not yet written stays in the buffer.
+    async fn flush(&mut self) -> Result<(), io::Error> {
+        while !self.buf.is_empty() {
+            let len = self.sock.write(&self.buf).await?;
+            if len == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            self.buf.drain(..len);
+        }
+        self.sock.flush().await
+    }
+}
+
+//------------ PendingGuard --------------------------------------------------
+
+/// The queries in flight and the senders for handing over their responses.
+type PendingMap = HashMap<u16, mpsc::UnboundedSender<Message<Bytes>>>;
+
+/// Registers the ID of a query in flight for as long as it lives.
+struct PendingGuard<'a> {
+    pending: &'a Mutex<PendingMap>,
+    id: u16,
+}
+
+impl<'a> PendingGuard<'a> {
+    /// Registers `id` and returns the guard and the receiving end for the
+    /// responses handed over by other queries.
+    fn new(
+        pending: &'a Mutex<PendingMap>,
+        id: u16,
+    ) -> Result<(Self, mpsc::UnboundedReceiver<Message<Bytes>>), io::Error>
+    {
+        let mut map = pending.lock().unwrap();
+        if map.contains_key(&id) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "query with this ID already in flight",
+            ));
+        }
+        let (tx, rx) = mpsc::unbounded();
+        map.insert(id, tx);
+        Ok((PendingGuard { pending, id }, rx))
+    }
+}
+
+impl<'a> Drop for PendingGuard<'a> {
+    fn drop(&mut self) {
+        self.pending.lock().unwrap().remove(&self.id);
+    }
+}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+mod test {
+    use super::super::ServerInfo;
+    use super::*;
+    use crate::base::iana::{Rcode, Rtype};
+    use crate::base::message_builder::{MessageBuilder, StreamTarget};
+    use crate::base::name::Dname;
+    use std::time::Duration;
+    use tokio::io::{duplex, DuplexStream};
+    use tokio::time::timeout;
+
+    fn query(id: u16, qname: &str) -> Message<Vec<u8>> {
+        let mut msg = MessageBuilder::new_vec();
+        msg.header_mut().set_id(id);
+        let mut msg = msg.question();
+        msg.push((Dname::vec_from_str(qname).unwrap(), Rtype::A))
+            .unwrap();
+        msg.into_message()
+    }
+
+    /// Reads `count` queries and then answers them in reverse order.
+    async fn reverse_server(mut sock: DuplexStream, count: usize) {
+        let mut queries = Vec::new();
+        for _ in 0..count {
+            let buf =
+                ServerInfo::read_stream_message(&mut sock).await.unwrap();
+            queries.push(Message::from_octets(buf).unwrap());
+        }
+        for query in queries.iter().rev() {
+            let answer = MessageBuilder::from_target(StreamTarget::new_vec())
+                .unwrap()
+                .start_answer(query, Rcode::NoError)
+                .unwrap()
+                .finish();
+            sock.write_all(answer.as_stream_slice()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn out_of_order() {
+        let (client, server) = duplex(4096);
+        let pipeline = Pipeline::new(client);
+        let queries = [
+            query(1, "a.example"),
+            query(2, "b.example"),
+            query(3, "c.example"),
+        ];
+        let (_, a, b, c) = futures::join!(
+            reverse_server(server, 3),
+            pipeline.query(&queries[0]),
+            pipeline.query(&queries[1]),
+            pipeline.query(&queries[2]),
+        );
+        for (answer, query) in [a, b, c].iter().zip(queries.iter()) {
+            let answer = answer.as_ref().unwrap();
+            assert!(answer.is_answer(query));
+            assert_eq!(answer.header().id(), query.header().id());
+        }
+        assert!(pipeline.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn duplicate_id() {
+        let (client, server) = duplex(4096);
+        let pipeline = Pipeline::new(client);
+        let first = query(7, "a.example");
+        let second = query(7, "b.example");
+        let (_, first, second) = futures::join!(
+            reverse_server(server, 1),
+            pipeline.query(&first),
+            pipeline.query(&second),
+        );
+        assert!(first.unwrap().header().qr());
+        assert_eq!(
+            second.err().unwrap().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel() {
+        let (client, mut server) = duplex(4096);
+        let pipeline = Pipeline::new(client);
+        let first = query(5, "a.example");
+        let second = query(5, "b.example");
+
+        // The server starts sending the answer to the first query and
+        // finishes it only after it received the second query.
+        let server = async move {
+            let buf =
+                ServerInfo::read_stream_message(&mut server).await.unwrap();
+            let query = Message::from_octets(buf).unwrap();
+            let stale = MessageBuilder::from_target(StreamTarget::new_vec())
+                .unwrap()
+                .start_answer(&query, Rcode::NoError)
+                .unwrap()
+                .finish();
+            let stale = stale.as_stream_slice();
+            server.write_all(&stale[..5]).await.unwrap();
+
+            let buf =
+                ServerInfo::read_stream_message(&mut server).await.unwrap();
+            let query = Message::from_octets(buf).unwrap();
+            let answer = MessageBuilder::from_target(StreamTarget::new_vec())
+                .unwrap()
+                .start_answer(&query, Rcode::NoError)
+                .unwrap()
+                .finish();
+            server.write_all(&stale[5..]).await.unwrap();
+            server.write_all(answer.as_stream_slice()).await.unwrap();
+        };
+
+        let client = async {
+            let res =
+                timeout(Duration::from_millis(50), pipeline.query(&first))
+                    .await;
+            assert!(res.is_err());
+            assert!(pipeline.pending.lock().unwrap().is_empty());
+            pipeline.query(&second).await
+        };
+
+        let (_, answer) = futures::join!(server, client);
+        let answer = answer.ok().unwrap();
+        assert!(answer.is_answer(&second));
+        assert!(pipeline.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn handed_over_while_reading() {
+        let (client, mut server) = duplex(4096);
+        let pipeline = Pipeline::new(client);
+        let slow = query(1, "a.example");
+        let fast = query(2, "b.example");
+        let (done_tx, done_rx) = futures::channel::oneshot::channel();
+
+        // The server only answers the second query and keeps the
+        // connection open until that query has finished.
+        let server = async move {
+            let _ = ServerInfo::read_stream_message(&mut server).await;
+            let buf =
+                ServerInfo::read_stream_message(&mut server).await.unwrap();
+            let query = Message::from_octets(buf).unwrap();
+            let answer = MessageBuilder::from_target(StreamTarget::new_vec())
+                .unwrap()
+                .start_answer(&query, Rcode::NoError)
+                .unwrap()
+                .finish();
+            server.write_all(answer.as_stream_slice()).await.unwrap();
+            let _ = done_rx.await;
+        };
+
+        // The slow query is polled first and thus reads the answer to the
+        // fast query while waiting for its own. The fast query has to
+        // finish long before the slow one gives up.
+        let slow_query =
+            timeout(Duration::from_millis(500), pipeline.query(&slow));
+        let fast_query = async {
+            let res =
+                timeout(Duration::from_millis(100), pipeline.query(&fast))
+                    .await;
+            let _ = done_tx.send(());
+            res
+        };
+        let (_, slow, fast) = futures::join!(server, slow_query, fast_query);
+        let fast = fast.unwrap().unwrap();
+        assert!(fast.is_answer(&query(2, "b.example")));
+        assert!(!matches!(slow, Ok(Ok(_))));
+    }
+
+    #[tokio::test]
+    async fn cancel_while_sending() {
+        // The connection only buffers a few octets, so sending stalls in
+        // the middle of the first query until the server reads.
+        let (client, mut server) = duplex(8);
+        let pipeline = Pipeline::new(client);
+        let first = query(5, "a.example");
+        let second = query(6, "b.example");
+
+        let res =
+            timeout(Duration::from_millis(50), pipeline.query(&first)).await;
+        assert!(res.is_err());
+
+        // Both queries arrive intact and the second one is answered.
+        let server = async move {
+            let buf =
+                ServerInfo::read_stream_message(&mut server).await.unwrap();
+            assert_eq!(buf.as_slice(), first.as_slice());
+            let buf =
+                ServerInfo::read_stream_message(&mut server).await.unwrap();
+            let query = Message::from_octets(buf).unwrap();
+            assert_eq!(query.header().id(), 6);
+            let answer = MessageBuilder::from_target(StreamTarget::new_vec())
+                .unwrap()
+                .start_answer(&query, Rcode::NoError)
+                .unwrap()
+                .finish();
+            server.write_all(answer.as_stream_slice()).await.unwrap();
+        };
+        let (_, answer) = futures::join!(server, pipeline.query(&second));
+        assert!(answer.unwrap().is_answer(&second));
+    }
+}
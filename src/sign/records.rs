@@ -7,7 +7,7 @@ use crate::base::name::ToDname;
 use crate::base::octets::{Compose, EmptyBuilder, FromBuilder};
 use crate::base::rdata::RecordData;
 use crate::base::record::Record;
-use crate::base::serial::Serial;
+use crate::base::serial::Timestamp;
 use crate::rdata::rfc4034::{ProtoRrsig, RtypeBitmap};
 use crate::rdata::{Dnskey, Ds, Nsec, Rrsig};
 use std::iter::FromIterator;
@@ -71,8 +71,8 @@ impl<N, D> SortedRecords<N, D> {
     pub fn sign<Octets, Key, ApexName>(
         &self,
         apex: &FamilyName<ApexName>,
-        expiration: Serial,
-        inception: Serial,
+        expiration: Timestamp,
+        inception: Timestamp,
         key: Key,
     ) -> Result<Vec<Record<N, Rrsig<Octets, ApexName>>>, Key::Error>
     where
@@ -1408,6 +1408,9 @@ impl Variables {
                 other,
             ),
         ))
+        // A TSIG record isn’t an OPT record, so running out of space is the
+        // only possible error.
+        .map_err(|_| ShortBuf)
     }
 
     /// Applies the variables to a signing context.
@@ -1656,7 +1659,8 @@ impl<K: AsRef<Key>> ServerError<K> {
                         error,
                         b"",
                     ),
-                ))?;
+                ))
+                .map_err(|_| ShortBuf)?;
             }
             ServerErrorInner::Signed { context, variables } => {
                 let (mac, key) = context.final_answer(
@@ -0,0 +1,212 @@
+//! Decoding and encoding of hexadecimal data.
+//!
+//! Hex encoding, also known as *base16* and defined in [RFC 4648], is used
+//! in the presentation format of a number of record types such as [DS] to
+//! represent binary data. Each octet is represented by two hex digits.
+//!
+//! The module provides the convenience functions [`parse`] and
+//! [`display`] which decode and encode octets, respectively, as well as
+//! the [`HexStr`] type that wraps an octets slice and displays it in hex.
+//!
+//! Since the data can be quite long, it is often broken up into multiple
+//! lines in master files. When decoding, [`parse`] therefore skips over
+//! any whitespace between the hex digits. When encoding, upper case hex
+//! digits are used throughout.
+//!
+//! [RFC 4648]: https://tools.ietf.org/html/rfc4648
+//! [DS]: crate::rdata::rfc4034::Ds
+
+use crate::base::octets::{EmptyBuilder, OctetsBuilder};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::string::String;
+
+//------------ Convenience Functions -----------------------------------------
+
+/// Parses a string with hex encoded data.
+///
+/// The function attempts to decode the entire string and returns the
+/// result in a newly created octets builder. Both upper and lower case hex
+/// digits are accepted. Whitespace anywhere in the string, even between
+/// the two digits of an octet, is ignored.
+pub fn parse<Target>(s: &str) -> Result<Target, DecodeError>
+where
+    Target: EmptyBuilder + OctetsBuilder,
+{
+    let mut target = Target::with_capacity(s.len() / 2);
+    let mut first: Option<(usize, u8)> = None;
+    for (pos, ch) in s.char_indices() {
+        if ch.is_whitespace() {
+            continue;
+        }
+        let value = match ch.to_digit(16) {
+            Some(value) => value as u8,
+            None => return Err(DecodeError::IllegalChar(ch, pos)),
+        };
+        match first.take() {
+            Some((_, high)) => target
+                .append_slice(&[high << 4 | value])
+                .map_err(|_| DecodeError::ShortBuf)?,
+            None => first = Some((pos, value)),
+        }
+    }
+    match first {
+        Some((pos, _)) => Err(DecodeError::OddLength(pos)),
+        None => Ok(target),
+    }
+}
+
+/// Encodes binary data in hex and writes it into a format stream.
+///
+/// Upper case hex digits are used and no whitespace is added. This function
+/// is intended to be used in implementations of formatting traits:
+///
+/// ```
+/// use core::fmt;
+/// use domain::utils::hex;
+///
+/// struct Foo<'a>(&'a [u8]);
+///
+/// impl<'a> fmt::Display for Foo<'a> {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         hex::display(&self.0, f)
+///     }
+/// }
+/// ```
+pub fn display<B, W>(bytes: &B, f: &mut W) -> fmt::Result
+where
+    B: AsRef<[u8]> + ?Sized,
+    W: fmt::Write,
+{
+    for ch in bytes.as_ref() {
+        write!(f, "{:02X}", ch)?
+    }
+    Ok(())
+}
+
+/// Encodes binary data in hex and returns the encoded data as a string.
+#[cfg(feature = "std")]
+pub fn encode_string<B: AsRef<[u8]> + ?Sized>(bytes: &B) -> String {
+    let mut res = String::with_capacity(bytes.as_ref().len() * 2);
+    display(bytes, &mut res).unwrap();
+    res
+}
+
+//------------ HexStr --------------------------------------------------------
+
+/// An octets slice displayed in hex.
+///
+/// This is a small wrapper that allows formatting binary data in hex
+/// consistently via both its `Display` and `Debug` implementations. It is
+/// used by record data types that show part of their data in hex in their
+/// presentation format.
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct HexStr<'a>(&'a [u8]);
+
+impl<'a> HexStr<'a> {
+    /// Creates a new value wrapping the given slice.
+    pub fn new(slice: &'a [u8]) -> Self {
+        HexStr(slice)
+    }
+
+    /// Returns the wrapped slice.
+    pub fn as_slice(self) -> &'a [u8] {
+        self.0
+    }
+}
+
+//--- Display and Debug
+
+impl<'a> fmt::Display for HexStr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display(self.0, f)
+    }
+}
+
+impl<'a> fmt::Debug for HexStr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("HexStr(")?;
+        display(self.0, f)?;
+        f.write_str(")")
+    }
+}
+
+//============ Error Types ===================================================
+
+//------------ DecodeError ---------------------------------------------------
+
+/// An error happened while decoding hex data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// A character that isn’t a hex digit was found.
+    ///
+    /// The character and its byte position in the input are given.
+    IllegalChar(char, usize),
+
+    /// The input contained an odd number of hex digits.
+    ///
+    /// The byte position of the last, unpaired digit is given.
+    OddLength(usize),
+
+    /// The decoded data didn’t fit into the octets builder.
+    ShortBuf,
+}
+
+//--- Display and Error
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::IllegalChar(ch, pos) => {
+                write!(f, "illegal character '{}' at position {}", ch, pos)
+            }
+            DecodeError::OddLength(pos) => {
+                write!(f, "unpaired hex digit at position {}", pos)
+            }
+            DecodeError::ShortBuf => f.write_str("buffer size exceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+//============ Testing =======================================================
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+    use std::format;
+    use std::vec::Vec;
+
+    #[test]
+    fn parse_str() {
+        assert_eq!(parse::<Vec<u8>>("").unwrap(), b"");
+        assert_eq!(parse::<Vec<u8>>("00fF7a").unwrap(), b"\x00\xff\x7a");
+        assert_eq!(
+            parse::<Vec<u8>>(
+                "2BB183AF5F22588179A53B0A\n\t98631FAD1A292118\n"
+            )
+            .unwrap(),
+            b"\x2b\xb1\x83\xaf\x5f\x22\x58\x81\x79\xa5\x3b\x0a\
+              \x98\x63\x1f\xad\x1a\x29\x21\x18"
+        );
+        assert_eq!(parse::<Vec<u8>>(" a b ").unwrap(), b"\xab");
+        assert_eq!(
+            parse::<Vec<u8>>("ab cd\ne"),
+            Err(DecodeError::OddLength(6))
+        );
+        assert_eq!(
+            parse::<Vec<u8>>("abxd"),
+            Err(DecodeError::IllegalChar('x', 2))
+        );
+    }
+
+    #[test]
+    fn display_hex() {
+        assert_eq!(encode_string(b"\x00\xff\x7a"), "00FF7A");
+        assert_eq!(format!("{}", HexStr::new(b"\x0a\xbc")), "0ABC");
+        assert_eq!(format!("{:?}", HexStr::new(b"\x0a\xbc")), "HexStr(0ABC)");
+    }
+}
@@ -9,7 +9,7 @@ use crate::base::name::ToDname;
 use crate::base::octets::{Compose, OctetsBuilder, ShortBuf};
 use crate::base::rdata::RecordData;
 use crate::base::record::Record;
-use crate::rdata::{Dnskey, Rrsig};
+use crate::rdata::{Dnskey, Ds, Rrsig};
 use ring::{digest, signature};
 use std::vec::Vec;
 use std::{error, fmt};
@@ -90,6 +90,35 @@ where
     }
 }
 
+//------------ Ds ------------------------------------------------------------
+
+impl<Octets: From<Vec<u8>>> Ds<Octets> {
+    /// Creates the DS record data for a DNSKEY.
+    ///
+    /// The digest is calculated via [`DnskeyExt::digest`] from the
+    /// canonical form of `owner`, the owner name of the DNSKEY record,
+    /// and the record data of `key` using the digest algorithm given in
+    /// `digest_type`. Returns an error if this digest algorithm isn’t
+    /// supported.
+    pub fn from_dnskey<N, K>(
+        owner: &N,
+        key: &Dnskey<K>,
+        digest_type: DigestAlg,
+    ) -> Result<Self, AlgorithmError>
+    where
+        N: ToDname,
+        K: AsRef<[u8]>,
+    {
+        let digest = key.digest(owner, digest_type)?;
+        Ok(Ds::new(
+            key.key_tag(),
+            key.algorithm(),
+            digest_type,
+            Vec::from(digest.as_ref()).into(),
+        ))
+    }
+}
+
 //------------ Rrsig ---------------------------------------------------------
 
 /// Extensions for DNSKEY record type.
@@ -334,8 +363,8 @@ impl error::Error for AlgorithmError {}
 mod test {
     use super::*;
     use crate::base::iana::{Class, Rtype, SecAlg};
-    use crate::base::serial::Serial;
-    use crate::master::scan::Scanner;
+    use crate::base::serial::Timestamp;
+    use crate::master::scan::{Scan, Scanner};
     use crate::rdata::{MasterRecordData, Mx};
     use crate::utils::base64;
     use bytes::Bytes;
@@ -401,6 +430,27 @@ mod test {
         assert_eq!(dnskey.digest(&owner, DigestAlg::Gost).is_err(), true);
     }
 
+    #[test]
+    fn ds_from_dnskey() {
+        let (dnskey, _) = root_pubkey();
+        let expected = Ds::new(
+            20326,
+            SecAlg::RsaSha256,
+            DigestAlg::Sha256,
+            base64::decode("4G1EuAuPHTmpXAsNfGXQhFjogECbvGg0VxBCN8f47I0=")
+                .unwrap(),
+        );
+        assert_eq!(
+            Ds::from_dnskey(&Dname::root(), &dnskey, DigestAlg::Sha256)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            Ds::from_dnskey(&Dname::root(), &dnskey, DigestAlg::Gost),
+            Err(AlgorithmError::Unsupported)
+        );
+    }
+
     fn rrsig_verify_dnskey(ksk: Dnskey, zsk: Dnskey, rrsig: Rrsig) {
         let mut records: Vec<_> = [&ksk, &zsk]
             .iter()
@@ -599,9 +649,9 @@ mod test {
     }
 
     // Parse RRSIG serial from text.
-    fn rrsig_serial(x: &str) -> Serial {
+    fn rrsig_serial(x: &str) -> Timestamp {
         let mut s = Scanner::new(x);
-        Serial::scan_rrsig(&mut s).unwrap()
+        Timestamp::scan(&mut s).unwrap()
     }
 
     #[test]